@@ -5,17 +5,38 @@ use std::path::PathBuf;
 
 use super::drops::DropsCommand;
 use super::idb::ItemsDbCommand;
+use super::item::ItemCommand;
 use super::memory::MemoryAction;
 use super::ncs::NcsCommand;
+use super::parts::PartsCommand;
 #[cfg(feature = "research")]
 use super::research::{ExtractCommand, UsmapCommand};
 use super::save::SaveArgs;
 use super::serial::SerialCommand;
 
+/// Command overview shown under `bl4 --help`
+const COMMAND_GROUPS: &str = "\
+Command groups:
+  Saves      save, inspect, configure
+  Items      serial, item, parts, drops, idb
+  Game data  ncs, usmap, extract, manifest (usmap/extract/manifest need 'research')
+  Runtime    memory, launch
+
+Run `bl4 <command> --help` for an example of each command,
+or `bl4 examples` for end-to-end recipes.
+
+Exit codes: 1 error, 2 file not found, 3 parse error, 4 validation failed,
+64 usage error.";
+
 #[derive(Parser)]
 #[command(name = "bl4")]
 #[command(about = "Borderlands 4 Save Editor", long_about = None)]
+#[command(after_help = COMMAND_GROUPS)]
 pub struct Cli {
+    /// Report failures on stderr as `{"error": ..., "code": N}` JSON
+    #[arg(long, global = true)]
+    pub json_errors: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -23,14 +44,20 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Save file operations (decrypt, encrypt, edit, get, set)
-    #[command(visible_alias = "s")]
+    #[command(
+        visible_alias = "s",
+        after_help = "Example:\n  bl4 save 1.sav get --level"
+    )]
     Save {
         #[command(flatten)]
         args: SaveArgs,
     },
 
     /// Inspect a save file (decrypt and display info)
-    #[command(visible_alias = "i")]
+    #[command(
+        visible_alias = "i",
+        after_help = "Example:\n  bl4 inspect 1.sav --full"
+    )]
     Inspect {
         /// Path to .sav file
         input: PathBuf,
@@ -45,7 +72,10 @@ pub enum Commands {
     },
 
     /// Configure default settings
-    #[command(visible_alias = "c")]
+    #[command(
+        visible_alias = "c",
+        after_help = "Example:\n  bl4 configure --steam-id 76561197960521364"
+    )]
     Configure {
         /// Set default Steam ID
         #[arg(long)]
@@ -63,66 +93,27 @@ pub enum Commands {
         command: SerialCommand,
     },
 
-    /// Query parts database - find parts for a weapon type
-    #[command(visible_alias = "p")]
-    Parts {
-        /// Weapon name (e.g. "Jakobs Pistol", "Vladof SMG")
-        #[arg(short, long)]
-        weapon: Option<String>,
-
-        /// Category ID (e.g. 3 for Jakobs Pistol)
-        #[arg(short, long)]
-        category: Option<i64>,
-
-        /// List all categories
-        #[arg(short, long)]
-        list: bool,
-
-        /// Path to parts database (directory of per-category TSVs or single file)
-        #[arg(long, default_value = "share/manifest/parts")]
-        parts_db: PathBuf,
-    },
-
-    /// Read/analyze game memory (live process or dump file)
-    #[command(visible_alias = "m")]
-    Memory {
-        /// Use preload-based injection (requires game launched with LD_PRELOAD)
-        #[arg(long)]
-        preload: bool,
-
-        /// Read from memory dump file instead of live process (for offline analysis)
-        #[arg(long, short = 'd')]
-        dump: Option<PathBuf>,
-
-        /// Path to maps file for dump (optional, defaults to <dump>.maps)
-        #[arg(long)]
-        maps: Option<PathBuf>,
-
+    /// Item operations (describe)
+    Item {
         #[command(subcommand)]
-        action: MemoryAction,
-    },
-
-    /// Launch Borderlands 4 with instrumentation
-    #[command(visible_alias = "l")]
-    Launch {
-        /// Skip confirmation prompt
-        #[arg(short = 'y', long)]
-        yes: bool,
+        command: ItemCommand,
     },
 
-    /// Usmap file utilities (requires 'research' feature)
-    #[cfg(feature = "research")]
-    Usmap {
+    /// Query parts database (list, show, merge, build-sqlite)
+    #[command(
+        visible_alias = "p",
+        after_help = "Example:\n  bl4 parts show \"Jakobs Pistol\""
+    )]
+    Parts {
         #[command(subcommand)]
-        command: UsmapCommand,
+        command: PartsCommand,
     },
 
-    /// Data extraction utilities (requires 'research' feature)
-    #[cfg(feature = "research")]
-    #[command(visible_alias = "e")]
-    Extract {
+    /// Query drop rates and locations for legendary items
+    #[command(visible_alias = "d")]
+    Drops {
         #[command(subcommand)]
-        command: ExtractCommand,
+        command: DropsCommand,
     },
 
     /// Manage the verified items database
@@ -142,11 +133,19 @@ pub enum Commands {
         command: NcsCommand,
     },
 
-    /// Query drop rates and locations for legendary items
-    #[command(visible_alias = "d")]
-    Drops {
+    /// Usmap file utilities (requires 'research' feature)
+    #[cfg(feature = "research")]
+    Usmap {
         #[command(subcommand)]
-        command: DropsCommand,
+        command: UsmapCommand,
+    },
+
+    /// Data extraction utilities (requires 'research' feature)
+    #[cfg(feature = "research")]
+    #[command(visible_alias = "e")]
+    Extract {
+        #[command(subcommand)]
+        command: ExtractCommand,
     },
 
     /// Generate manifest files from game data (requires 'research' feature)
@@ -200,4 +199,34 @@ pub enum Commands {
         #[arg(long, requires = "oodle_exec")]
         oodle_fifo: bool,
     },
+
+    /// Read/analyze game memory (live process or dump file)
+    #[command(visible_alias = "m")]
+    Memory {
+        /// Use preload-based injection (requires game launched with LD_PRELOAD)
+        #[arg(long)]
+        preload: bool,
+
+        /// Read from memory dump file instead of live process (for offline analysis)
+        #[arg(long, short = 'd')]
+        dump: Option<PathBuf>,
+
+        /// Path to maps file for dump (optional, defaults to <dump>.maps)
+        #[arg(long)]
+        maps: Option<PathBuf>,
+
+        #[command(subcommand)]
+        action: MemoryAction,
+    },
+
+    /// Launch Borderlands 4 with instrumentation
+    #[command(visible_alias = "l")]
+    Launch {
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Print common end-to-end recipes
+    Examples,
 }
@@ -0,0 +1,19 @@
+//! Item command CLI definitions
+
+use clap::Subcommand;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum ItemCommand {
+    /// Describe an item: manufacturer, type, rarity, legendary name, and named parts
+    #[command(after_help = "Example:\n  bl4 item describe '@Ugr$ZCm/&tH!t{KgK/Shxu>k'")]
+    Describe {
+        /// Item serial to describe
+        serial: String,
+
+        /// Path to parts database (directory of per-category TSVs or single file).
+        /// Defaults to $BL4_PARTS_DB, then $XDG_DATA_HOME/bl4/parts, then the embedded database
+        #[arg(long)]
+        parts_db: Option<PathBuf>,
+    },
+}
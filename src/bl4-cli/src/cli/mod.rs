@@ -5,8 +5,10 @@
 mod core;
 mod drops;
 mod idb;
+mod item;
 mod memory;
 mod ncs;
+mod parts;
 #[cfg(feature = "research")]
 mod research;
 mod save;
@@ -15,9 +17,158 @@ mod serial;
 pub use core::{Cli, Commands};
 pub use drops::DropsCommand;
 pub use idb::{ItemsDbCommand, OutputFormat};
+pub use item::ItemCommand;
 pub use memory::{MemoryAction, PreloadAction};
-pub use ncs::NcsCommand;
+pub use ncs::{DocumentFormat, NcsCommand};
+pub use parts::PartsCommand;
 #[cfg(feature = "research")]
 pub use research::{ExtractCommand, UsmapCommand};
 pub use save::{MapAction, SaveAction, SaveArgs};
 pub use serial::SerialCommand;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, Parser};
+
+    fn parse(args: &[&str]) -> Commands {
+        let mut argv = vec!["bl4"];
+        argv.extend_from_slice(args);
+        Cli::try_parse_from(argv).unwrap().command
+    }
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_representative_commands() {
+        let Commands::Save { args } = parse(&["save", "1.sav", "get", "--level"]) else {
+            panic!("expected save command");
+        };
+        assert!(matches!(
+            args.action,
+            Some(SaveAction::Get { level: true, .. })
+        ));
+        let Commands::Save { args } = parse(&["save", "1.sav", "decrypt", "-s", "7656"]) else {
+            panic!("expected save command");
+        };
+        assert_eq!(args.steam_id.as_deref(), Some("7656"));
+        assert!(matches!(
+            parse(&["save", "1.sav", "summary"]),
+            Commands::Save {
+                args: SaveArgs {
+                    action: Some(SaveAction::Summary),
+                    ..
+                }
+            }
+        ));
+        assert!(matches!(
+            parse(&["parts", "show", "Jakobs Pistol"]),
+            Commands::Parts {
+                command: PartsCommand::Show {
+                    weapon: Some(_),
+                    category: None,
+                    parts_db: None,
+                },
+            }
+        ));
+        assert!(matches!(
+            parse(&["parts", "list", "--parts-db", "parts.db"]),
+            Commands::Parts {
+                command: PartsCommand::List { parts_db: Some(_) },
+            }
+        ));
+        assert!(matches!(
+            parse(&[
+                "ncs",
+                "extract",
+                "./ncs_output/",
+                "-t",
+                "manifest",
+                "--json"
+            ]),
+            Commands::Ncs {
+                command: NcsCommand::Extract { json: true, .. }
+            }
+        ));
+        assert!(matches!(
+            parse(&["ncs", "deps", "./ncs_output/", "--dot"]),
+            Commands::Ncs {
+                command: NcsCommand::Deps {
+                    dot: true,
+                    json: false,
+                    ..
+                }
+            }
+        ));
+        assert!(matches!(
+            parse(&["ncs", "show", "inv0.bin", "--format", "csv"]),
+            Commands::Ncs {
+                command: NcsCommand::Show {
+                    format: Some(DocumentFormat::Csv),
+                    ..
+                }
+            }
+        ));
+        assert!(matches!(
+            parse(&["ncs", "decompress", "game.pak"]),
+            Commands::Ncs {
+                command: NcsCommand::Decompress {
+                    format: DocumentFormat::Tsv,
+                    ..
+                }
+            }
+        ));
+        assert!(matches!(
+            parse(&["item", "describe", "@Ugr$ZCm/&tH!t{KgK/Shxu>k"]),
+            Commands::Item {
+                command: ItemCommand::Describe { .. }
+            }
+        ));
+        assert!(matches!(parse(&["examples"]), Commands::Examples));
+    }
+
+    #[test]
+    fn test_parts_subcommands() {
+        assert!(matches!(
+            parse(&["parts", "build-sqlite", "parts/", "--out", "parts.db"]),
+            Commands::Parts {
+                command: PartsCommand::BuildSqlite { .. },
+            }
+        ));
+        assert!(matches!(
+            parse(&["parts", "merge", "parts/"]),
+            Commands::Parts {
+                command: PartsCommand::Merge { output: None, .. },
+            }
+        ));
+        assert!(matches!(
+            parse(&["parts", "show", "-c", "3"]),
+            Commands::Parts {
+                command: PartsCommand::Show {
+                    weapon: None,
+                    category: Some(3),
+                    ..
+                },
+            }
+        ));
+        assert!(Cli::try_parse_from(["bl4", "parts", "build-sqlite", "parts/"]).is_err());
+        assert!(Cli::try_parse_from(["bl4", "parts", "show"]).is_err());
+        assert!(Cli::try_parse_from(["bl4", "parts", "show", "Jakobs", "-c", "3"]).is_err());
+        assert!(Cli::try_parse_from(["bl4", "parts", "--weapon", "Jakobs"]).is_err());
+        // Merge and build-sqlite read TSVs directly, so they take no --parts-db
+        assert!(
+            Cli::try_parse_from(["bl4", "parts", "merge", "parts/", "--parts-db", "p.db"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_top_level_help_lists_groups() {
+        let help = Cli::command().render_help().to_string();
+        for group in ["Saves", "Items", "Game data", "Runtime", "bl4 examples"] {
+            assert!(help.contains(group), "missing {:?} in help", group);
+        }
+    }
+}
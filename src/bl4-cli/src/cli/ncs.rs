@@ -3,9 +3,33 @@
 use clap::Subcommand;
 use std::path::PathBuf;
 
+/// How a parsed document is rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocumentFormat {
+    /// `# table` lines followed by `record<TAB>key<TAB>value` rows
+    #[default]
+    Tsv,
+    /// `table,record,key,value` rows with a header line
+    Csv,
+    /// Pretty-printed JSON
+    Json,
+}
+
+impl DocumentFormat {
+    /// File extension for output files in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Tsv => "tsv",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum NcsCommand {
     /// Scan a directory of decompressed NCS files and list types
+    #[command(after_help = "Example:\n  bl4 ncs scan ./ncs_output/ --json")]
     Scan {
         /// Directory containing decompressed .bin files
         path: PathBuf,
@@ -24,6 +48,7 @@ pub enum NcsCommand {
     },
 
     /// Show content of a specific NCS file
+    #[command(after_help = "Example:\n  bl4 ncs show inv0.bin --format csv")]
     Show {
         /// Path to decompressed NCS file
         path: PathBuf,
@@ -40,12 +65,43 @@ pub enum NcsCommand {
         #[arg(long)]
         json: bool,
 
-        /// Output as TSV (tab-separated values)
+        /// Output as TSV (tab-separated values); same as --format tsv
         #[arg(long)]
         tsv: bool,
+
+        /// Output the parsed document as tsv, csv or json
+        #[arg(long, conflicts_with_all = ["json", "tsv"])]
+        format: Option<DocumentFormat>,
+    },
+
+    /// Show one record: tags, entries with refs resolved, parsed serial indices
+    #[command(after_help = "Example:\n  bl4 ncs record inv0.bin --table inv --index 12")]
+    Record {
+        /// Path to NCS file (compressed or decompressed)
+        path: PathBuf,
+
+        /// Record index within the table (the N in `record_N` from `ncs show`)
+        #[arg(short, long)]
+        index: usize,
+
+        /// Table to read from; required when the file has more than one
+        #[arg(short, long)]
+        table: Option<String>,
+    },
+
+    /// Show values unpacked from composite strings (e.g. "1airship", "5true")
+    #[command(after_help = "Example:\n  bl4 ncs packed inv0.bin --json")]
+    Packed {
+        /// Path to NCS file (compressed or decompressed)
+        path: PathBuf,
+
+        /// Output as JSON ({ original: [values] })
+        #[arg(long)]
+        json: bool,
     },
 
     /// Search for NCS files containing a pattern
+    #[command(after_help = "Example:\n  bl4 ncs search ./ncs_output/ weapon_ps")]
     Search {
         /// Directory to search
         path: PathBuf,
@@ -53,16 +109,32 @@ pub enum NcsCommand {
         /// Pattern to search for (case-insensitive)
         pattern: String,
 
-        /// Search in all strings, not just entry names
+        /// Search in all strings, not just entry names, guids and numeric fields
         #[arg(short, long)]
         all: bool,
 
         /// Maximum results to show
         #[arg(short = 'n', long, default_value = "20")]
         limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Map every GUID in a directory of NCS files to its entry or display name
+    #[command(after_help = "Example:\n  bl4 ncs guids ./ncs_output/ --json > guids.json")]
+    Guids {
+        /// Directory containing NCS files
+        path: PathBuf,
+
+        /// Output as JSON ({ guids: {guid: name}, collisions: [...] })
+        #[arg(long)]
+        json: bool,
     },
 
     /// Extract specific data types from NCS files
+    #[command(after_help = "Example:\n  bl4 ncs extract ./ncs_output/ -t manifest --json")]
     Extract {
         /// Directory containing decompressed NCS files
         path: PathBuf,
@@ -81,6 +153,7 @@ pub enum NcsCommand {
     },
 
     /// Show statistics about NCS files
+    #[command(after_help = "Example:\n  bl4 ncs stats ./ncs_output/ --formats")]
     Stats {
         /// Directory containing decompressed NCS files
         path: PathBuf,
@@ -90,7 +163,23 @@ pub enum NcsCommand {
         formats: bool,
     },
 
+    /// Show which NCS types depend on which (load order, cycles, missing tables)
+    #[command(after_help = "Example:\n  bl4 ncs deps ./ncs_output/ --dot > deps.dot")]
+    Deps {
+        /// Directory containing NCS files
+        path: PathBuf,
+
+        /// Output the graph as JSON
+        #[arg(long, conflicts_with = "dot")]
+        json: bool,
+
+        /// Output the graph as Graphviz DOT
+        #[arg(long)]
+        dot: bool,
+    },
+
     /// Decompress NCS data from a pak file or raw NCS
+    #[command(after_help = "Example:\n  bl4 ncs decompress game.pak -o ./ncs_output/ --raw")]
     Decompress {
         /// Input file (pak file or raw NCS)
         input: PathBuf,
@@ -103,10 +192,14 @@ pub enum NcsCommand {
         #[arg(long)]
         offset: Option<usize>,
 
-        /// Output raw binary instead of parsed TSV
+        /// Output raw binary instead of parsed documents
         #[arg(long)]
         raw: bool,
 
+        /// Format for parsed documents: tsv (default), csv, json
+        #[arg(long, default_value = "tsv", conflicts_with = "raw")]
+        format: DocumentFormat,
+
         /// Path to Oodle DLL for native decompression (Windows only)
         ///
         /// Load the official Oodle DLL (e.g., oo2core_9_win64.dll) for full
@@ -135,6 +228,7 @@ pub enum NcsCommand {
     },
 
     /// Debug binary structure of an NCS file
+    #[command(after_help = "Example:\n  bl4 ncs debug inv0.bin --offsets")]
     Debug {
         /// Path to decompressed NCS file
         path: PathBuf,
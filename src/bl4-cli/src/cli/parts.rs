@@ -0,0 +1,57 @@
+//! Parts command CLI definitions
+
+use clap::Subcommand;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum PartsCommand {
+    /// List all categories
+    #[command(after_help = "Example:\n  bl4 parts list")]
+    List {
+        /// Path to parts database (directory of per-category TSVs, or a TSV, JSON or SQLite file).
+        /// Defaults to $BL4_PARTS_DB, then $XDG_DATA_HOME/bl4/parts, then the embedded database
+        #[arg(long)]
+        parts_db: Option<PathBuf>,
+    },
+
+    /// Show parts for a weapon type or category
+    #[command(
+        after_help = "Examples:\n  bl4 parts show \"Jakobs Pistol\"\n  bl4 parts show --category 3"
+    )]
+    Show {
+        /// Weapon name (e.g. "Jakobs Pistol", "Vladof SMG")
+        #[arg(required_unless_present = "category")]
+        weapon: Option<String>,
+
+        /// Category ID (e.g. 3 for Jakobs Pistol)
+        #[arg(short, long, conflicts_with = "weapon")]
+        category: Option<i64>,
+
+        /// Path to parts database (directory of per-category TSVs, or a TSV, JSON or SQLite file).
+        /// Defaults to $BL4_PARTS_DB, then $XDG_DATA_HOME/bl4/parts, then the embedded database
+        #[arg(long)]
+        parts_db: Option<PathBuf>,
+    },
+
+    /// Merge a directory of per-category TSVs into one monolithic TSV
+    #[command(after_help = "Example:\n  bl4 parts merge parts/ -o parts.tsv")]
+    Merge {
+        /// Directory of per-category TSVs
+        dir: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(short, long, visible_alias = "out")]
+        output: Option<PathBuf>,
+    },
+
+    /// Import a directory of per-category TSVs into a SQLite database
+    #[command(after_help = "Example:\n  bl4 parts build-sqlite parts/ -o parts.db")]
+    BuildSqlite {
+        /// Directory of per-category TSVs
+        dir: PathBuf,
+
+        /// Output SQLite file
+        #[arg(short, long, visible_alias = "out")]
+        output: PathBuf,
+    },
+}
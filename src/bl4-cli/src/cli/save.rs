@@ -5,7 +5,7 @@ use std::path::PathBuf;
 
 #[derive(Args)]
 pub struct SaveArgs {
-    /// Path to .sav file
+    /// Path to .sav file (or decrypted .yaml for `edit --preset`)
     pub input: PathBuf,
 
     /// Reveal or clear the fog-of-discovery map
@@ -21,7 +21,7 @@ pub struct SaveArgs {
     pub validate_items: bool,
 
     /// Steam ID (uses configured default if not provided)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub steam_id: Option<String>,
 
     /// Create backup before modifying
@@ -41,6 +41,7 @@ pub enum MapAction {
 #[derive(Subcommand)]
 pub enum SaveAction {
     /// Decrypt to YAML (stdout or -o file)
+    #[command(after_help = "Example:\n  bl4 save 1.sav decrypt -o save.yaml")]
     Decrypt {
         /// Path to output YAML file (uses stdout if not specified)
         #[arg(short, long)]
@@ -48,15 +49,30 @@ pub enum SaveAction {
     },
 
     /// Encrypt from YAML (positional file or stdin) to .sav
+    #[command(after_help = "Example:\n  bl4 save 1.sav encrypt save.yaml")]
     Encrypt {
         /// YAML input file (reads stdin if not provided)
         yaml: Option<PathBuf>,
     },
 
-    /// Edit in $EDITOR
-    Edit,
+    /// Edit in $EDITOR, or apply a ChangeSet preset with --preset
+    #[command(after_help = "Example:\n  bl4 save 1.sav edit --preset max_cash.yaml --dry-run")]
+    Edit {
+        /// ChangeSet preset YAML to apply instead of opening an editor
+        #[arg(long)]
+        preset: Option<PathBuf>,
+
+        /// Write the result here instead of overwriting the input (.sav is encrypted, else YAML)
+        #[arg(short, long, requires = "preset")]
+        output: Option<PathBuf>,
+
+        /// Print the changes the preset would make without writing anything
+        #[arg(long, requires = "preset")]
+        dry_run: bool,
+    },
 
     /// Query values
+    #[command(after_help = "Example:\n  bl4 save 1.sav get \"state.currencies.cash\"")]
     Get {
         /// YAML path query (e.g. "state.currencies.cash")
         query: Option<String>,
@@ -69,7 +85,7 @@ pub enum SaveAction {
         #[arg(long)]
         money: bool,
 
-        /// Show character info (name, class, difficulty)
+        /// Show character info (name, class, difficulty, playtime)
         #[arg(long)]
         info: bool,
 
@@ -78,7 +94,19 @@ pub enum SaveAction {
         all: bool,
     },
 
+    /// List child keys under a path (e.g. "state.inventory"), or root keys
+    #[command(after_help = "Example:\n  bl4 save 1.sav keys state.inventory")]
+    Keys {
+        /// YAML path to list (lists root keys if omitted)
+        path: Option<String>,
+    },
+
+    /// Summarize the backpack: item counts by label and weapon/gear type
+    #[command(after_help = "Example:\n  bl4 save 1.sav summary")]
+    Summary,
+
     /// Set a value
+    #[command(after_help = "Example:\n  bl4 save 1.sav set \"state.currencies.cash\" 999999")]
     Set {
         /// YAML path to modify (e.g. "state.currencies.cash")
         path: String,
@@ -6,6 +6,7 @@ use std::path::PathBuf;
 #[derive(Subcommand)]
 pub enum SerialCommand {
     /// Decode an item serial number
+    #[command(after_help = "Example:\n  bl4 serial decode '@Ugr$ZCm/&tH!t{KgK/Shxu>k' --verbose")]
     Decode {
         /// Item serial to decode (e.g. @Ugr$ZCm/&tH!t{KgK/Shxu>k)
         serial: String,
@@ -30,9 +31,10 @@ pub enum SerialCommand {
         #[arg(short = 's', long)]
         short: bool,
 
-        /// Path to parts database (directory of per-category TSVs or single file)
-        #[arg(long, default_value = "share/manifest/parts")]
-        parts_db: PathBuf,
+        /// Path to parts database (directory of per-category TSVs or single file).
+        /// Defaults to $BL4_PARTS_DB, then $XDG_DATA_HOME/bl4/parts, then the embedded database
+        #[arg(long)]
+        parts_db: Option<PathBuf>,
 
         /// Remove a part by name (repeatable)
         #[arg(long, action = clap::ArgAction::Append)]
@@ -44,12 +46,14 @@ pub enum SerialCommand {
     },
 
     /// Re-encode a serial (for testing round-trip encoding)
+    #[command(after_help = "Example:\n  bl4 serial encode '@Ugr$ZCm/&tH!t{KgK/Shxu>k'")]
     Encode {
         /// Item serial to decode and re-encode
         serial: String,
     },
 
     /// Compare two item serials side by side
+    #[command(after_help = "Example:\n  bl4 serial compare '<serial1>' '<serial2>'")]
     Compare {
         /// First serial
         serial1: String,
@@ -59,6 +63,7 @@ pub enum SerialCommand {
     },
 
     /// Modify a serial by swapping parts from another serial
+    #[command(after_help = "Example:\n  bl4 serial modify '<base>' '<source>' 4,12")]
     Modify {
         /// Base serial to modify
         base: String,
@@ -71,6 +76,9 @@ pub enum SerialCommand {
     },
 
     /// Validate item serial(s) for legality
+    #[command(
+        after_help = "Example:\n  bl4 serial validate '@Ugr$ZCm/&tH!t{KgK/Shxu>k' --verbose"
+    )]
     Validate {
         /// Item serial(s) to validate
         serials: Vec<String>,
@@ -81,6 +89,7 @@ pub enum SerialCommand {
     },
 
     /// Batch decode serials from a file to binary output
+    #[command(after_help = "Example:\n  bl4 serial batch-decode serials.txt output.bin")]
     BatchDecode {
         /// Input file with one serial per line
         input: PathBuf,
@@ -0,0 +1,40 @@
+//! `bl4 examples` - end-to-end recipes
+//!
+//! Per-command examples live in each command's `--help`; these show how
+//! the commands chain together for common jobs.
+
+const RECIPES: &str = "\
+Edit a save by hand
+  bl4 configure --steam-id 76561197960521364
+  bl4 save 1.sav decrypt -o save.yaml
+  $EDITOR save.yaml
+  bl4 save 1.sav encrypt save.yaml
+
+Preview and apply a ChangeSet preset
+  bl4 save 1.sav edit --preset max_cash.yaml --dry-run
+  bl4 save 1.sav edit --preset max_cash.yaml -o 1.edited.sav
+
+Check the items in a save
+  bl4 save 1.sav --validate-items
+  bl4 idb import-save 1.sav --decode
+  bl4 idb list --rarity Legendary
+
+Figure out what an item is
+  bl4 item describe '@Ugr$ZCm/&tH!t{KgK/Shxu>k'
+  bl4 serial decode '@Ugr$ZCm/&tH!t{KgK/Shxu>k' --rarity
+  bl4 drops find \"Terminus\"
+
+Browse parts for a weapon
+  bl4 parts list
+  bl4 parts show \"Jakobs Pistol\"
+
+Rebuild game data from NCS
+  bl4 ncs decompress game.pak -o ./ncs_output/ --raw
+  bl4 ncs scan ./ncs_output/
+  bl4 ncs extract ./ncs_output/ -t manifest -o share/manifest/parts_database.json
+  bl4 ncs search ./ncs_output/ weapon_ps";
+
+/// Handle the examples command
+pub fn handle() {
+    println!("{}", RECIPES);
+}
@@ -41,8 +41,8 @@ pub fn handle_check(input: &Path) -> Result<()> {
         println!("{:?}: valid NCS manifest file", input);
         println!("  Entry count: {}", manifest.entry_count);
         println!("  Entries found: {}", manifest.entries.len());
-    } else if bl4_ncs::is_ncs(&data) {
-        let header = bl4_ncs::NcsHeader::from_bytes(&data)?;
+    } else if let Some(start) = bl4_ncs::find_ncs_start(&data) {
+        let header = bl4_ncs::NcsHeader::from_bytes(&data[start..])?;
         println!("{:?}: valid NCS data file", input);
         println!("  Version: {}", header.version);
         println!("  Compressed: {}", header.is_compressed());
@@ -76,12 +76,12 @@ pub fn handle_decompress(input: &Path, output: Option<std::path::PathBuf>) -> Re
         return Ok(());
     }
 
-    if !bl4_ncs::is_ncs(&data) {
+    let Some(start) = bl4_ncs::find_ncs_start(&data) else {
         anyhow::bail!("Not a valid NCS file");
-    }
+    };
 
     println!("Parsing NCS header...");
-    let header = bl4_ncs::NcsHeader::from_bytes(&data)?;
+    let header = bl4_ncs::NcsHeader::from_bytes(&data[start..])?;
     println!(
         "  Version: {}, compressed: {}",
         header.version,
@@ -132,11 +132,14 @@ pub fn handle_info(input: &Path) -> Result<()> {
         for entry in &manifest.entries {
             println!("    - {}", entry.filename);
         }
-    } else if bl4_ncs::is_ncs(&data) {
-        let header = bl4_ncs::NcsHeader::from_bytes(&data)?;
+    } else if let Some(start) = bl4_ncs::find_ncs_start(&data) {
+        let header = bl4_ncs::NcsHeader::from_bytes(&data[start..])?;
 
         println!("NCS Data File: {:?}", input);
         println!("  File size: {} bytes", data.len());
+        if start > 0 {
+            println!("  Header offset: {} bytes", start);
+        }
         println!("  Version: {}", header.version);
         println!("  Compressed: {}", header.is_compressed());
 
@@ -204,16 +207,16 @@ fn search_dir(dir: &Path, found: &mut usize) -> Result<()> {
 fn check_file(path: &Path, found: &mut usize) -> Result<()> {
     use std::io::Read;
     let file = fs::File::open(path)?;
-    let mut reader = std::io::BufReader::new(file);
-    let mut header = [0u8; 5];
-    if reader.read_exact(&mut header).is_ok() {
-        if bl4_ncs::is_ncs(&header) {
-            println!("{}: NCS data", path.display());
-            *found += 1;
-        } else if bl4_ncs::is_ncs_manifest(&header) {
-            println!("{}: NCS manifest", path.display());
-            *found += 1;
-        }
+    let mut header = Vec::new();
+    // Enough for the header after the most padding find_ncs_start skips
+    file.take((bl4_ncs::NCS_MAX_PREAMBLE + bl4_ncs::NCS_HEADER_SIZE) as u64)
+        .read_to_end(&mut header)?;
+    if bl4_ncs::find_ncs_start(&header).is_some() {
+        println!("{}: NCS data", path.display());
+        *found += 1;
+    } else if bl4_ncs::is_ncs_manifest(&header) {
+        println!("{}: NCS manifest", path.display());
+        *found += 1;
     }
     Ok(())
 }
@@ -0,0 +1,208 @@
+//! Item command handlers
+//!
+//! Ties the serial decoder, parts database, and reference tables together
+//! into a readable "what is this item" breakdown.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::parts::{load_parts_db, resolve_parts_db, PartEntry, PartsDatabase};
+use super::serial::resolve_legendary_name;
+
+/// A part from the serial, resolved against the parts database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescribedPart {
+    pub index: u64,
+    /// Part name from the database (or manifest), None if unknown
+    pub name: Option<String>,
+    pub slot: String,
+}
+
+/// Everything we can say about a decoded item
+#[derive(Debug, Clone)]
+pub struct ItemDescription {
+    pub category: Option<i64>,
+    pub category_name: Option<&'static str>,
+    pub manufacturer: Option<&'static str>,
+    pub weapon_type: Option<&'static str>,
+    pub rarity: Option<&'static bl4::RarityTier>,
+    pub legendary: Option<String>,
+    pub elements: Option<String>,
+    pub level: Option<u64>,
+    pub parts: Vec<DescribedPart>,
+}
+
+/// Look up a part by category and index in the parts database
+fn lookup_part(db: &PartsDatabase, category: i64, index: u64) -> Option<&PartEntry> {
+    db.parts
+        .iter()
+        .find(|p| p.category == category && p.index as u64 == index)
+}
+
+/// Manufacturer and weapon type from a `MANU_TYPE.part_*` name prefix
+fn weapon_from_part_name(name: &str) -> Option<(&'static str, &'static str)> {
+    let prefix = name.split('.').next()?;
+    let (manufacturer, weapon_type) = prefix.split_once('_')?;
+    Some((
+        bl4::manufacturer_by_code(manufacturer)?.name,
+        bl4::weapon_type_by_code(weapon_type)?.name,
+    ))
+}
+
+/// Resolve each non-element part against the database, falling back to the manifest
+fn describe_parts(item: &bl4::ItemSerial, db: &PartsDatabase) -> Vec<DescribedPart> {
+    let category = item.parts_category();
+    item.resolved_parts()
+        .into_iter()
+        .filter(|p| !p.is_element)
+        .map(|p| {
+            let entry = category.and_then(|cat| lookup_part(db, cat, p.index));
+            let name = entry
+                .map(|e| e.name.clone())
+                .or_else(|| p.name.map(str::to_string));
+            let slot = entry
+                .and_then(|e| e.slot.clone())
+                .unwrap_or_else(|| p.slot.to_string());
+            DescribedPart {
+                index: p.index,
+                name,
+                slot,
+            }
+        })
+        .collect()
+}
+
+/// Build a description of a decoded item
+pub fn describe_item(item: &bl4::ItemSerial, db: &PartsDatabase) -> ItemDescription {
+    let parts = describe_parts(item, db);
+    let part_names = || parts.iter().filter_map(|p| p.name.as_deref());
+
+    let (manufacturer, weapon_type) = match item
+        .weapon_info()
+        .or_else(|| part_names().find_map(weapon_from_part_name))
+    {
+        Some((mfr, wt)) => (Some(mfr), Some(wt)),
+        None => (item.manufacturer_name(), None),
+    };
+
+    let rarity = part_names()
+        .find_map(bl4::rarity_from_internal)
+        .or_else(|| {
+            let name = item.rarity_name()?;
+            bl4::RARITY_TIERS.iter().find(|r| r.name == name)
+        });
+
+    let is_legendary = rarity.is_some_and(|r| r.tier == 5);
+    let legendary = resolve_legendary_name(
+        &item.parts_with_names(),
+        item.parts_category(),
+        is_legendary,
+    );
+
+    ItemDescription {
+        category: item.parts_category(),
+        category_name: item.category_name(),
+        manufacturer,
+        weapon_type,
+        rarity,
+        legendary,
+        elements: item.element_names(),
+        level: item.level,
+        parts,
+    }
+}
+
+/// Print a description in a readable layout
+fn print_description(serial: &str, desc: &ItemDescription) {
+    let title = desc.category_name.unwrap_or("Unknown item");
+    match &desc.legendary {
+        Some(name) => println!("{} ({})", name, title),
+        None => println!("{}", title),
+    }
+    println!("Serial: {}", serial);
+
+    if let Some(cat) = desc.category {
+        println!("Category: {}", cat);
+    }
+    if let Some(mfr) = desc.manufacturer {
+        println!("Manufacturer: {}", mfr);
+    }
+    if let Some(wt) = desc.weapon_type {
+        println!("Weapon type: {}", wt);
+    }
+    if let Some(rarity) = desc.rarity {
+        println!("Rarity: {} (tier {})", rarity.name, rarity.tier);
+    }
+    if let Some(elements) = &desc.elements {
+        println!("Element: {}", elements);
+    }
+    if let Some(level) = desc.level {
+        println!("Level: {}", level);
+    }
+
+    if desc.parts.is_empty() {
+        return;
+    }
+    println!("\nParts:");
+    let slot_width = desc.parts.iter().map(|p| p.slot.len()).max().unwrap_or(0);
+    for part in &desc.parts {
+        let name = part.name.as_deref().unwrap_or("(unknown)");
+        println!(
+            "  {:>4}  {:<width$}  {}",
+            part.index,
+            part.slot,
+            name,
+            width = slot_width
+        );
+    }
+}
+
+/// Handle `item describe` command
+pub fn describe(serial: &str, parts_db: Option<&Path>) -> Result<()> {
+    let item = bl4::ItemSerial::decode(serial).context("Failed to decode serial")?;
+    let db = load_parts_db(&resolve_parts_db(parts_db))?;
+
+    print_description(serial, &describe_item(&item, &db));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weapon_from_part_name() {
+        assert_eq!(
+            weapon_from_part_name("JAK_SG.part_barrel_01"),
+            Some(("Jakobs", "Shotgun"))
+        );
+        assert!(weapon_from_part_name("part_barrel_01").is_none());
+        assert!(weapon_from_part_name("XXX_PS.part_barrel_01").is_none());
+    }
+
+    #[test]
+    fn test_describe_item_uses_parts_db() {
+        let item = bl4::ItemSerial::decode("@Ugr$ZCm/&tH!t{KgK/Shxu>k").unwrap();
+        let category = item.parts_category().unwrap();
+        let first = item
+            .resolved_parts()
+            .into_iter()
+            .find(|p| !p.is_element)
+            .unwrap();
+
+        let db = PartsDatabase {
+            parts: vec![PartEntry {
+                name: "TEST.part_custom".to_string(),
+                category,
+                index: first.index as i64,
+                slot: Some("custom".to_string()),
+            }],
+        };
+
+        let desc = describe_item(&item, &db);
+        let part = desc.parts.iter().find(|p| p.index == first.index).unwrap();
+        assert_eq!(part.name.as_deref(), Some("TEST.part_custom"));
+        assert_eq!(part.slot, "custom");
+        assert_eq!(desc.category, Some(category));
+    }
+}
@@ -59,9 +59,9 @@ pub fn filter_item_fields_with_overrides(
     serde_json::Value::Object(obj)
 }
 
-/// Escape a string for CSV output
+/// Escape a string for CSV output (RFC 4180)
 pub fn escape_csv(s: &str) -> String {
-    if s.contains(',') || s.contains('"') || s.contains('\n') {
+    if s.contains([',', '"', '\n', '\r']) {
         format!("\"{}\"", s.replace('"', "\"\""))
     } else {
         s.to_string()
@@ -4,6 +4,8 @@
 
 pub mod configure;
 pub mod drops;
+pub mod examples;
+pub mod item;
 pub mod items_db;
 pub mod launch;
 pub mod memory;
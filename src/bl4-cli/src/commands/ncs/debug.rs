@@ -19,6 +19,13 @@ pub fn debug_file(path: &Path, show_hex: bool, do_parse: bool, _show_offsets: bo
         println!("\nType: {}", content.type_name());
         println!("Format: {}", content.format_code());
         println!("Strings: {}", content.strings.len());
+        if let Some(control) = content.control_section(&data) {
+            println!(
+                "Control section: offset=0x{:x}, count={}, mode=0x{:02x}",
+                control.offset, control.count, control.mode
+            );
+            println!("Category names: {:?}", content.category_names(&data));
+        }
 
         if show_hex {
             println!("\nFirst 64 bytes:");
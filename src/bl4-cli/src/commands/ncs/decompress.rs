@@ -2,11 +2,13 @@
 
 use anyhow::{Context, Result};
 use bl4_ncs::oodle::{self, OodleDecompressor};
-use bl4_ncs::{decompress_ncs_with, is_ncs, parse_ncs_binary, NcsContent};
+use bl4_ncs::{decompress_ncs_with, find_ncs_start, parse_ncs_binary, NcsContent};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::format::format_tsv;
+use crate::cli::DocumentFormat;
+
+use super::format::format_document;
 use super::util::print_hex;
 
 /// Select the Oodle decompressor backend based on CLI options
@@ -45,16 +47,18 @@ fn select_backend(oodle_exec: Option<&str>, oodle_fifo: bool) -> Result<Box<dyn
 }
 
 #[cfg(target_os = "windows")]
+#[allow(clippy::too_many_arguments)]
 pub fn decompress_file(
     input: &Path,
     output: Option<&Path>,
     offset: Option<usize>,
     raw: bool,
+    format: DocumentFormat,
     oodle_dll: Option<&Path>,
     oodle_exec: Option<&str>,
 ) -> Result<()> {
     let decompressor = select_backend(oodle_dll, oodle_exec)?;
-    decompress_file_impl(input, output, offset, raw, decompressor)
+    decompress_file_impl(input, output, offset, raw, format, decompressor)
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -64,18 +68,21 @@ pub fn decompress_file(
     output: Option<&Path>,
     offset: Option<usize>,
     raw: bool,
+    format: DocumentFormat,
     oodle_exec: Option<&str>,
     oodle_fifo: bool,
 ) -> Result<()> {
     let decompressor = select_backend(oodle_exec, oodle_fifo)?;
-    decompress_file_impl(input, output, offset, raw, decompressor)
+    decompress_file_impl(input, output, offset, raw, format, decompressor)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn decompress_file_impl(
     input: &Path,
     output: Option<&Path>,
     offset: Option<usize>,
     raw: bool,
+    format: DocumentFormat,
     decompressor: Box<dyn OodleDecompressor>,
 ) -> Result<()> {
     use bl4_ncs::scan_for_ncs;
@@ -92,7 +99,7 @@ fn decompress_file_impl(
             if raw {
                 fs::write(output_path, &decompressed)?;
             } else if let Some(doc) = parse_ncs_binary(&decompressed) {
-                fs::write(output_path, format_tsv(&doc))?;
+                fs::write(output_path, format_document(&doc, format)?)?;
             } else {
                 fs::write(output_path, &decompressed)?;
             }
@@ -109,14 +116,14 @@ fn decompress_file_impl(
     }
 
     // If this is a single NCS file, decompress it
-    if is_ncs(&data) {
+    if find_ncs_start(&data).is_some() {
         let decompressed = decompress_ncs_with(&data, decompressor.as_ref())
             .map_err(|e| anyhow::anyhow!("Failed to decompress NCS data: {}", e))?;
         if let Some(output_path) = output {
             if raw {
                 fs::write(output_path, &decompressed)?;
             } else if let Some(doc) = parse_ncs_binary(&decompressed) {
-                fs::write(output_path, format_tsv(&doc))?;
+                fs::write(output_path, format_document(&doc, format)?)?;
             } else {
                 fs::write(output_path, &decompressed)?;
             }
@@ -134,7 +141,7 @@ fn decompress_file_impl(
 
     // Try PAK index-based extraction first for .pak files (finds all NCS files)
     if input.extension().map(|e| e == "pak").unwrap_or(false) {
-        return decompress_pak_index(input, output, raw, decompressor);
+        return decompress_pak_index(input, output, raw, format, decompressor);
     }
 
     // Fallback: Scan for NCS chunks via magic bytes (for non-PAK files)
@@ -177,10 +184,9 @@ fn decompress_file_impl(
                 } else if let Some(doc) = parse_ncs_binary(&decompressed) {
                     let table_name = doc.tables.keys().next()
                         .map(|s| s.as_str()).unwrap_or("unknown");
-                    let filename = format!("{}.tsv", table_name);
+                    let filename = format!("{}.{}", table_name, format.extension());
                     let out_path = output_dir.join(&filename);
-                    let tsv_content = format_tsv(&doc);
-                    fs::write(&out_path, tsv_content.as_bytes())?;
+                    fs::write(&out_path, format_document(&doc, format)?)?;
                     success += 1;
                 } else if let Some(content) = NcsContent::parse(&decompressed) {
                     let filename = format!("{}.bin", content.type_name());
@@ -235,6 +241,7 @@ fn decompress_pak_index(
     input: &Path,
     output: Option<&Path>,
     raw: bool,
+    format: DocumentFormat,
     decompressor: Box<dyn OodleDecompressor>,
 ) -> Result<()> {
     use bl4_ncs::{decompress_ncs_with, type_from_filename};
@@ -290,9 +297,8 @@ fn decompress_pak_index(
         } else if let Some(doc) = parse_ncs_binary(&decompressed) {
             let table_name = doc.tables.keys().next()
                 .map(|s| s.as_str()).unwrap_or("unknown");
-            let out_path = output_dir.join(format!("{}.tsv", table_name));
-            let tsv_content = format_tsv(&doc);
-            fs::write(&out_path, tsv_content.as_bytes())?;
+            let out_path = output_dir.join(format!("{}.{}", table_name, format.extension()));
+            fs::write(&out_path, format_document(&doc, format)?)?;
             success += 1;
         } else if let Some(content) = NcsContent::parse(&decompressed) {
             let out_path = output_dir.join(format!("{}.bin", content.type_name()));
@@ -0,0 +1,49 @@
+//! NCS dependency graph command
+
+use anyhow::{bail, Result};
+use bl4_ncs::build_dependency_graph;
+use std::path::Path;
+
+/// Show the type-level dependency graph for the NCS files under a directory
+pub fn show_deps(path: &Path, json: bool, dot: bool) -> Result<()> {
+    if !path.is_dir() {
+        bail!("{} is not a directory", path.display());
+    }
+
+    let graph = build_dependency_graph(path);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&graph)?);
+        return Ok(());
+    }
+    if dot {
+        print!("{}", graph.to_dot());
+        return Ok(());
+    }
+
+    println!("=== Dependencies ({} types) ===", graph.nodes.len());
+    match graph.topological_order() {
+        Ok(order) => {
+            println!("\nLoad order:");
+            for name in order {
+                println!("  {}", name);
+            }
+        }
+        Err(stuck) => {
+            println!("\nCycle detected; could not order:");
+            for name in stuck {
+                println!("  {}", name);
+            }
+        }
+    }
+
+    let missing = graph.missing();
+    if !missing.is_empty() {
+        println!("\nUnresolved ({}):", missing.len());
+        for (from, dep) in missing {
+            println!("  {} -> {}", from, dep);
+        }
+    }
+
+    Ok(())
+}
@@ -6,13 +6,7 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::types::{FileInfo, ItemParts, LegendaryComposition, ManufacturerMapping, NexusSerializedEntry, PartIndex};
-
-/// Known weapon manufacturers
-const MANUFACTURERS: &[&str] = &["BOR", "DAD", "JAK", "MAL", "ORD", "TED", "TOR", "VLA"];
-
-/// Known weapon types
-const WEAPON_TYPES: &[&str] = &["AR", "HW", "PS", "SG", "SM", "SR"];
+use super::types::{ExtractFailure, ExtractResult, FileInfo, ItemParts, LegendaryComposition, ManufacturerMapping, NexusSerializedEntry, PartIndex};
 
 /// Check if a filename is an inventory NCS file.
 ///
@@ -97,42 +91,13 @@ pub fn extract_by_type(
         return export_parts_manifest(path, output, json);
     }
 
-    let mut extracted = Vec::new();
-
-    for entry in walkdir::WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let file_path = entry.path();
-        if !file_path.extension().map(|e| e == "bin").unwrap_or(false) {
-            continue;
-        }
-
-        if let Ok(data) = fs::read(file_path) {
-            if let Some(content) = NcsContent::parse(&data) {
-                if content.type_name() == extract_type {
-                    extracted.push(FileInfo {
-                        path: file_path.to_string_lossy().to_string(),
-                        type_name: content.type_name().to_string(),
-                        format_code: content.format_code().to_string(),
-                        entry_names: content.entry_names().map(|s| s.to_string()).collect(),
-                        guids: content.guids().map(|s| s.to_string()).collect(),
-                        numeric_values: content
-                            .numeric_values()
-                            .map(|(s, v)| (s.to_string(), v))
-                            .collect(),
-                    });
-                }
-            }
-        }
-    }
+    let result = collect_by_type(path, extract_type);
 
     let output_str = if json {
-        serde_json::to_string_pretty(&extracted)?
+        serde_json::to_string_pretty(&result)?
     } else {
         let mut out = String::from("file\ttype\tformat\tentry_name\n");
-        for info in &extracted {
+        for info in &result.files {
             for name in &info.entry_names {
                 out.push_str(&format!(
                     "{}\t{}\t{}\t{}\n",
@@ -147,16 +112,90 @@ pub fn extract_by_type(
         fs::write(output_path, &output_str)?;
         println!(
             "Wrote {} entries to {}",
-            extracted.len(),
+            result.files.len(),
             output_path.display()
         );
     } else {
         println!("{}", output_str);
     }
 
+    print_failure_summary(&result.failed);
     Ok(())
 }
 
+/// Walk `path` for `.bin` files of `extract_type`, recording every file skipped on error
+fn collect_by_type(path: &Path, extract_type: &str) -> ExtractResult {
+    let mut result = ExtractResult::default();
+
+    for entry in walkdir::WalkDir::new(path) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                result.failed.push(ExtractFailure {
+                    path: e
+                        .path()
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_default(),
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let file_path = entry.path();
+        if !entry.file_type().is_file()
+            || !file_path.extension().map(|e| e == "bin").unwrap_or(false)
+        {
+            continue;
+        }
+
+        let path_str = file_path.to_string_lossy().to_string();
+        let data = match fs::read(file_path) {
+            Ok(data) => data,
+            Err(e) => {
+                result.failed.push(ExtractFailure {
+                    path: path_str,
+                    reason: format!("read failed: {}", e),
+                });
+                continue;
+            }
+        };
+        let Some(content) = NcsContent::parse(&data) else {
+            result.failed.push(ExtractFailure {
+                path: path_str,
+                reason: "not parseable as NCS content".to_string(),
+            });
+            continue;
+        };
+
+        if content.type_name() == extract_type {
+            result.files.push(FileInfo {
+                path: path_str,
+                type_name: content.type_name().to_string(),
+                format_code: content.format(),
+                entry_names: content.entry_names().map(|s| s.to_string()).collect(),
+                guids: content.guids().map(|s| s.to_string()).collect(),
+                numeric_values: content
+                    .numeric_values()
+                    .map(|(s, v)| (s.to_string(), v))
+                    .collect(),
+            });
+        }
+    }
+
+    result
+}
+
+/// Report skipped files on stderr so they don't mix with extracted data
+fn print_failure_summary(failed: &[ExtractFailure]) {
+    if failed.is_empty() {
+        return;
+    }
+    eprintln!("{} files skipped on error:", failed.len());
+    for failure in failed {
+        eprintln!("  {}: {}", failure.path, failure.reason);
+    }
+}
+
 /// Extract part serial indices from inv.bin
 ///
 /// The inv.bin NCS file contains part definitions where:
@@ -175,13 +214,13 @@ fn extract_part_indices(path: &Path, output: Option<&Path>, json: bool) -> Resul
         let s = &strings[i];
 
         // Check if this looks like a part name (MANU_TYPE_Name pattern)
-        if let Some((manufacturer, weapon_type)) = parse_part_name(s) {
+        if let Some(info) = bl4::parse_part_name(s) {
             // Look for numeric index within next 10 strings (indices often have fields between)
             let window_end = (i + 10).min(strings.len());
             for candidate in &strings[(i + 1)..window_end] {
 
                 // Stop if we hit another part name (new record)
-                if parse_part_name(candidate).is_some() {
+                if bl4::parse_part_name(candidate).is_some() {
                     break;
                 }
 
@@ -191,8 +230,8 @@ fn extract_part_indices(path: &Path, output: Option<&Path>, json: bool) -> Resul
                         parts.push(PartIndex {
                             part_name: s.clone(),
                             serial_index: idx,
-                            manufacturer,
-                            weapon_type,
+                            manufacturer: info.manufacturer.code.to_string(),
+                            weapon_type: info.weapon_type.code.to_string(),
                         });
                         break;
                     }
@@ -240,57 +279,10 @@ fn extract_part_indices(path: &Path, output: Option<&Path>, json: bool) -> Resul
 /// Find inv.bin file in a directory
 /// Extract null-terminated strings from binary data
 fn extract_null_strings(data: &[u8]) -> Vec<String> {
-    let mut strings = Vec::new();
-    let mut current = Vec::new();
-
-    for &b in data {
-        if b == 0 {
-            if !current.is_empty() {
-                if let Ok(s) = std::str::from_utf8(&current) {
-                    if !s.is_empty() {
-                        strings.push(s.to_string());
-                    }
-                }
-                current.clear();
-            }
-        } else if (32..=126).contains(&b) {
-            current.push(b);
-        } else {
-            current.clear();
-        }
-    }
-
-    strings
-}
-
-/// Parse a part name in MANU_TYPE_Name format
-/// Returns (manufacturer, weapon_type) if valid, None otherwise
-fn parse_part_name(s: &str) -> Option<(String, String)> {
-    let parts: Vec<&str> = s.splitn(3, '_').collect();
-    if parts.len() < 3 {
-        return None;
-    }
-
-    let manufacturer = parts[0];
-    let weapon_type = parts[1];
-
-    // Must be a known manufacturer
-    if !MANUFACTURERS.contains(&manufacturer) {
-        return None;
-    }
-
-    // Must be a known weapon type
-    if !WEAPON_TYPES.contains(&weapon_type) {
-        return None;
-    }
-
-    // Rest of the name must be alphanumeric with underscores
-    let rest = parts[2];
-    if rest.is_empty() || !rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
-        return None;
-    }
-
-    Some((manufacturer.to_string(), weapon_type.to_string()))
+    bl4_ncs::extract_ascii_strings(data, 1)
+        .into_iter()
+        .map(|(_, s)| s)
+        .collect()
 }
 
 /// Extract complete item-to-parts mapping from inv.bin
@@ -476,48 +468,20 @@ fn parse_item_type_str(s: &str) -> Option<String> {
 /// Parse an item type identifier (e.g., "DAD_PS", "BOR_SG")
 /// Returns (manufacturer_code, weapon_type_code) tuple
 fn parse_item_type(s: &str) -> Option<(String, String)> {
-    let parts: Vec<&str> = s.split('_').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    let manufacturer = parts[0];
-    let weapon_type = parts[1];
-
-    if !MANUFACTURERS.contains(&manufacturer) {
-        return None;
-    }
+    let (manufacturer, weapon_type) = s.split_once('_')?;
 
-    if !WEAPON_TYPES.contains(&weapon_type) {
-        return None;
-    }
+    let manufacturer = bl4::manufacturer_by_code(manufacturer)?;
+    let weapon_type = bl4::weapon_type_by_code(weapon_type)?;
 
-    Some((manufacturer.to_string(), weapon_type.to_string()))
+    Some((manufacturer.code.to_string(), weapon_type.code.to_string()))
 }
 
 /// Parse an item part (e.g., "DAD_PS_Barrel_01")
 /// Returns (item_id, manufacturer, weapon_type) if valid
 fn parse_item_part(s: &str) -> Option<(String, String, String)> {
-    let parts: Vec<&str> = s.splitn(3, '_').collect();
-    if parts.len() < 3 {
-        return None;
-    }
-
-    let manufacturer = parts[0];
-    let weapon_type = parts[1];
-
-    if !MANUFACTURERS.contains(&manufacturer) {
-        return None;
-    }
-
-    if !WEAPON_TYPES.contains(&weapon_type) {
-        return None;
-    }
-
-    let rest = parts[2];
-    if rest.is_empty() || !rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
-        return None;
-    }
+    let info = bl4::parse_part_name(s)?;
+    let manufacturer = info.manufacturer.code;
+    let weapon_type = info.weapon_type.code;
 
     let item_id = format!("{}_{}", manufacturer, weapon_type);
     Some((item_id, manufacturer.to_string(), weapon_type.to_string()))
@@ -675,11 +639,7 @@ fn parse_manufacturer_code(s: &str) -> Option<String> {
     let manufacturer = parts[0];
 
     // Check if it's a known manufacturer code
-    if MANUFACTURERS.contains(&manufacturer) {
-        return Some(manufacturer.to_string());
-    }
-
-    None
+    bl4::manufacturer_by_code(manufacturer).map(|m| m.code.to_string())
 }
 
 /// Convert weapon type code to display name
@@ -858,12 +818,12 @@ fn extract_string_numeric_pairs_cmd(path: &Path, output: Option<&Path>, json: bo
 
 /// Extract serial indices using the new NCS binary parser
 fn extract_serial_indices_ncs_cmd(path: &Path, output: Option<&Path>, json: bool) -> Result<()> {
-    use bl4_ncs::{decompress_ncs, is_ncs};
+    use bl4_ncs::{decompress_ncs, find_ncs_start};
 
     let file_path = find_inv_file(path)?;
     let raw_data = fs::read(&file_path).context("Failed to read file")?;
 
-    let data = if is_ncs(&raw_data) {
+    let data = if find_ncs_start(&raw_data).is_some() {
         decompress_ncs(&raw_data).context("Failed to decompress NCS data")?
     } else {
         raw_data
@@ -906,12 +866,12 @@ fn extract_serial_indices_ncs_cmd(path: &Path, output: Option<&Path>, json: bool
 
 /// Extract using the new binary parser (structured output)
 fn extract_binary_native(path: &Path, output: Option<&Path>, json: bool) -> Result<()> {
-    use bl4_ncs::{decompress_ncs, is_ncs};
+    use bl4_ncs::{decompress_ncs, find_ncs_start};
 
     let inv_path = find_inv_file(path)?;
     let raw_data = fs::read(&inv_path).context("Failed to read inv file")?;
 
-    let data = if is_ncs(&raw_data) {
+    let data = if find_ncs_start(&raw_data).is_some() {
         decompress_ncs(&raw_data).context("Failed to decompress NCS data")?
     } else {
         raw_data
@@ -961,7 +921,7 @@ fn extract_binary_v2(path: &Path, output: Option<&Path>, json: bool) -> Result<(
 
 /// Build complete serial index decoder by scanning all inv*.bin files
 fn build_serial_decoder(path: &Path, output: Option<&Path>, json: bool) -> Result<()> {
-    use bl4_ncs::{decompress_ncs, is_ncs};
+    use bl4_ncs::{decompress_ncs, find_ncs_start};
 
     let mut all_indices = Vec::new();
     let mut files_processed = 0;
@@ -989,7 +949,7 @@ fn build_serial_decoder(path: &Path, output: Option<&Path>, json: bool) -> Resul
             }
         };
 
-        let data = if is_ncs(&raw_data) {
+        let data = if find_ncs_start(&raw_data).is_some() {
             match decompress_ncs(&raw_data) {
                 Ok(d) => d,
                 Err(e) => {
@@ -1151,7 +1111,7 @@ fn humanize_dep_table(dep_table: &str) -> String {
 
 /// Parse an NCS file, handling decompression if needed
 fn parse_ncs_file(path: &Path) -> Option<bl4_ncs::document::Document> {
-    use bl4_ncs::{decompress_ncs, is_ncs};
+    use bl4_ncs::{decompress_ncs, find_ncs_start};
 
     let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
     let raw_data = match fs::read(path) {
@@ -1162,7 +1122,7 @@ fn parse_ncs_file(path: &Path) -> Option<bl4_ncs::document::Document> {
         }
     };
 
-    let data = if is_ncs(&raw_data) {
+    let data = if find_ncs_start(&raw_data).is_some() {
         match decompress_ncs(&raw_data) {
             Ok(d) => d,
             Err(e) => {
@@ -1391,3 +1351,23 @@ fn write_parts_manifest(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_by_type_records_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("junk.bin"), b"not ncs content").unwrap();
+        fs::write(dir.path().join("ignored.txt"), b"skip me").unwrap();
+
+        let result = collect_by_type(dir.path(), "inv");
+        assert!(result.files.is_empty());
+        assert_eq!(result.failed.len(), 1);
+        assert!(result.failed[0].path.ends_with("junk.bin"));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["failed"][0]["reason"], "not parseable as NCS content");
+    }
+}
@@ -1,11 +1,19 @@
 //! Output formatting for NCS commands
 
-use bl4_ncs::ParsedDocument;
+use anyhow::Result;
+use bl4_ncs::{ParsedDocument, ParsedTable};
 use std::fmt::Write;
 
-/// Output parsed document as TSV to stdout
-pub fn output_tsv(doc: &ParsedDocument) {
-    print!("{}", format_tsv(doc));
+use crate::cli::DocumentFormat;
+use crate::commands::items_db::helpers::escape_csv;
+
+/// Render a parsed document in the given format
+pub fn format_document(doc: &ParsedDocument, format: DocumentFormat) -> Result<String> {
+    Ok(match format {
+        DocumentFormat::Tsv => format_tsv(doc),
+        DocumentFormat::Csv => format_csv(doc),
+        DocumentFormat::Json => serde_json::to_string_pretty(doc)? + "\n",
+    })
 }
 
 /// Format parsed document as TSV string
@@ -15,18 +23,49 @@ pub fn format_tsv(doc: &ParsedDocument) -> String {
     for (table_name, table) in &doc.tables {
         writeln!(output, "# {}", table_name).unwrap();
 
-        for (i, record) in table.records.iter().enumerate() {
-            for entry in &record.entries {
-                write!(output, "record_{}\t{}\t", i, entry.key).unwrap();
-                format_value(&entry.value, &mut output);
-                writeln!(output).unwrap();
-            }
+        for (record, key, value) in table_fields(table) {
+            writeln!(output, "{}\t{}\t{}", record, key, value).unwrap();
         }
     }
 
     output
 }
 
+/// Format parsed document as CSV string (RFC 4180)
+///
+/// Every field that needs it is quoted, including array and map values,
+/// which render with `, ` separators.
+pub fn format_csv(doc: &ParsedDocument) -> String {
+    let mut output = String::from("table,record,key,value\r\n");
+
+    for (table_name, table) in &doc.tables {
+        for (record, key, value) in table_fields(table) {
+            write!(
+                output,
+                "{},{},{},{}\r\n",
+                escape_csv(table_name),
+                record,
+                escape_csv(key),
+                escape_csv(&value)
+            )
+            .unwrap();
+        }
+    }
+
+    output
+}
+
+/// `(record label, key, rendered value)` for every entry in a table, in file order
+fn table_fields(table: &ParsedTable) -> impl Iterator<Item = (String, &str, String)> {
+    table.records.iter().enumerate().flat_map(|(i, record)| {
+        record.entries.iter().map(move |entry| {
+            let mut value = String::new();
+            format_value(&entry.value, &mut value);
+            (format!("record_{}", i), entry.key.as_str(), value)
+        })
+    })
+}
+
 fn format_value(value: &bl4_ncs::ParsedValue, output: &mut String) {
     match value {
         bl4_ncs::ParsedValue::Null => write!(output, "null").unwrap(),
@@ -55,3 +94,65 @@ fn format_value(value: &bl4_ncs::ParsedValue, output: &mut String) {
         bl4_ncs::ParsedValue::Ref { r#ref } => write!(output, "ref({})", r#ref).unwrap(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bl4_ncs::{ParsedEntry, ParsedRecord2, ParsedValue};
+    use std::collections::BTreeMap;
+
+    fn doc(entries: Vec<(&str, ParsedValue)>) -> ParsedDocument {
+        let record = ParsedRecord2 {
+            tags: Vec::new(),
+            entries: entries
+                .into_iter()
+                .map(|(key, value)| ParsedEntry {
+                    key: key.to_string(),
+                    value,
+                    dep_entries: Vec::new(),
+                })
+                .collect(),
+        };
+        let table = ParsedTable {
+            name: "rarity".to_string(),
+            deps: Vec::new(),
+            pair_remap: Vec::new(),
+            value_remap: Vec::new(),
+            records: vec![record],
+        };
+        ParsedDocument {
+            tables: BTreeMap::from([("rarity".to_string(), table)]),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_csv_quotes_fields() {
+        let leaf = |s: &str| ParsedValue::Leaf(s.to_string());
+        let doc = doc(vec![
+            ("name", leaf("Legendary, Pearl")),
+            ("tint", ParsedValue::Array(vec![leaf("1"), leaf("0.5")])),
+            ("quote", leaf("say \"hi\"")),
+            ("plain", leaf("common")),
+        ]);
+
+        assert_eq!(
+            format_csv(&doc),
+            "table,record,key,value\r\n\
+             rarity,record_0,name,\"Legendary, Pearl\"\r\n\
+             rarity,record_0,tint,\"[1, 0.5]\"\r\n\
+             rarity,record_0,quote,\"say \"\"hi\"\"\"\r\n\
+             rarity,record_0,plain,common\r\n"
+        );
+    }
+
+    #[test]
+    fn test_format_tsv_and_csv_share_fields() {
+        let doc = doc(vec![("name", ParsedValue::Leaf("a,b".to_string()))]);
+        assert_eq!(format_tsv(&doc), "# rarity\nrecord_0\tname\ta,b\n");
+        assert!(format_document(&doc, DocumentFormat::Json)
+            .unwrap()
+            .contains("\"a,b\""));
+    }
+}
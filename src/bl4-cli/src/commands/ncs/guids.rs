@@ -0,0 +1,122 @@
+//! NCS guids command
+
+use anyhow::Result;
+use bl4_ncs::{decompress_ncs, find_ncs_start, NcsContent};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::types::{GuidCollision, GuidIndex, GuidSource};
+
+/// Merge the [`NcsContent::guid_map`] of every `.bin` file under `path`
+pub fn collect_guids(path: &Path) -> GuidIndex {
+    let mut sources: BTreeMap<String, Vec<GuidSource>> = BTreeMap::new();
+
+    for entry in walkdir::WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let file_path = entry.path();
+        if !file_path.extension().map(|e| e == "bin").unwrap_or(false) {
+            continue;
+        }
+
+        let Some(content) = fs::read(file_path).ok().and_then(|data| {
+            let data = if find_ncs_start(&data).is_some() {
+                decompress_ncs(&data).ok()?
+            } else {
+                data
+            };
+            NcsContent::parse(&data)
+        }) else {
+            continue;
+        };
+
+        let path = file_path.to_string_lossy().to_string();
+        for (guid, name) in content.guid_map() {
+            sources.entry(guid).or_default().push(GuidSource {
+                name,
+                path: path.clone(),
+            });
+        }
+    }
+
+    merge_sources(sources)
+}
+
+/// First name per GUID, plus every GUID whose files disagree on the name
+fn merge_sources(sources: BTreeMap<String, Vec<GuidSource>>) -> GuidIndex {
+    let mut index = GuidIndex::default();
+    for (guid, sources) in sources {
+        let first = sources[0].name.clone();
+        if sources.iter().any(|s| s.name != first) {
+            index.collisions.push(GuidCollision {
+                guid: guid.clone(),
+                sources,
+            });
+        }
+        index.guids.insert(guid, first);
+    }
+    index
+}
+
+/// Print the GUID index for a directory of NCS files
+pub fn show_guids(path: &Path, json: bool) -> Result<()> {
+    let index = collect_guids(path);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&index)?);
+        return Ok(());
+    }
+
+    for (guid, name) in &index.guids {
+        println!("{}\t{}", guid, name);
+    }
+
+    if !index.collisions.is_empty() {
+        eprintln!("\nCollisions ({}):", index.collisions.len());
+        for collision in &index.collisions {
+            eprintln!("  {}", collision.guid);
+            for source in &collision.sources {
+                eprintln!("    {}  ({})", source.name, source.path);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, path: &str) -> GuidSource {
+        GuidSource {
+            name: name.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_merge_sources_reports_collisions() {
+        let sources = BTreeMap::from([
+            (
+                "AAAA".to_string(),
+                vec![source("Weapon_AR", "a.bin"), source("Weapon_AR", "b.bin")],
+            ),
+            (
+                "BBBB".to_string(),
+                vec![source("Enemy_Bat", "a.bin"), source("Saddleback", "c.bin")],
+            ),
+        ]);
+
+        let index = merge_sources(sources);
+        assert_eq!(index.guids["AAAA"], "Weapon_AR");
+        assert_eq!(index.guids["BBBB"], "Enemy_Bat");
+        assert_eq!(index.collisions.len(), 1);
+        assert_eq!(index.collisions[0].guid, "BBBB");
+        assert_eq!(index.collisions[0].sources.len(), 2);
+    }
+}
@@ -2,8 +2,12 @@
 
 mod debug;
 mod decompress;
+mod deps;
 mod extract;
 mod format;
+mod guids;
+mod packed;
+mod record;
 mod scan;
 mod search;
 mod show;
@@ -12,11 +16,11 @@ mod util;
 
 use anyhow::Result;
 
-use crate::cli::NcsCommand;
+use crate::cli::{DocumentFormat, NcsCommand};
 
 // Re-export types for external use
 #[allow(unused_imports)]
-pub use types::{FileInfo, PartIndex, ScanResult, SearchMatch};
+pub use types::{FileInfo, PartIndex, SearchMatch};
 
 pub fn handle_ncs_command(command: NcsCommand) -> Result<()> {
     match command {
@@ -33,14 +37,24 @@ pub fn handle_ncs_command(command: NcsCommand) -> Result<()> {
             hex,
             json,
             tsv,
-        } => show::show_file(&path, all_strings, hex, json, tsv),
+            format,
+        } => show::show_file(
+            &path,
+            all_strings,
+            hex,
+            json,
+            format.or(tsv.then_some(DocumentFormat::Tsv)),
+        ),
 
         NcsCommand::Search {
             path,
             pattern,
             all,
             limit,
-        } => search::search_files(&path, &pattern, all, limit),
+            json,
+        } => search::search_files(&path, &pattern, all, limit, json),
+
+        NcsCommand::Guids { path, json } => guids::show_guids(&path, json),
 
         NcsCommand::Extract {
             path,
@@ -51,15 +65,24 @@ pub fn handle_ncs_command(command: NcsCommand) -> Result<()> {
 
         NcsCommand::Stats { path, formats } => scan::show_stats(&path, formats),
 
+        NcsCommand::Deps { path, json, dot } => deps::show_deps(&path, json, dot),
+
+        NcsCommand::Packed { path, json } => packed::show_packed(&path, json),
+
+        NcsCommand::Record { path, index, table } => {
+            record::show_record(&path, index, table.as_deref())
+        }
+
         #[cfg(target_os = "windows")]
         NcsCommand::Decompress {
             input,
             output,
             offset,
             raw,
+            format,
             oodle_dll,
             oodle_exec,
-        } => decompress::decompress_file(&input, output.as_deref(), offset, raw, oodle_dll.as_deref(), oodle_exec.as_deref()),
+        } => decompress::decompress_file(&input, output.as_deref(), offset, raw, format, oodle_dll.as_deref(), oodle_exec.as_deref()),
 
         #[cfg(not(target_os = "windows"))]
         NcsCommand::Decompress {
@@ -67,9 +90,10 @@ pub fn handle_ncs_command(command: NcsCommand) -> Result<()> {
             output,
             offset,
             raw,
+            format,
             oodle_exec,
             oodle_fifo,
-        } => decompress::decompress_file(&input, output.as_deref(), offset, raw, oodle_exec.as_deref(), oodle_fifo),
+        } => decompress::decompress_file(&input, output.as_deref(), offset, raw, format, oodle_exec.as_deref(), oodle_fifo),
 
         NcsCommand::Debug { path, hex, parse, offsets } => debug::debug_file(&path, hex, parse, offsets),
     }
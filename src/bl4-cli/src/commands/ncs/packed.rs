@@ -0,0 +1,44 @@
+//! NCS packed string command
+
+use anyhow::{Context, Result};
+use bl4_ncs::{decompress_ncs, find_ncs_start, NcsContent, UnpackedValue};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+fn format_value(value: &UnpackedValue) -> String {
+    match value {
+        UnpackedValue::Integer(n) => format!("int {}", n),
+        UnpackedValue::Float(f) => format!("float {}", f),
+        UnpackedValue::String(s) => format!("str {:?}", s),
+        UnpackedValue::Boolean(b) => format!("bool {}", b),
+    }
+}
+
+/// Show the values unpacked from composite strings in an NCS file
+pub fn show_packed(path: &Path, json: bool) -> Result<()> {
+    let data = fs::read(path).context("Failed to read file")?;
+
+    let decompressed = if find_ncs_start(&data).is_some() {
+        decompress_ncs(&data).context("Failed to decompress NCS data")?
+    } else {
+        data
+    };
+
+    let content = NcsContent::parse(&decompressed).context("Failed to parse NCS content")?;
+    let packed = content.packed_strings();
+
+    if json {
+        let map: BTreeMap<&str, &[UnpackedValue]> = packed.iter().collect();
+        println!("{}", serde_json::to_string_pretty(&map)?);
+        return Ok(());
+    }
+
+    println!("=== Packed Strings ({}) ===", packed.len());
+    for (original, values) in packed.iter() {
+        let values: Vec<String> = values.iter().map(format_value).collect();
+        println!("  {:40} {}", original, values.join(", "));
+    }
+
+    Ok(())
+}
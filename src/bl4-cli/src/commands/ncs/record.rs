@@ -0,0 +1,95 @@
+//! NCS record command
+
+use anyhow::{bail, Context, Result};
+use bl4_ncs::{decompress_ncs, find_ncs_start, parse_ncs_binary, ParsedDocument, ParsedTable};
+use std::fs;
+use std::path::Path;
+
+/// Print one record of an NCS file with its refs and serial indices resolved
+pub fn show_record(path: &Path, index: usize, table: Option<&str>) -> Result<()> {
+    let data = fs::read(path).context("Failed to read file")?;
+
+    let decompressed = if find_ncs_start(&data).is_some() {
+        decompress_ncs(&data).context("Failed to decompress NCS data")?
+    } else {
+        data
+    };
+
+    let doc = parse_ncs_binary(&decompressed).context("Failed to parse NCS document")?;
+    let table = select_table(&doc, table)?;
+    let Some(record) = table.records.get(index) else {
+        bail!(
+            "Record {} out of range: table '{}' has {} records",
+            index,
+            table.name,
+            table.records.len()
+        );
+    };
+
+    println!("{}[{}]", table.name, index);
+    print!("{}", record.pretty(&doc));
+    Ok(())
+}
+
+/// The named table, or the only one when no name is given
+fn select_table<'a>(doc: &'a ParsedDocument, name: Option<&str>) -> Result<&'a ParsedTable> {
+    if let Some(name) = name {
+        return doc.table(name).with_context(|| {
+            format!(
+                "No table '{}' (tables: {})",
+                name,
+                doc.table_names().join(", ")
+            )
+        });
+    }
+    match doc.tables.values().next() {
+        Some(table) if doc.tables.len() == 1 => Ok(table),
+        Some(_) => bail!(
+            "File has several tables, pick one with --table: {}",
+            doc.table_names().join(", ")
+        ),
+        None => bail!("File has no tables"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn doc(names: &[&str]) -> ParsedDocument {
+        ParsedDocument {
+            tables: names
+                .iter()
+                .map(|name| {
+                    let table = ParsedTable {
+                        name: name.to_string(),
+                        deps: Vec::new(),
+                        pair_remap: Vec::new(),
+                        value_remap: Vec::new(),
+                        records: Vec::new(),
+                    };
+                    (name.to_string(), table)
+                })
+                .collect::<BTreeMap<_, _>>(),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_table() {
+        let single = doc(&["inv"]);
+        assert_eq!(select_table(&single, None).unwrap().name, "inv");
+
+        let several = doc(&["inv", "inv_comp"]);
+        assert_eq!(
+            select_table(&several, Some("inv_comp")).unwrap().name,
+            "inv_comp"
+        );
+        let err = select_table(&several, None).unwrap_err().to_string();
+        assert!(err.contains("inv, inv_comp"), "{}", err);
+        assert!(select_table(&several, Some("missing")).is_err());
+        assert!(select_table(&doc(&[]), None).is_err());
+    }
+}
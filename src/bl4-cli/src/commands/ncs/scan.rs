@@ -1,100 +1,75 @@
 //! NCS scan and stats commands
 
 use anyhow::Result;
-use bl4_ncs::{decompress_ncs, is_ncs, NcsContent};
+use bl4_ncs::{NcsContent, NcsScanOptions, NcsScanResult};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-use super::types::ScanResult;
+fn print_scan_summary(result: &NcsScanResult) {
+    println!("\n=== Scan Results ===");
+    println!("Total files: {}", result.total_files);
+    println!("Parsed files: {}", result.parsed_files);
+    if let Some(rate) = result.parse_rate() {
+        println!("Parse rate: {:.1}%", rate * 100.0);
+    }
 
-pub fn scan_directory(path: &Path, filter_type: Option<&str>, verbose: bool, json: bool) -> Result<()> {
-    let mut result = ScanResult {
-        total_files: 0,
-        parsed_files: 0,
-        types: HashMap::new(),
-        formats: HashMap::new(),
-    };
+    println!("Strings: {}", result.strings);
+    println!(
+        "Records (estimated): {} ({} files uncounted)",
+        result.estimated_records, result.uncounted_files
+    );
+
+    println!("\n=== Types ({}) ===", result.types.len());
+    let mut types: Vec<_> = result.types.iter().collect();
+    types.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+    for (type_name, files) in types.iter().take(30) {
+        println!("  {:40} {}", type_name, files.len());
+    }
 
-    for entry in walkdir::WalkDir::new(path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let file_path = entry.path();
-        if !file_path.extension().map(|e| e == "bin").unwrap_or(false) {
-            continue;
-        }
+    println!("\n=== Format Codes ===");
+    let mut formats: Vec<_> = result.formats.iter().collect();
+    formats.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.as_str().cmp(b.0.as_str())));
+    for (format, count) in formats {
+        println!("  {:40} {}", format, count);
+    }
 
-        result.total_files += 1;
+    println!("\n=== Failures ===");
+    println!("  {:40} {}", "read", result.read_failures);
+    println!("  {:40} {}", "decompress", result.decompress_failures);
+    println!("  {:40} {}", "parse", result.parse_failures);
+}
 
-        if let Ok(data) = fs::read(file_path) {
-            // Decompress if needed
-            let decompressed = if is_ncs(&data) {
-                decompress_ncs(&data).ok()
-            } else {
-                Some(data)
-            };
-
-            let Some(decompressed) = decompressed else {
-                continue;
-            };
-
-            if let Some(content) = NcsContent::parse(&decompressed) {
-                result.parsed_files += 1;
-
-                let type_name = content.type_name().to_string();
-                let format_code = content.format_code().to_string();
-
-                // Apply filter
-                if let Some(filter) = filter_type {
-                    if !type_name.contains(filter) {
-                        continue;
-                    }
-                }
-
-                result
-                    .types
-                    .entry(type_name.clone())
-                    .or_default()
-                    .push(file_path.to_string_lossy().to_string());
-
-                *result.formats.entry(format_code.clone()).or_insert(0) += 1;
-
-                if verbose && !json {
-                    println!(
-                        "{}: {} ({})",
-                        file_path.file_name().unwrap().to_string_lossy(),
-                        type_name,
-                        format_code
-                    );
-                }
-            }
+pub fn scan_directory(
+    path: &Path,
+    filter_type: Option<&str>,
+    verbose: bool,
+    json: bool,
+) -> Result<()> {
+    let options = NcsScanOptions {
+        filter_type: filter_type.map(str::to_string),
+    };
+    let result = bl4_ncs::scan_directory(path, &options);
+
+    if verbose {
+        for file in &result.files {
+            eprintln!(
+                "{}: {} ({}, {} strings, {} records)",
+                file.path.file_name().unwrap().to_string_lossy(),
+                file.type_name,
+                file.stats.format,
+                file.stats.string_count,
+                file.stats
+                    .estimated_record_count
+                    .map_or_else(|| "?".to_string(), |n| n.to_string())
+            );
         }
     }
 
     if json {
         println!("{}", serde_json::to_string_pretty(&result)?);
     } else {
-        println!("\n=== Scan Results ===");
-        println!("Total files: {}", result.total_files);
-        println!("Parsed files: {}", result.parsed_files);
-        println!(
-            "Parse rate: {:.1}%",
-            (result.parsed_files as f64 / result.total_files as f64) * 100.0
-        );
-
-        println!("\n=== Types ({}) ===", result.types.len());
-        let mut types: Vec<_> = result.types.iter().collect();
-        types.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
-        for (type_name, files) in types.iter().take(30) {
-            println!("  {:40} {}", type_name, files.len());
-        }
-
-        println!("\n=== Format Codes ===");
-        for (format, count) in &result.formats {
-            println!("  {}: {}", format, count);
-        }
+        print_scan_summary(&result);
     }
 
     Ok(())
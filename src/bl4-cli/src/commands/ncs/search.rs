@@ -2,12 +2,45 @@
 
 use anyhow::Result;
 use bl4_ncs::NcsContent;
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
 use super::types::SearchMatch;
 
-pub fn search_files(path: &Path, pattern: &str, all: bool, limit: usize) -> Result<()> {
+/// Strings considered by a search: entry names, guids and numeric-field names,
+/// or every string in the table when `all` is set
+fn searchable_strings(content: &NcsContent, all: bool) -> Vec<&str> {
+    if all {
+        return content.strings.iter().map(|s| s.as_str()).collect();
+    }
+
+    let numeric_fields = content
+        .strings
+        .iter()
+        .map(|s| s.as_str())
+        .filter(|s| content.numeric_value(s).is_some() && s.parse::<f64>().is_err());
+
+    let mut seen = HashSet::new();
+    content
+        .entry_names()
+        .chain(content.guids())
+        .chain(numeric_fields)
+        .filter(|s| seen.insert(*s))
+        .collect()
+}
+
+/// Collect the strings in `content` containing `pattern_lower`
+pub fn content_matches(content: &NcsContent, pattern_lower: &str, all: bool) -> Vec<String> {
+    searchable_strings(content, all)
+        .into_iter()
+        .filter(|s| s.to_lowercase().contains(pattern_lower))
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Search `.bin` files under `path`, stopping after `limit` matching files
+pub fn collect_matches(path: &Path, pattern: &str, all: bool, limit: usize) -> Vec<SearchMatch> {
     let pattern_lower = pattern.to_lowercase();
     let mut matches = Vec::new();
 
@@ -16,40 +49,43 @@ pub fn search_files(path: &Path, pattern: &str, all: bool, limit: usize) -> Resu
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
     {
+        if matches.len() >= limit {
+            break;
+        }
+
         let file_path = entry.path();
         if !file_path.extension().map(|e| e == "bin").unwrap_or(false) {
             continue;
         }
 
-        if let Ok(data) = fs::read(file_path) {
-            if let Some(content) = NcsContent::parse(&data) {
-                let search_strings: Vec<&str> = if all {
-                    content.strings.iter().map(|s| s.as_str()).collect()
-                } else {
-                    content.entry_names().collect()
-                };
-
-                let matching: Vec<String> = search_strings
-                    .iter()
-                    .filter(|s| s.to_lowercase().contains(&pattern_lower))
-                    .map(|s| s.to_string())
-                    .collect();
-
-                if !matching.is_empty() {
-                    matches.push(SearchMatch {
-                        path: file_path.to_string_lossy().to_string(),
-                        type_name: content.type_name().to_string(),
-                        matches: matching,
-                    });
-
-                    if matches.len() >= limit {
-                        break;
-                    }
-                }
-            }
+        let Some(content) = fs::read(file_path)
+            .ok()
+            .and_then(|data| NcsContent::parse(&data))
+        else {
+            continue;
+        };
+
+        let matching = content_matches(&content, &pattern_lower, all);
+        if !matching.is_empty() {
+            matches.push(SearchMatch {
+                path: file_path.to_string_lossy().to_string(),
+                type_name: content.type_name().to_string(),
+                matches: matching,
+            });
         }
     }
 
+    matches
+}
+
+pub fn search_files(path: &Path, pattern: &str, all: bool, limit: usize, json: bool) -> Result<()> {
+    let matches = collect_matches(path, pattern, all, limit);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&matches)?);
+        return Ok(());
+    }
+
     println!("=== Search Results for '{}' ===", pattern);
     println!("Found {} files with matches\n", matches.len());
 
@@ -67,3 +103,52 @@ pub fn search_files(path: &Path, pattern: &str, all: bool, limit: usize) -> Resu
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bl4_ncs::NcsContentHeader;
+    use std::collections::HashMap;
+
+    fn make_content(strings: &[&str]) -> NcsContent {
+        NcsContent {
+            header: NcsContentHeader {
+                type_name: "manufacturer".to_string(),
+                format_code: "abjx".to_string(),
+                raw_header: Vec::new(),
+                control_section_offset: None,
+                category_names_offset: None,
+            },
+            strings: strings.iter().map(|s| s.to_string()).collect(),
+            entry_values: strings
+                .windows(2)
+                .map(|pair| (pair[0].to_string(), pair[1].to_string()))
+                .collect(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_content_matches_names_guids_and_numeric_fields() {
+        let guid = "0123456789ABCDEF0123456789ABCDEF";
+        let content = make_content(&["Jakobs_Damage", "1.500000", guid, "jakobs shotgun"]);
+
+        let matches = content_matches(&content, "jakobs", false);
+        assert_eq!(matches, vec!["Jakobs_Damage".to_string()]);
+
+        let matches = content_matches(&content, "89abcdef", false);
+        assert_eq!(matches, vec![guid.to_string()]);
+
+        // Free-form values are only searched with `all`
+        let matches = content_matches(&content, "shotgun", false);
+        assert!(matches.is_empty());
+        let matches = content_matches(&content, "shotgun", true);
+        assert_eq!(matches, vec!["jakobs shotgun".to_string()]);
+    }
+
+    #[test]
+    fn test_collect_matches_missing_dir() {
+        let matches = collect_matches(Path::new("/nonexistent/ncs"), "anything", false, 10);
+        assert!(matches.is_empty());
+    }
+}
@@ -1,16 +1,24 @@
 //! NCS show command
 
 use anyhow::{Context, Result};
-use bl4_ncs::{decompress_ncs, is_ncs, parse_ncs_binary, NcsContent};
+use bl4_ncs::{decompress_ncs, find_ncs_start, parse_ncs_binary, NcsContent};
 use std::fs;
 use std::path::Path;
 
-use super::format::output_tsv;
+use crate::cli::DocumentFormat;
+
+use super::format::format_document;
 use super::types::FileInfo;
 use super::util::print_hex;
 
-#[allow(clippy::fn_params_excessive_bools)]
-pub fn show_file(path: &Path, all_strings: bool, hex: bool, json: bool, tsv: bool) -> Result<()> {
+/// Show a file's parsed document in `format`, or its basic info
+pub fn show_file(
+    path: &Path,
+    all_strings: bool,
+    hex: bool,
+    json: bool,
+    format: Option<DocumentFormat>,
+) -> Result<()> {
     let data = fs::read(path).context("Failed to read file")?;
 
     if hex {
@@ -19,25 +27,16 @@ pub fn show_file(path: &Path, all_strings: bool, hex: bool, json: bool, tsv: boo
     }
 
     // Decompress if this is a compressed NCS file
-    let decompressed = if is_ncs(&data) {
+    let decompressed = if find_ncs_start(&data).is_some() {
         decompress_ncs(&data).context("Failed to decompress NCS data")?
     } else {
         data
     };
 
-    // For JSON output, use the structured parser
-    if json {
-        if let Some(doc) = parse_ncs_binary(&decompressed) {
-            println!("{}", serde_json::to_string_pretty(&doc)?);
-            return Ok(());
-        }
-        // Fall back to basic info if structured parse fails
-    }
-
-    // For TSV output, use the structured parser
-    if tsv {
+    // For document output, use the structured parser
+    if let Some(format) = format.or(json.then_some(DocumentFormat::Json)) {
         if let Some(doc) = parse_ncs_binary(&decompressed) {
-            output_tsv(&doc);
+            print!("{}", format_document(&doc, format)?);
             return Ok(());
         }
         // Fall back to basic info if structured parse fails
@@ -48,7 +47,7 @@ pub fn show_file(path: &Path, all_strings: bool, hex: bool, json: bool, tsv: boo
     let info = FileInfo {
         path: path.to_string_lossy().to_string(),
         type_name: content.type_name().to_string(),
-        format_code: content.format_code().to_string(),
+        format_code: content.format(),
         entry_names: if all_strings {
             content.strings.clone()
         } else {
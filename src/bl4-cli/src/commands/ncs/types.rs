@@ -1,28 +1,34 @@
 //! NCS command type definitions
 
+use bl4_ncs::FormatCode;
 use serde::Serialize;
-use std::collections::HashMap;
-
-/// Result of scanning a directory
-#[derive(Debug, Serialize)]
-pub struct ScanResult {
-    pub total_files: usize,
-    pub parsed_files: usize,
-    pub types: HashMap<String, Vec<String>>,
-    pub formats: HashMap<String, usize>,
-}
+use std::collections::BTreeMap;
 
 /// Information about a single NCS file
 #[derive(Debug, Serialize)]
 pub struct FileInfo {
     pub path: String,
     pub type_name: String,
-    pub format_code: String,
+    pub format_code: FormatCode,
     pub entry_names: Vec<String>,
     pub guids: Vec<String>,
     pub numeric_values: Vec<(String, f64)>,
 }
 
+/// A file that `ncs extract` could not read or parse
+#[derive(Debug, Serialize)]
+pub struct ExtractFailure {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Files of the requested type, plus every file that was skipped on error
+#[derive(Debug, Default, Serialize)]
+pub struct ExtractResult {
+    pub files: Vec<FileInfo>,
+    pub failed: Vec<ExtractFailure>,
+}
+
 /// Search result
 #[derive(Debug, Serialize)]
 pub struct SearchMatch {
@@ -88,3 +94,27 @@ pub struct ManufacturerMapping {
     /// Display name (e.g., "Ripper", "Daedalus")
     pub name: String,
 }
+
+/// GUID-to-name index merged across a directory of NCS files
+#[derive(Debug, Default, Serialize)]
+pub struct GuidIndex {
+    /// Each GUID with the name from the first file it appears in
+    pub guids: BTreeMap<String, String>,
+    /// GUIDs that map to different names in different files
+    pub collisions: Vec<GuidCollision>,
+}
+
+/// A GUID with more than one distinct name across files
+#[derive(Debug, Serialize)]
+pub struct GuidCollision {
+    pub guid: String,
+    /// Every file's name for the GUID, in file order
+    pub sources: Vec<GuidSource>,
+}
+
+/// The name one file gives a GUID
+#[derive(Debug, Clone, Serialize)]
+pub struct GuidSource {
+    pub name: String,
+    pub path: String,
+}
@@ -2,10 +2,13 @@
 //!
 //! Provides functions to query and display parts from the parts database.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::Deserialize;
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the parts database location
+pub const PARTS_DB_ENV: &str = "BL4_PARTS_DB";
 
 /// Part categories file structure (for BuildPartsDb command)
 #[derive(Debug, Deserialize)]
@@ -38,6 +41,9 @@ pub struct PartEntry {
     pub name: String,
     pub category: i64,
     pub index: i64,
+    /// Slot name taken from the per-category filename (e.g. "barrel")
+    #[serde(default)]
+    pub slot: Option<String>,
 }
 
 /// Result of querying the parts database
@@ -47,11 +53,32 @@ pub struct PartsQueryResult {
     pub total_parts: usize,
 }
 
+/// First bytes of every SQLite database file
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+/// Whether `path` is a SQLite database (as written by `bl4 parts build-sqlite`)
+fn is_sqlite(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut magic = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .is_ok_and(|()| &magic == SQLITE_MAGIC)
+}
+
 /// Load and parse the parts database from a file or directory of per-category TSVs
+///
+/// Files may be a monolithic TSV, JSON, or a SQLite database built with
+/// `bl4 parts build-sqlite`.
 pub fn load_database(path: &Path) -> Result<PartsDatabase> {
     if path.is_dir() {
         return load_database_dir(path);
     }
+    if is_sqlite(path) {
+        let db = bl4::PartsDatabase::from_sqlite(path)
+            .with_context(|| format!("Failed to read parts database: {:?}", path))?;
+        return Ok(from_library(&db));
+    }
 
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read parts database: {:?}", path))?;
@@ -64,11 +91,17 @@ pub fn load_database(path: &Path) -> Result<PartsDatabase> {
             .lines()
             .skip(1)
             .filter_map(|line| {
-                let mut cols = line.splitn(3, '\t');
+                let mut cols = line.split('\t');
                 let category = cols.next()?.parse::<i64>().ok()?;
                 let index = cols.next()?.parse::<i64>().ok()?;
                 let name = cols.next()?.to_string();
-                Some(PartEntry { name, category, index })
+                let slot = cols.next().map(str::to_string);
+                Some(PartEntry {
+                    name,
+                    category,
+                    index,
+                    slot,
+                })
             })
             .collect();
         Ok(PartsDatabase { parts })
@@ -77,9 +110,94 @@ pub fn load_database(path: &Path) -> Result<PartsDatabase> {
     }
 }
 
+/// Where the parts database is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartsDbSource {
+    /// A directory of per-category TSVs or a single TSV/JSON file
+    Path(PathBuf),
+    /// The database compiled into the `bl4` library
+    Embedded,
+}
+
+/// Resolve the parts database location
+///
+/// Resolution order:
+/// 1. `--parts-db` flag
+/// 2. `BL4_PARTS_DB` environment variable
+/// 3. `$XDG_DATA_HOME/bl4/parts` (platform data dir), if it exists
+/// 4. The embedded database
+pub fn resolve_parts_db(flag: Option<&Path>) -> PartsDbSource {
+    resolve_parts_db_with(
+        flag,
+        std::env::var_os(PARTS_DB_ENV).map(PathBuf::from),
+        dirs::data_dir().map(|d| d.join("bl4").join("parts")),
+    )
+}
+
+fn resolve_parts_db_with(
+    flag: Option<&Path>,
+    env: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+) -> PartsDbSource {
+    if let Some(path) = flag {
+        return PartsDbSource::Path(path.to_path_buf());
+    }
+    if let Some(path) = env.filter(|p| !p.as_os_str().is_empty()) {
+        return PartsDbSource::Path(path);
+    }
+    match data_dir {
+        Some(path) if path.exists() => PartsDbSource::Path(path),
+        _ => PartsDbSource::Embedded,
+    }
+}
+
+/// Load the parts database from a resolved source
+pub fn load_parts_db(source: &PartsDbSource) -> Result<PartsDatabase> {
+    match source {
+        PartsDbSource::Path(path) => load_database(path),
+        PartsDbSource::Embedded => Ok(embedded_database()),
+    }
+}
+
+/// Convert the library's embedded database into the CLI representation
+fn embedded_database() -> PartsDatabase {
+    from_library(bl4::embedded_parts_database())
+}
+
+/// Convert a library database into the CLI representation
+fn from_library(db: &bl4::PartsDatabase) -> PartsDatabase {
+    let parts = db
+        .parts
+        .iter()
+        .map(|p| PartEntry {
+            name: p.name.clone(),
+            category: p.category,
+            index: p.index,
+            slot: Some(p.slot.clone()),
+        })
+        .collect();
+    PartsDatabase { parts }
+}
+
+/// Convert the CLI representation into a library database
+fn to_library(db: &PartsDatabase) -> bl4::PartsDatabase {
+    let parts = db
+        .parts
+        .iter()
+        .map(|p| bl4::PartRecord {
+            category: p.category,
+            index: p.index,
+            name: p.name.clone(),
+            slot: p.slot.clone().unwrap_or_else(|| "unknown".to_string()),
+        })
+        .collect();
+    bl4::PartsDatabase { parts }
+}
+
 /// Load parts database from a directory of per-category TSV files
 ///
-/// Each file is named `{category_id}.tsv` with format `index\tname`.
+/// Each file is named `{slot}-{category_id}.tsv` (or `{category_id}.tsv`)
+/// with format `index\tname`.
 fn load_database_dir(dir: &Path) -> Result<PartsDatabase> {
     let mut parts = Vec::new();
 
@@ -93,10 +211,14 @@ fn load_database_dir(dir: &Path) -> Result<PartsDatabase> {
             continue;
         }
 
-        let category: i64 = match path.file_stem().and_then(|s| s.to_str()).and_then(parse_category_id) {
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let category: i64 = match parse_category_id(stem) {
             Some(id) => id,
             None => continue,
         };
+        let slot = parse_slot_name(stem);
 
         let content = std::fs::read_to_string(&path)
             .with_context(|| format!("Failed to read {:?}", path))?;
@@ -111,7 +233,12 @@ fn load_database_dir(dir: &Path) -> Result<PartsDatabase> {
                 Some(n) => n.to_string(),
                 None => continue,
             };
-            parts.push(PartEntry { name, category, index });
+            parts.push(PartEntry {
+                name,
+                category,
+                index,
+                slot: Some(slot.clone()),
+            });
         }
     }
 
@@ -129,6 +256,70 @@ fn parse_category_id(stem: &str) -> Option<i64> {
     stem.parse().ok()
 }
 
+/// Extract the slot name from a filename stem like "barrel-10001"
+///
+/// Plain numeric filenames carry no slot info and map to "unknown",
+/// matching the build script.
+fn parse_slot_name(stem: &str) -> String {
+    match stem.rfind('-') {
+        Some(pos) if stem[pos + 1..].parse::<i64>().is_ok() => stem[..pos].to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Serialize a database as a monolithic `category\tindex\tname\tslot` TSV
+///
+/// This is the same layout `bl4`'s build script embeds, so the output can
+/// replace the generated `parts_database.tsv` directly.
+pub fn to_tsv(db: &PartsDatabase) -> String {
+    let mut out = String::from("category\tindex\tname\tslot\n");
+    for part in &db.parts {
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\n",
+            part.category,
+            part.index,
+            part.name,
+            part.slot.as_deref().unwrap_or("unknown")
+        ));
+    }
+    out
+}
+
+/// Merge a directory of per-category TSVs into a single database file
+///
+/// Writes to stdout when no output path is given.
+pub fn merge(dir: &Path, output: Option<&Path>) -> Result<()> {
+    let db = load_database_dir(dir)?;
+    let tsv = to_tsv(&db);
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &tsv).with_context(|| format!("Failed to write {:?}", path))?;
+            eprintln!("Merged {} parts into {}", db.parts.len(), path.display());
+        }
+        None => print!("{}", tsv),
+    }
+
+    Ok(())
+}
+
+/// Import a directory of per-category TSVs into a SQLite database
+///
+/// The result is keyed by `(category, index)` and loads with one query, so
+/// pointing `--parts-db` at it avoids re-reading every TSV per invocation.
+pub fn build_sqlite(dir: &Path, output: &Path) -> Result<()> {
+    let db = load_database_dir(dir)?;
+    to_library(&db)
+        .write_sqlite(output)
+        .with_context(|| format!("Failed to write {:?}", output))?;
+    eprintln!(
+        "Imported {} parts into {}",
+        db.parts.len(),
+        output.display()
+    );
+    Ok(())
+}
+
 /// Build a category-to-parts mapping from the database
 pub fn build_category_map(db: &PartsDatabase) -> BTreeMap<i64, Vec<&PartEntry>> {
     let mut by_category: BTreeMap<i64, Vec<&PartEntry>> = BTreeMap::new();
@@ -170,19 +361,56 @@ pub enum FindCategoryResult {
     Multiple(Vec<(i64, String)>),
 }
 
+/// Derive a normalized part-type label from a part name
+///
+/// Handles every naming style in the reference data:
+/// - `DAD_PS.part_barrel_02_finnty` / `part_grip_01` → `"barrel"` / `"grip"`
+/// - `DAD_AR.comp_05_legendary_OM` → `"rarity"`
+/// - `JAK_PS_Grip_01` (`MANU_TYPE_<Category>_NN`) → `"grip"`
+///
+/// Anything else falls back to `"other"`.
+pub fn part_type_label(name: &str) -> String {
+    let slot = bl4::manifest::slot_from_part_name(name);
+    if slot != "unknown" {
+        return slot.to_string();
+    }
+
+    let segment = name.split('.').next_back().unwrap_or(name);
+
+    // part_<type> with a slot the manifest doesn't know about yet
+    if let Some(rest) = segment.strip_prefix("part_") {
+        if let Some(ptype) = rest.split('_').next().filter(|t| !t.is_empty()) {
+            return ptype.to_lowercase();
+        }
+    }
+
+    legacy_type_label(segment).unwrap_or_else(|| "other".to_string())
+}
+
+/// Parse the `MANU_TYPE_<Category>_NN` form, e.g. `JAK_PS_Grip_01`
+fn legacy_type_label(name: &str) -> Option<String> {
+    let tokens: Vec<&str> = name.split('_').collect();
+    let [manu, wtype, category @ .., number] = tokens.as_slice() else {
+        return None;
+    };
+
+    let is_code = |t: &str, len: usize| t.len() == len && t.chars().all(|c| c.is_ascii_uppercase());
+    if !is_code(*manu, 3) || !is_code(*wtype, 2) || category.is_empty() {
+        return None;
+    }
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(category.join("_").to_lowercase())
+}
+
 /// Group parts by type (barrel, grip, mag, etc.)
 pub fn group_parts_by_type<'a>(parts: &[&'a PartEntry]) -> BTreeMap<String, Vec<&'a PartEntry>> {
     let mut by_type: BTreeMap<String, Vec<&'a PartEntry>> = BTreeMap::new();
 
     for &part in parts {
-        let part_type = part
-            .name
-            .split(".part_")
-            .nth(1)
-            .and_then(|s| s.split('_').next())
-            .unwrap_or("other")
-            .to_string();
-        by_type.entry(part_type).or_default().push(part);
+        by_type.entry(part_type_label(&part.name)).or_default().push(part);
     }
 
     by_type
@@ -228,37 +456,22 @@ pub fn show_category_parts(cat_id: i64, parts: Option<&Vec<&PartEntry>>) {
     }
 }
 
-/// Show usage help for the parts command
-pub fn show_usage() {
-    println!("Usage: bl4 parts --weapon <name> OR --category <id> OR --list");
-    println!();
-    println!("Examples:");
-    println!("  bl4 parts --list                 # List all categories");
-    println!("  bl4 parts --weapon 'Jakobs'      # Find Jakobs weapons");
-    println!("  bl4 parts --category 3           # Show parts for category 3");
+/// List all categories in the parts database
+pub fn list(parts_db: Option<&Path>) -> Result<()> {
+    let db = load_parts_db(&resolve_parts_db(parts_db))?;
+    list_categories(&build_category_map(&db), db.parts.len());
+    Ok(())
 }
 
-/// Main handler for the parts command
-pub fn handle(
-    weapon: Option<String>,
-    category: Option<i64>,
-    list: bool,
-    parts_db: &Path,
-) -> Result<()> {
-    let db = load_database(parts_db)?;
+/// Show parts for a category, given by ID or looked up by weapon name
+pub fn show(weapon: Option<&str>, category: Option<i64>, parts_db: Option<&Path>) -> Result<()> {
+    let db = load_parts_db(&resolve_parts_db(parts_db))?;
     let by_category = build_category_map(&db);
 
-    if list {
-        list_categories(&by_category, db.parts.len());
-        return Ok(());
-    }
-
-    // Find target category
-    let target_cat: Option<i64> = if let Some(cat) = category {
-        Some(cat)
-    } else if let Some(ref wname) = weapon {
-        match find_category_by_name(&by_category, wname) {
-            Some(FindCategoryResult::Single(cat_id)) => Some(cat_id),
+    let target_cat = match (category, weapon) {
+        (Some(cat), _) => cat,
+        (None, Some(wname)) => match find_category_by_name(&by_category, wname) {
+            Some(FindCategoryResult::Single(cat_id)) => cat_id,
             Some(FindCategoryResult::Multiple(matches)) => {
                 println!(
                     "Multiple matches for '{}'. Please be more specific or use -c <category_id>",
@@ -269,18 +482,12 @@ pub fn handle(
                 }
                 return Ok(());
             }
-            None => None,
-        }
-    } else {
-        None
+            None => bail!("No category matches '{}' (see `bl4 parts list`)", wname),
+        },
+        (None, None) => bail!("Either a weapon name or --category is required"),
     };
 
-    if let Some(cat_id) = target_cat {
-        show_category_parts(cat_id, by_category.get(&cat_id));
-    } else {
-        show_usage();
-    }
-
+    show_category_parts(target_cat, by_category.get(&target_cat));
     Ok(())
 }
 
@@ -295,26 +502,31 @@ mod tests {
                     name: "JAK_PS.part_barrel_01".to_string(),
                     category: 3,
                     index: 0,
+                    slot: None,
                 },
                 PartEntry {
                     name: "JAK_PS.part_barrel_02".to_string(),
                     category: 3,
                     index: 1,
+                    slot: None,
                 },
                 PartEntry {
                     name: "JAK_PS.part_grip_01".to_string(),
                     category: 3,
                     index: 2,
+                    slot: None,
                 },
                 PartEntry {
                     name: "VLA_AR.part_barrel_01".to_string(),
                     category: 5,
                     index: 0,
+                    slot: None,
                 },
                 PartEntry {
                     name: "VLA_AR.part_mag_01".to_string(),
                     category: 5,
                     index: 1,
+                    slot: None,
                 },
             ],
         }
@@ -348,6 +560,7 @@ mod tests {
             name: "TEST.part_barrel_01".to_string(),
             category: 1,
             index: 42,
+            slot: None,
         };
 
         assert_eq!(part.name, "TEST.part_barrel_01");
@@ -400,6 +613,112 @@ mod tests {
         assert_eq!(db.parts[2].category, 5);
     }
 
+    #[test]
+    fn test_parts_database_load_tsv_with_slot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parts.tsv");
+        std::fs::write(&path, "category\tindex\tname\tslot\n3\t1\tJAK_PS.part_barrel_01\tbarrel\n").unwrap();
+
+        let db = load_database(&path).unwrap();
+        assert_eq!(db.parts[0].name, "JAK_PS.part_barrel_01");
+        assert_eq!(db.parts[0].slot.as_deref(), Some("barrel"));
+    }
+
+    #[test]
+    fn test_build_sqlite_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let parts_dir = dir.path().join("parts");
+        std::fs::create_dir(&parts_dir).unwrap();
+
+        std::fs::write(parts_dir.join("barrel-3.tsv"), "index\tname\n1\tJAK_PS.part_barrel_01\n").unwrap();
+        std::fs::write(parts_dir.join("5.tsv"), "index\tname\n0\tVLA_AR.part_mag_01\n").unwrap();
+
+        let out = dir.path().join("parts.db");
+        build_sqlite(&parts_dir, &out).unwrap();
+        assert!(is_sqlite(&out));
+        assert!(!is_sqlite(&parts_dir.join("5.tsv")));
+
+        let db = load_database(&out).unwrap();
+        assert_eq!(db.parts.len(), 2);
+        assert_eq!(db.parts[0].name, "JAK_PS.part_barrel_01");
+        assert_eq!(db.parts[0].slot.as_deref(), Some("barrel"));
+        assert_eq!(db.parts[1].slot.as_deref(), Some("unknown"));
+    }
+
+    #[test]
+    fn test_merge_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let parts_dir = dir.path().join("parts");
+        std::fs::create_dir(&parts_dir).unwrap();
+
+        std::fs::write(parts_dir.join("barrel-3.tsv"), "index\tname\n1\tJAK_PS.part_barrel_01\n").unwrap();
+        std::fs::write(parts_dir.join("5.tsv"), "index\tname\n0\tVLA_AR.part_mag_01\n").unwrap();
+
+        let out = dir.path().join("parts.tsv");
+        merge(&parts_dir, Some(&out)).unwrap();
+
+        let content = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(
+            content,
+            "category\tindex\tname\tslot\n3\t1\tJAK_PS.part_barrel_01\tbarrel\n5\t0\tVLA_AR.part_mag_01\tunknown\n"
+        );
+
+        let db = load_database(&out).unwrap();
+        assert_eq!(db.parts.len(), 2);
+        assert_eq!(db.parts[1].slot.as_deref(), Some("unknown"));
+    }
+
+    #[test]
+    fn test_resolve_parts_db_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let xdg = dir.path().join("bl4").join("parts");
+        let flag = PathBuf::from("flag/parts");
+        let env = PathBuf::from("env/parts");
+
+        assert_eq!(
+            resolve_parts_db_with(Some(&flag), Some(env.clone()), Some(xdg.clone())),
+            PartsDbSource::Path(flag)
+        );
+        assert_eq!(
+            resolve_parts_db_with(None, Some(env.clone()), Some(xdg.clone())),
+            PartsDbSource::Path(env)
+        );
+        // Missing data dir falls through to the embedded database
+        assert_eq!(
+            resolve_parts_db_with(None, None, Some(xdg.clone())),
+            PartsDbSource::Embedded
+        );
+        std::fs::create_dir_all(&xdg).unwrap();
+        assert_eq!(
+            resolve_parts_db_with(None, None, Some(xdg.clone())),
+            PartsDbSource::Path(xdg)
+        );
+        assert_eq!(resolve_parts_db_with(None, None, None), PartsDbSource::Embedded);
+    }
+
+    #[test]
+    fn test_resolve_parts_db_flag_wins() {
+        // Whatever BL4_PARTS_DB holds in the test environment, the flag wins
+        let flag = PathBuf::from("flag/parts");
+        assert_eq!(resolve_parts_db(Some(&flag)), PartsDbSource::Path(flag));
+    }
+
+    #[test]
+    fn test_resolve_parts_db_empty_env_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            resolve_parts_db_with(None, Some(PathBuf::new()), Some(dir.path().to_path_buf())),
+            PartsDbSource::Path(dir.path().to_path_buf())
+        );
+    }
+
+    #[test]
+    fn test_load_embedded_parts_db() {
+        let db = load_parts_db(&PartsDbSource::Embedded).unwrap();
+        assert!(!db.parts.is_empty());
+        assert!(db.parts.iter().all(|p| p.slot.is_some()));
+    }
+
     #[test]
     fn test_empty_database() {
         let db = PartsDatabase { parts: vec![] };
@@ -414,6 +733,7 @@ mod tests {
             name: "UNKNOWN_FORMAT".to_string(),
             category: 1,
             index: 0,
+            slot: None,
         }];
         let refs: Vec<&PartEntry> = parts.iter().collect();
         let by_type = group_parts_by_type(&refs);
@@ -421,4 +741,51 @@ mod tests {
         // Should fall back to "other" type
         assert!(by_type.contains_key("other"));
     }
+
+    #[test]
+    fn test_part_type_label_naming_styles() {
+        // MANU_TYPE.part_<type>_NN
+        assert_eq!(part_type_label("JAK_SG.part_foregrip_03"), "foregrip");
+        assert_eq!(part_type_label("DAD_PS.part_barrel_02_finnty"), "barrel");
+        // Bare part names from per-category TSVs
+        assert_eq!(part_type_label("part_scope_acc_s01_l01_a"), "scope");
+        assert_eq!(part_type_label("part_body_b"), "body");
+        // comp_NN rarity compositions
+        assert_eq!(part_type_label("DAD_AR.comp_05_legendary_OM"), "rarity");
+        assert_eq!(part_type_label("comp_01_common"), "rarity");
+        // MANU_TYPE_<Category>_NN
+        assert_eq!(part_type_label("JAK_PS_Grip_01"), "grip");
+        assert_eq!(part_type_label("VLA_AR_Barrel_Acc_02"), "barrel_acc");
+        // Unknown part_ slot keeps its first token
+        assert_eq!(part_type_label("TOR_HW.part_widget_01"), "widget");
+    }
+
+    #[test]
+    fn test_part_type_label_other() {
+        assert_eq!(part_type_label("UNKNOWN_FORMAT"), "other");
+        assert_eq!(part_type_label("DAD_PS.Zipper"), "other");
+        assert_eq!(part_type_label("DAD_AR_Lumberjack"), "other");
+        assert_eq!(part_type_label("passive_red_1_1_tier_1"), "other");
+    }
+
+    #[test]
+    fn test_group_parts_mixed_styles() {
+        let names = ["JAK_PS.part_grip_01", "JAK_PS_Grip_02", "JAK_PS.comp_05_legendary_kingsgambit"];
+        let parts: Vec<PartEntry> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| PartEntry {
+                name: name.to_string(),
+                category: 3,
+                index: i as i64,
+                slot: None,
+            })
+            .collect();
+        let refs: Vec<&PartEntry> = parts.iter().collect();
+        let by_type = group_parts_by_type(&refs);
+
+        assert_eq!(by_type.get("grip").map(|v| v.len()), Some(2));
+        assert_eq!(by_type.get("rarity").map(|v| v.len()), Some(1));
+        assert!(!by_type.contains_key("other"));
+    }
 }
@@ -140,6 +140,73 @@ pub fn edit(args: &SaveArgs) -> Result<()> {
     })
 }
 
+fn is_sav_path(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "sav")
+}
+
+/// Load a save from an encrypted .sav or a decrypted YAML file
+fn load_save_any(path: &Path, steam_id: Option<String>) -> Result<bl4::SaveFile> {
+    let data = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let yaml_data = if is_sav_path(path) {
+        let steam_id = get_steam_id(steam_id)?;
+        bl4::decrypt_sav(&data, &steam_id).context("Failed to decrypt save file")?
+    } else {
+        data
+    };
+
+    bl4::SaveFile::from_yaml(&yaml_data).context("Failed to parse save file")
+}
+
+/// Handle `save edit --preset`: apply a ChangeSet preset to a save
+pub fn apply_preset(
+    args: &SaveArgs,
+    preset: &Path,
+    output: Option<&Path>,
+    dry_run: bool,
+) -> Result<()> {
+    let preset_yaml = fs::read_to_string(preset)
+        .with_context(|| format!("Failed to read preset {}", preset.display()))?;
+    let changes = bl4::ChangeSet::from_yaml(&preset_yaml).context("Failed to parse preset")?;
+    for warning in changes.validate() {
+        eprintln!("Warning: {}", warning);
+    }
+
+    // In-place .sav edits go through the usual backup/encrypt path
+    if is_sav_path(&args.input) && output.is_none() && !dry_run {
+        return with_save_file(args, |save| {
+            changes.apply(save).context("Failed to apply preset")?;
+            eprintln!("Applied {} change(s) from {}", changes.len(), preset.display());
+            Ok(())
+        });
+    }
+
+    let original = load_save_any(&args.input, args.steam_id.clone())?;
+    let mut edited = original.clone();
+    changes.apply(&mut edited).context("Failed to apply preset")?;
+
+    if dry_run {
+        for change in original.diff(&edited) {
+            println!("{}", change);
+        }
+        return Ok(());
+    }
+
+    let target = output.unwrap_or(&args.input);
+    let yaml = edited.to_yaml().context("Failed to serialize YAML")?;
+    let bytes = if is_sav_path(target) {
+        let steam_id = get_steam_id(args.steam_id.clone())?;
+        bl4::encrypt_sav(&yaml, &steam_id).context("Failed to encrypt save file")?
+    } else {
+        yaml
+    };
+
+    fs::write(target, &bytes).with_context(|| format!("Failed to write {}", target.display()))?;
+    eprintln!("Applied {} change(s) to {}", changes.len(), target.display());
+
+    Ok(())
+}
+
 /// Handle `save get` command
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 pub fn get(
@@ -178,6 +245,15 @@ pub fn get(
         if let Some(diff) = save.get_difficulty() {
             println!("Difficulty: {}", diff);
         }
+        if let Some(playtime) = save.playtime() {
+            let secs = playtime.as_secs();
+            println!(
+                "Playtime: {}h {:02}m {:02}s",
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60
+            );
+        }
         if show_all || info {
             println!();
         }
@@ -207,6 +283,51 @@ pub fn get(
     Ok(())
 }
 
+/// Handle `save keys` command
+pub fn keys(input: &Path, steam_id: Option<String>, path: Option<&str>) -> Result<()> {
+    let steam_id = get_steam_id(steam_id)?;
+    let encrypted =
+        fs::read(input).with_context(|| format!("Failed to read {}", input.display()))?;
+
+    let yaml_data =
+        bl4::decrypt_sav(&encrypted, &steam_id).context("Failed to decrypt save file")?;
+
+    let save = bl4::SaveFile::from_yaml(&yaml_data).context("Failed to parse save file")?;
+
+    let path = path.unwrap_or("");
+    if !path.is_empty() {
+        save.get(path).context("Query failed")?;
+    }
+
+    for key in save.keys(path) {
+        println!("{}", key);
+    }
+
+    Ok(())
+}
+
+/// Handle `save summary` command
+pub fn summary(input: &Path, steam_id: Option<String>) -> Result<()> {
+    let save = load_save_any(input, steam_id)?;
+    let summary = save.inventory_summary();
+
+    println!(
+        "Backpack: {} items, {} empty slots",
+        summary.backpack_items, summary.empty_slots
+    );
+    println!("Favorites: {}", summary.favorites);
+    println!("Junk: {}", summary.junk);
+
+    if !summary.by_type.is_empty() {
+        println!("\nBy type:");
+        for (item_type, count) in &summary.by_type {
+            println!("  {:<20} {}", item_type, count);
+        }
+    }
+
+    Ok(())
+}
+
 /// Handle `save set` command
 pub fn set(args: &SaveArgs, path: &str, value: &str, raw: bool) -> Result<()> {
     with_save_file(args, |save| {
@@ -297,6 +418,10 @@ pub fn validate_items(args: &SaveArgs) -> Result<()> {
         counts[2]
     );
 
+    if counts[1] > 0 {
+        let illegal = format!("{} illegal items", counts[1]);
+        return Err(crate::exit_code::ValidationFailed(illegal).into());
+    }
     Ok(())
 }
 
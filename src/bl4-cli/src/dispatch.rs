@@ -21,7 +21,13 @@ pub fn dispatch_save(args: SaveArgs) -> Result<()> {
             commands::save::encrypt(&args.input, yaml.as_deref(), args.steam_id)
         }
 
-        Some(SaveAction::Edit) => commands::save::edit(&args),
+        Some(SaveAction::Edit {
+            preset: Some(ref preset),
+            ref output,
+            dry_run,
+        }) => commands::save::apply_preset(&args, preset, output.as_deref(), dry_run),
+
+        Some(SaveAction::Edit { .. }) => commands::save::edit(&args),
 
         Some(SaveAction::Get {
             query,
@@ -39,6 +45,12 @@ pub fn dispatch_save(args: SaveArgs) -> Result<()> {
             all,
         ),
 
+        Some(SaveAction::Keys { ref path }) => {
+            commands::save::keys(&args.input, args.steam_id.clone(), path.as_deref())
+        }
+
+        Some(SaveAction::Summary) => commands::save::summary(&args.input, args.steam_id),
+
         Some(SaveAction::Set {
             ref path,
             ref value,
@@ -60,6 +72,29 @@ pub fn dispatch_save(args: SaveArgs) -> Result<()> {
     }
 }
 
+/// Dispatch item subcommands
+pub fn dispatch_item(command: ItemCommand) -> Result<()> {
+    match command {
+        ItemCommand::Describe { serial, parts_db } => {
+            commands::item::describe(&serial, parts_db.as_deref())
+        }
+    }
+}
+
+/// Dispatch parts subcommands
+pub fn dispatch_parts(command: PartsCommand) -> Result<()> {
+    match command {
+        PartsCommand::List { parts_db } => commands::parts::list(parts_db.as_deref()),
+        PartsCommand::Show {
+            weapon,
+            category,
+            parts_db,
+        } => commands::parts::show(weapon.as_deref(), category, parts_db.as_deref()),
+        PartsCommand::Merge { dir, output } => commands::parts::merge(&dir, output.as_deref()),
+        PartsCommand::BuildSqlite { dir, output } => commands::parts::build_sqlite(&dir, &output),
+    }
+}
+
 /// Dispatch serial subcommands
 pub fn dispatch_serial(command: SerialCommand) -> Result<()> {
     match command {
@@ -74,7 +109,15 @@ pub fn dispatch_serial(command: SerialCommand) -> Result<()> {
             remove,
             add,
         } => commands::serial::decode(
-            &serial, verbose, debug, analyze, rarity, short, &parts_db, &remove, &add,
+            &serial,
+            verbose,
+            debug,
+            analyze,
+            rarity,
+            short,
+            parts_db.as_deref(),
+            &remove,
+            &add,
         ),
 
         SerialCommand::Encode { serial } => commands::serial::encode(&serial),
@@ -0,0 +1,142 @@
+//! Process exit codes and top-level error reporting
+//!
+//! Failures exit with a code that says what kind of problem it was, so
+//! scripts can branch without scraping stderr:
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 1 | Any other failure |
+//! | 2 | An input file does not exist |
+//! | 3 | An input could not be decoded (decryption, YAML, NCS, serial, JSON) |
+//! | 4 | The input decoded but failed validation |
+//! | 64 | Invalid command-line usage |
+
+use std::fmt;
+use std::io;
+use std::process::ExitCode;
+
+pub const FAILURE: u8 = 1;
+pub const FILE_NOT_FOUND: u8 = 2;
+pub const PARSE_ERROR: u8 = 3;
+pub const VALIDATION_FAILED: u8 = 4;
+/// `EX_USAGE` from sysexits.h; clap's own default of 2 would clash with [`FILE_NOT_FOUND`]
+pub const USAGE: u8 = 64;
+
+/// A check the user asked for ran and found problems
+#[derive(Debug)]
+pub struct ValidationFailed(pub String);
+
+impl fmt::Display for ValidationFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Validation failed: {}", self.0)
+    }
+}
+
+impl std::error::Error for ValidationFailed {}
+
+/// Exit code for an error, from the first cause in its chain that has one
+pub fn for_error(err: &anyhow::Error) -> u8 {
+    err.chain().find_map(for_cause).unwrap_or(FAILURE)
+}
+
+fn for_cause(cause: &(dyn std::error::Error + 'static)) -> Option<u8> {
+    if let Some(e) = cause.downcast_ref::<io::Error>() {
+        return (e.kind() == io::ErrorKind::NotFound).then_some(FILE_NOT_FOUND);
+    }
+    if cause.is::<ValidationFailed>() {
+        return Some(VALIDATION_FAILED);
+    }
+    if let Some(e) = cause.downcast_ref::<bl4::SaveError>() {
+        return for_save_error(e);
+    }
+    if let Some(e) = cause.downcast_ref::<bl4::CryptoError>() {
+        return Some(match e {
+            bl4::CryptoError::InvalidSteamId => VALIDATION_FAILED,
+            _ => PARSE_ERROR,
+        });
+    }
+    if let Some(e) = cause.downcast_ref::<bl4_ncs::Error>() {
+        return match e {
+            // The chain continues into the io::Error itself
+            bl4_ncs::Error::Io(_) => None,
+            _ => Some(PARSE_ERROR),
+        };
+    }
+    let is_parse_error = cause.is::<bl4::SerialError>()
+        || cause.is::<serde_yaml::Error>()
+        || cause.is::<serde_json::Error>()
+        || cause.is::<std::string::FromUtf8Error>();
+    is_parse_error.then_some(PARSE_ERROR)
+}
+
+/// Decode failures are parse errors; a save that decoded but doesn't fit the
+/// requested query or edit (wrong kind, missing key, bad slot...) failed validation
+fn for_save_error(err: &bl4::SaveError) -> Option<u8> {
+    use bl4::SaveError;
+
+    match err {
+        SaveError::YamlParse(_) | SaveError::Serial(_) | SaveError::UnsupportedCompression(_) => {
+            Some(PARSE_ERROR)
+        }
+        SaveError::Io(_) => None,
+        SaveError::FodCompress(_) => Some(FAILURE),
+        _ => Some(VALIDATION_FAILED),
+    }
+}
+
+/// Print `err` to stderr and turn it into the process exit code
+///
+/// With `json`, stderr gets a single `{"error": ..., "code": N}` object
+/// instead of the usual `Error: ...` chain.
+pub fn report(err: &anyhow::Error, json: bool) -> ExitCode {
+    let code = for_error(err);
+    if json {
+        let body = serde_json::json!({ "error": format!("{:#}", err), "code": code });
+        eprintln!("{}", body);
+    } else {
+        eprintln!("Error: {:?}", err);
+    }
+    ExitCode::from(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_missing_file_behind_context() {
+        let err = std::fs::read("/nonexistent/bl4/1.sav")
+            .context("Failed to read save")
+            .unwrap_err();
+        assert_eq!(for_error(&err), FILE_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_decode_errors_are_parse_errors() {
+        let err =
+            anyhow::Error::new(bl4::CryptoError::InvalidSize(17)).context("Failed to decrypt");
+        assert_eq!(for_error(&err), PARSE_ERROR);
+
+        let err = anyhow::Error::new(bl4_ncs::Error::Oodle("bad block".into()));
+        assert_eq!(for_error(&err), PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let err = anyhow::Error::new(bl4::SaveError::NotABl4Save);
+        assert_eq!(for_error(&err), VALIDATION_FAILED);
+
+        let err = anyhow::Error::new(ValidationFailed("2 illegal items".into()));
+        assert_eq!(for_error(&err), VALIDATION_FAILED);
+    }
+
+    #[test]
+    fn test_other_errors_fall_back_to_failure() {
+        assert_eq!(for_error(&anyhow::anyhow!("something broke")), FAILURE);
+
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert_eq!(for_error(&anyhow::Error::new(denied)), FAILURE);
+    }
+}
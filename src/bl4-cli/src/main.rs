@@ -5,6 +5,7 @@ mod cli;
 mod commands;
 mod config;
 mod dispatch;
+mod exit_code;
 mod file_io;
 #[cfg(feature = "research")]
 mod manifest;
@@ -12,20 +13,35 @@ mod memory;
 
 use anyhow::Result;
 use clap::Parser;
+use std::process::ExitCode;
 
 use cli::*;
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     // Shorthand: `bl4 1.sav get --level` → `bl4 save 1.sav get --level`
-    let args: Vec<String> = std::env::args().collect();
-    let cli = if args.len() > 1 && args[1].ends_with(".sav") {
-        let mut rewritten = vec![args[0].clone(), "save".to_string()];
-        rewritten.extend_from_slice(&args[1..]);
-        Cli::parse_from(rewritten)
-    } else {
-        Cli::parse()
+    let mut args: Vec<String> = std::env::args().collect();
+    if args.len() > 1 && args[1].ends_with(".sav") {
+        args.insert(1, "save".to_string());
+    }
+
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        // --help and --version land here too and print to stdout
+        Err(e) if !e.use_stderr() => e.exit(),
+        Err(e) => {
+            let _ = e.print();
+            return ExitCode::from(exit_code::USAGE);
+        }
     };
 
+    let json_errors = cli.json_errors;
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => exit_code::report(&e, json_errors),
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
     match cli.command {
         Commands::Configure { steam_id, show } => {
             commands::configure::handle(steam_id, show)?;
@@ -43,14 +59,9 @@ fn main() -> Result<()> {
 
         Commands::Serial { command } => dispatch::dispatch_serial(command)?,
 
-        Commands::Parts {
-            weapon,
-            category,
-            list,
-            parts_db,
-        } => {
-            commands::parts::handle(weapon, category, list, &parts_db)?;
-        }
+        Commands::Item { command } => dispatch::dispatch_item(command)?,
+
+        Commands::Parts { command } => dispatch::dispatch_parts(command)?,
 
         Commands::Memory {
             preload,
@@ -75,6 +86,8 @@ fn main() -> Result<()> {
 
         Commands::Drops { command } => commands::drops::handle(command)?,
 
+        Commands::Examples => commands::examples::handle(),
+
         #[cfg(feature = "research")]
         Commands::Manifest {
             dump,
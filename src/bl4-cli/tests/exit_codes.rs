@@ -0,0 +1,46 @@
+//! Exit codes and `--json-errors` output of the `bl4` binary
+
+use std::process::{Command, Output};
+
+const STEAM_ID: &str = "76561197960521364";
+
+fn bl4(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bl4"))
+        .args(args)
+        .output()
+        .expect("failed to run bl4")
+}
+
+#[test]
+fn missing_save_exits_2() {
+    let out = bl4(&["save", "/nonexistent/bl4/1.sav", "decrypt", "-s", STEAM_ID]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn undecryptable_save_exits_3_with_json_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let sav = dir.path().join("1.sav");
+    std::fs::write(&sav, [0u8; 17]).unwrap();
+
+    let out = bl4(&[
+        "--json-errors",
+        "save",
+        sav.to_str().unwrap(),
+        "decrypt",
+        "-s",
+        STEAM_ID,
+    ]);
+    assert_eq!(out.status.code(), Some(3));
+
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    let error: serde_json::Value = serde_json::from_str(stderr.trim()).unwrap();
+    assert_eq!(error["code"], 3);
+    assert!(error["error"].as_str().unwrap().contains("multiple of 16"));
+}
+
+#[test]
+fn bad_usage_exits_64() {
+    let out = bl4(&["save"]);
+    assert_eq!(out.status.code(), Some(64));
+}
@@ -88,14 +88,24 @@ impl<'a> BitReader<'a> {
         self.byte_pos * 8 + self.bit_pos as usize
     }
 
-    /// Get total bits available
-    pub fn total_bits(&self) -> usize {
+    /// Get total length of the underlying data in bits
+    pub fn len_bits(&self) -> usize {
         self.data.len() * 8
     }
 
+    /// Get total bits available (same as `len_bits`)
+    pub fn total_bits(&self) -> usize {
+        self.len_bits()
+    }
+
+    /// Get number of bits left to read (0 if seeked past the end)
+    pub fn remaining_bits(&self) -> usize {
+        self.len_bits().saturating_sub(self.position())
+    }
+
     /// Check if n more bits are available
     pub fn has_bits(&self, n: usize) -> bool {
-        self.position() + n <= self.total_bits()
+        n <= self.remaining_bits()
     }
 
     /// Read a FixedWidthIntArray24 header (24-bit count + 8-bit width)
@@ -111,6 +121,9 @@ impl<'a> BitReader<'a> {
         if width == 0 || width > 32 {
             return Some(Vec::new());
         }
+        if !self.has_bits(count as usize * width as usize) {
+            return None;
+        }
         let mut values = Vec::with_capacity(count as usize);
         for _ in 0..count {
             values.push(self.read_bits(width)?);
@@ -126,6 +139,11 @@ impl<'a> BitReader<'a> {
         }
     }
 
+    /// Get the whole underlying buffer, regardless of position
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
     /// Get remaining bytes (for debugging)
     pub fn remaining_bytes(&self) -> &[u8] {
         if self.bit_pos == 0 {
@@ -186,6 +204,25 @@ mod tests {
         assert_eq!(reader.read_bits(12), Some(0xFFF));
     }
 
+    #[test]
+    fn test_remaining_bits() {
+        let data = [0xAB, 0xCD, 0xEF];
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.len_bits(), 24);
+        assert_eq!(reader.remaining_bits(), 24);
+
+        reader.read_bits(5);
+        assert_eq!(reader.remaining_bits(), 19);
+        reader.read_bits(11);
+        assert_eq!(reader.remaining_bits(), 8);
+        assert!(reader.has_bits(8));
+        assert!(!reader.has_bits(9));
+
+        reader.seek(100);
+        assert_eq!(reader.remaining_bits(), 0);
+        assert_eq!(reader.len_bits(), 24);
+    }
+
     #[test]
     fn test_bit_width() {
         assert_eq!(bit_width(0), 1);
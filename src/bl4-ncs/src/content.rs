@@ -5,8 +5,8 @@
 //!
 //! ## Format Codes
 //!
-//! Each NCS file has a 4-letter format code (e.g., "abjx", "abij") that indicates
-//! the structure and features present:
+//! Each NCS file has a format code (e.g., "abjx", "abij"; see [`FormatCode`])
+//! that indicates the structure and features present:
 //!
 //! - `ab` - Base prefix (always present)
 //! - `i` - Indexed entries with table references
@@ -18,9 +18,11 @@
 //! - `h` - Hash table
 //! - `e` - Enum values
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use crate::format_code::FormatCode;
 use crate::header::{find_type_starts, parse_basic_header_with_config, ParseConfig};
+use crate::types::PackedStrings;
 
 /// NCS content header
 #[derive(Debug, Clone)]
@@ -31,6 +33,43 @@ pub struct Header {
     pub format_code: String,
     /// Raw header bytes for analysis
     pub raw_header: Vec<u8>,
+    /// Where the control section starts, if one was found after the string table
+    pub control_section_offset: Option<usize>,
+    /// Where the category names start (right after the control section)
+    pub category_names_offset: Option<usize>,
+}
+
+/// The 4-byte marker between the string table and the category names
+///
+/// ```text
+/// Offset  Size  Description
+/// 0x00    1     Marker (always 0x01)
+/// 0x01    1     Separator (always 0x00)
+/// 0x02    1     Entry/index count
+/// 0x03    1     Type/mode byte: 0x62 ('b') text-based, 0xe9 encoded, 0x06 simple
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControlSection {
+    /// Offset of the marker byte in the decompressed file
+    pub offset: usize,
+    pub count: u8,
+    pub mode: u8,
+}
+
+/// Cheap summary of an NCS file for triage, from [`Content::quick_stats`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickStats {
+    /// Readable strings found by the heuristic scan (same as `Content::strings.len()`)
+    pub string_count: usize,
+    /// Records in the binary section, counted from their length prefixes
+    ///
+    /// Agrees with a full [`crate::parse_ncs_binary`], since both walk the
+    /// same prefixes and resync past a corrupt one the same way; a record
+    /// whose body is corrupt is still counted. None when the string table or
+    /// binary section can't be located.
+    pub estimated_record_count: Option<usize>,
+    /// Format code from the content header
+    pub format: FormatCode,
 }
 
 /// Parsed NCS content
@@ -40,6 +79,11 @@ pub struct Content {
     pub header: Header,
     /// String table entries
     pub strings: Vec<String>,
+    /// Value string of each entry name, indexed once at parse time for
+    /// [`Content::entry`]
+    ///
+    /// Empty for hand-built content unless filled in.
+    pub entry_values: HashMap<String, String>,
     /// Key-value pairs extracted from content
     pub metadata: HashMap<String, String>,
 }
@@ -72,14 +116,17 @@ impl Content {
 
         // String table starts after format code + null terminator
         let strings_start = basic.format_offset + basic.format_code.len() + 1;
-        let strings = extract_strings(data, strings_start);
+        let (strings, entry_values) = extract_strings(data, strings_start);
         let metadata = extract_metadata(&strings);
+        let control_section_offset = find_control_section(data, strings_start);
 
         Some(Self {
             header: Header {
                 type_name: basic.type_name,
                 format_code: basic.format_code,
                 raw_header: basic.prefix_bytes,
+                control_section_offset,
+                category_names_offset: control_section_offset.map(|o| o + CONTROL_SECTION_LEN),
             },
             strings,
             metadata,
@@ -136,14 +183,17 @@ impl Content {
             .ok()?
             .to_string();
 
-        let strings = extract_strings(data, valid_end + 1);
+        let (strings, entry_values) = extract_strings(data, valid_end + 1);
         let metadata = extract_metadata(&strings);
+        let control_section_offset = find_control_section(data, valid_end + 1);
 
         Some(Self {
             header: Header {
                 type_name,
                 format_code,
                 raw_header: data[..type_start].to_vec(),
+                control_section_offset,
+                category_names_offset: control_section_offset.map(|o| o + CONTROL_SECTION_LEN),
             },
             strings,
             metadata,
@@ -160,6 +210,33 @@ impl Content {
         &self.header.format_code
     }
 
+    /// Get the format code as a [`FormatCode`]
+    pub fn format(&self) -> FormatCode {
+        FormatCode::from(self.header.format_code.as_str())
+    }
+
+    /// Summarize string and record counts without decoding any records
+    ///
+    /// `data` is the decompressed file this content was parsed from; the
+    /// record count needs the binary section, which `Content` doesn't keep.
+    pub fn quick_stats(&self, data: &[u8]) -> QuickStats {
+        QuickStats {
+            string_count: self.strings.len(),
+            estimated_record_count: crate::parse::count_records(data),
+            format: self.format(),
+        }
+    }
+
+    /// Parse the control section of `data`, the file this content came from
+    pub fn control_section(&self, data: &[u8]) -> Option<ControlSection> {
+        parse_control_section(data, &self.header)
+    }
+
+    /// Category names of `data`, the file this content came from
+    pub fn category_names(&self, data: &[u8]) -> Vec<String> {
+        parse_category_names(data, &self.header)
+    }
+
     /// Check if this is a specific type
     pub fn is_type(&self, name: &str) -> bool {
         self.header.type_name == name
@@ -167,29 +244,68 @@ impl Content {
 
     /// Check if format has indexed entries
     pub fn has_indexed_entries(&self) -> bool {
-        self.header.format_code.contains('i')
+        self.format().has_tag('i')
     }
 
     /// Check if format has list structure
     pub fn has_list(&self) -> bool {
-        self.header.format_code.contains('l')
+        self.format().has_tag('l')
     }
 
     /// Check if format has properties
     pub fn has_properties(&self) -> bool {
-        self.header.format_code.contains('p')
+        self.format().has_tag('p')
     }
 
     /// Get strings that look like GUIDs
     pub fn guids(&self) -> impl Iterator<Item = &str> {
-        self.strings.iter().filter_map(|s| {
-            // GUID format: 32 hex chars, often with underscores or hyphens
-            if s.len() >= 32 && s.chars().filter(|c| c.is_ascii_hexdigit()).count() >= 28 {
-                Some(s.as_str())
-            } else {
-                None
+        self.strings
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|s| is_guid_like(s))
+    }
+
+    /// Map each GUID in the string table to the name it belongs to
+    ///
+    /// A display string of the form `<Kind>, <GUID>, <DisplayName>`, such as
+    /// `NexusSerialized, ...` or `NameData_<Type>, ...` (see
+    /// [`crate::name_data`]), maps its GUID to the display name. Any other
+    /// GUID string maps to the nearest [`Content::entry_names`] string that
+    /// isn't a GUID itself, preferring the one before it on a tie, since
+    /// names precede their values in the table. GUIDs with no entry name
+    /// anywhere are left out, and the first context found for a repeated
+    /// GUID wins.
+    pub fn guid_map(&self) -> HashMap<String, String> {
+        let names: Vec<usize> = (0..self.strings.len())
+            .filter(|&i| {
+                let s = &self.strings[i];
+                is_entry_name(s) && !is_guid_like(s)
+            })
+            .collect();
+
+        let mut map = HashMap::new();
+        for (i, s) in self.strings.iter().enumerate() {
+            if !is_guid_like(s) {
+                continue;
             }
-        })
+            if let Some((guid, display)) = split_display_string(s) {
+                map.entry(guid.to_string())
+                    .or_insert_with(|| display.to_string());
+                continue;
+            }
+            // Nearest entry name: the last one before `i` or the first after
+            let after = names.partition_point(|&n| n < i);
+            let before = after.checked_sub(1).map(|b| names[b]);
+            let nearest = match (before, names.get(after).copied()) {
+                (Some(b), Some(a)) => Some(if i - b <= a - i { b } else { a }),
+                (b, a) => b.or(a),
+            };
+            if let Some(n) = nearest {
+                map.entry(s.clone())
+                    .or_insert_with(|| self.strings[n].clone());
+            }
+        }
+        map
     }
 
     /// Get strings that look like asset paths
@@ -213,27 +329,168 @@ impl Content {
             .filter_map(|s| s.parse::<f64>().ok().map(|v| (s.as_str(), v)))
     }
 
+    /// Look up the value stored for a named entry
+    ///
+    /// The string table is flat, with each entry name immediately followed by
+    /// its value, so this reads the string after the first occurrence of
+    /// `name`. None when that string was dropped as noise (e.g. a one-digit
+    /// value), rather than the unrelated string that follows it.
+    pub fn entry(&self, name: &str) -> Option<EntryValue<'_>> {
+        self.entry_values.get(name).map(|v| EntryValue::parse(v))
+    }
+
+    /// Look up a named entry's value as a number
+    pub fn numeric_value(&self, name: &str) -> Option<f64> {
+        self.entry(name)?.as_f64()
+    }
+
+    /// Unpack composite strings (e.g. "1airship", "5true") from the string table
+    pub fn packed_strings(&self) -> PackedStrings {
+        PackedStrings::from_strings(&self.strings)
+    }
+
     /// Get entry names (strings that look like identifiers)
     pub fn entry_names(&self) -> impl Iterator<Item = &str> {
-        self.strings.iter().filter_map(|s| {
-            // Entry names are typically CamelCase or snake_case, start with letter/underscore
-            if s.len() >= 3
-                && (s
-                    .chars()
-                    .next()
-                    .map(|c| c.is_ascii_alphabetic() || c == '_')
-                    .unwrap_or(false))
-                && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
-                && !s.chars().all(|c| c.is_ascii_lowercase())
-            {
-                Some(s.as_str())
-            } else {
-                None
-            }
-        })
+        self.strings
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|s| is_entry_name(s))
+    }
+}
+
+/// An entry value from the string table, typed by its text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryValue<'a> {
+    /// A number such as `12.000000` or `42`
+    Number(f64),
+    /// `true` or `false`
+    Bool(bool),
+    /// Anything else, as stored
+    Text(&'a str),
+}
+
+impl<'a> EntryValue<'a> {
+    /// Type a raw value string
+    pub fn parse(s: &'a str) -> Self {
+        if let Ok(n) = s.parse::<f64>() {
+            return EntryValue::Number(n);
+        }
+        match s {
+            "true" => EntryValue::Bool(true),
+            "false" => EntryValue::Bool(false),
+            _ => EntryValue::Text(s),
+        }
+    }
+
+    /// The value as a number, if it is one
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            EntryValue::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The value as text, if it is neither a number nor a bool
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            EntryValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// GUID format: 32 hex chars, often with underscores or hyphens
+fn is_guid_like(s: &str) -> bool {
+    s.len() >= 32 && s.chars().filter(|c| c.is_ascii_hexdigit()).count() >= 28
+}
+
+/// Entry names are typically CamelCase or snake_case, start with letter/underscore
+fn is_entry_name(s: &str) -> bool {
+    s.len() >= 3
+        && (s
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_alphabetic() || c == '_')
+            .unwrap_or(false))
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !s.chars().all(|c| c.is_ascii_lowercase())
+}
+
+/// `(GUID, display name)` from a `<Kind>, <GUID>, <DisplayName>` string
+fn split_display_string(s: &str) -> Option<(&str, &str)> {
+    let mut parts = s.splitn(3, ", ").skip(1);
+    let guid = parts.next()?.trim();
+    let display = parts.next()?.trim();
+    is_guid_like(guid).then_some((guid, display))
+}
+
+/// Size of the control section in bytes
+const CONTROL_SECTION_LEN: usize = 4;
+
+/// Category names the list is known to start with, null terminator included
+const FIRST_CATEGORY_NAMES: &[&[u8]] = &[b"none\0", b"base\0"];
+
+/// Read the control section at `header.control_section_offset`
+pub fn parse_control_section(data: &[u8], header: &Header) -> Option<ControlSection> {
+    let offset = header.control_section_offset?;
+    match data.get(offset..offset + CONTROL_SECTION_LEN)? {
+        &[0x01, 0x00, count, mode] => Some(ControlSection {
+            offset,
+            count,
+            mode,
+        }),
+        _ => None,
     }
 }
 
+/// Read the category names at `header.category_names_offset`
+///
+/// Category names are DLC/content pack identifiers ("none", "base",
+/// "basegame", ...) and follow the primary strings in the combined string
+/// table, so `serialindex._category` and similar values index into them.
+/// The list ends at the first string that isn't a lowercase identifier,
+/// which is where field abbreviations like `corid_aid.a!` or the binary
+/// section begin. `abij` files follow the names with plain field names that
+/// pass that check, so for them the list runs on into those.
+pub fn parse_category_names(data: &[u8], header: &Header) -> Vec<String> {
+    let Some(mut pos) = header.category_names_offset else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    while let Some(len) = data.get(pos..).and_then(|rest| memchr::memchr(0, rest)) {
+        let bytes = &data[pos..pos + len];
+        let is_name = !bytes.is_empty()
+            && bytes
+                .iter()
+                .all(|&b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+        if !is_name {
+            break;
+        }
+        names.push(String::from_utf8_lossy(bytes).into_owned());
+        pos += len + 1;
+    }
+    names
+}
+
+/// Find the control section after the string table starting at `strings_start`
+///
+/// Looks for the `01 00` marker right after a null terminator, followed two
+/// bytes later by a known first category name.
+fn find_control_section(data: &[u8], strings_start: usize) -> Option<usize> {
+    let haystack = data.get(strings_start..)?;
+    memchr::memmem::find_iter(haystack, b"\x01\x00")
+        .map(|rel| strings_start + rel)
+        .find(|&pos| {
+            pos > 0
+                && data[pos - 1] == 0
+                && FIRST_CATEGORY_NAMES.iter().any(|name| {
+                    data.get(pos + CONTROL_SECTION_LEN..)
+                        .is_some_and(|rest| rest.starts_with(name))
+                })
+        })
+}
+
 /// Find format code after a given offset using memmem
 fn find_format_code_after(data: &[u8], after: usize) -> Option<usize> {
     use memchr::memmem;
@@ -269,33 +526,48 @@ fn find_format_code_after(data: &[u8], after: usize) -> Option<usize> {
     None
 }
 
-/// Extract all readable strings from data
-fn extract_strings(data: &[u8], start: usize) -> Vec<String> {
+/// Extract all readable strings from data, plus the value after each entry name
+///
+/// Every string kept is a candidate name, mapped to the run right after its
+/// first occurrence. That run may also be a decimal literal such as
+/// `12.000000`, which [`is_valid_string`] drops from the table as noise.
+fn extract_strings(data: &[u8], start: usize) -> (Vec<String>, HashMap<String, String>) {
     let mut strings = Vec::new();
+    let mut entry_values = HashMap::new();
+    let mut seen = HashSet::new();
+    // The run just finished, when it's a name's first occurrence
+    let mut pending_name: Option<String> = None;
     let mut current = Vec::new();
     let mut in_string = false;
 
+    let mut finish = |current: &mut Vec<u8>| {
+        let s = std::str::from_utf8(current).ok();
+        let valid = s.is_some_and(is_valid_string);
+        if let (Some(name), Some(s)) = (pending_name.take(), s) {
+            if valid || is_decimal_literal(s) {
+                entry_values.insert(name, s.to_string());
+            }
+        }
+        if let Some(s) = s.filter(|_| valid) {
+            strings.push(s.to_string());
+            if seen.insert(s.to_string()) {
+                pending_name = Some(s.to_string());
+            }
+        }
+        current.clear();
+    };
+
     for &byte in &data[start..] {
         if byte == 0 {
             if !current.is_empty() {
-                if let Ok(s) = std::str::from_utf8(&current) {
-                    if is_valid_string(s) {
-                        strings.push(s.to_string());
-                    }
-                }
-                current.clear();
+                finish(&mut current);
             }
             in_string = false;
         } else if byte.is_ascii_graphic() || byte == b' ' {
             current.push(byte);
             in_string = true;
         } else if in_string && current.len() >= 3 {
-            if let Ok(s) = std::str::from_utf8(&current) {
-                if is_valid_string(s) {
-                    strings.push(s.to_string());
-                }
-            }
-            current.clear();
+            finish(&mut current);
             in_string = false;
         } else {
             current.clear();
@@ -305,14 +577,10 @@ fn extract_strings(data: &[u8], start: usize) -> Vec<String> {
 
     // Handle trailing string
     if !current.is_empty() {
-        if let Ok(s) = std::str::from_utf8(&current) {
-            if is_valid_string(s) {
-                strings.push(s.to_string());
-            }
-        }
+        finish(&mut current);
     }
 
-    strings
+    (strings, entry_values)
 }
 
 /// Check if a string is valid (not just noise)
@@ -324,6 +592,11 @@ fn is_valid_string(s: &str) -> bool {
     letter_count >= s.len() / 3
 }
 
+/// Check if a string is a number with a decimal point, like `12.000000`
+fn is_decimal_literal(s: &str) -> bool {
+    s.contains('.') && s.parse::<f64>().is_ok()
+}
+
 /// Extract metadata from string patterns
 fn extract_metadata(strings: &[String]) -> HashMap<String, String> {
     let mut metadata = HashMap::new();
@@ -344,6 +617,7 @@ fn extract_metadata(strings: &[String]) -> HashMap<String, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::UnpackedValue;
 
     fn make_test_ncs(type_name: &str, format_code: &str) -> Vec<u8> {
         let mut data = vec![0u8; 5]; // Header zeros
@@ -368,6 +642,67 @@ mod tests {
 
         assert_eq!(content.type_name(), "itempoollist");
         assert_eq!(content.format_code(), "abjx");
+        assert_eq!(content.format(), FormatCode::Abjx);
+    }
+
+    #[test]
+    fn test_quick_stats() {
+        let data = make_test_ncs("itempoollist", "abjx");
+        let content = Content::parse(&data).unwrap();
+        let stats = content.quick_stats(&data);
+
+        assert_eq!(stats.string_count, content.strings.len());
+        assert_eq!(stats.format, FormatCode::Abjx);
+        // The test file has no binary section to count
+        assert_eq!(stats.estimated_record_count, None);
+    }
+
+    /// Laid out like achievement.bin: strings, control section, category
+    /// names, a field abbreviation, then binary data
+    fn make_test_ncs_with_control() -> Vec<u8> {
+        let mut data = make_test_ncs("achievement", "abjx");
+        data.extend_from_slice(b"29\0");
+        data.extend_from_slice(&[0x01, 0x00, 0x0b, 0xe9]);
+        data.extend_from_slice(b"none\0base\0basegame\0");
+        data.extend_from_slice(b"corid_aid.a!\0");
+        data.extend_from_slice(&[0x80, 0x80, 0x13, 0x0f, 0x00, 0x00]);
+        data
+    }
+
+    #[test]
+    fn test_control_section_and_category_names() {
+        let data = make_test_ncs_with_control();
+        let content = Content::parse(&data).unwrap();
+        let control_offset = data.len() - 42;
+
+        assert_eq!(content.header.control_section_offset, Some(control_offset));
+        assert_eq!(
+            content.header.category_names_offset,
+            Some(control_offset + 4)
+        );
+        assert_eq!(
+            content.control_section(&data),
+            Some(ControlSection {
+                offset: control_offset,
+                count: 0x0b,
+                mode: 0xe9,
+            })
+        );
+        assert_eq!(
+            content.category_names(&data),
+            vec!["none", "base", "basegame"]
+        );
+    }
+
+    #[test]
+    fn test_control_section_missing() {
+        // "none" and "basegame" appear as plain strings, with no marker before them
+        let data = make_test_ncs("itempoollist", "abjx");
+        let content = Content::parse(&data).unwrap();
+
+        assert_eq!(content.header.control_section_offset, None);
+        assert_eq!(content.control_section(&data), None);
+        assert!(content.category_names(&data).is_empty());
     }
 
     #[test]
@@ -380,6 +715,111 @@ mod tests {
         assert!(content.strings.iter().any(|s| s == "basegame"));
     }
 
+    #[test]
+    fn test_entry_lookup() {
+        let data = make_test_ncs("trait_pool", "abjx");
+        let content = Content::parse(&data).unwrap();
+
+        assert_eq!(content.entry("test_entry"), Some(EntryValue::Number(12.0)));
+        assert_eq!(content.numeric_value("test_entry"), Some(12.0));
+        // The value is indexed for lookups but stays out of the string table
+        assert!(!content.strings.iter().any(|s| s == "12.000000"));
+        assert_eq!(content.entry("none"), Some(EntryValue::Text("basegame")));
+        assert_eq!(content.numeric_value("none"), None);
+        assert_eq!(content.entry("basegame"), None);
+        assert_eq!(content.numeric_value("missing"), None);
+    }
+
+    #[test]
+    fn test_entry_value_filtered_out() {
+        let mut data = make_test_ncs("trait_pool", "abjx");
+        // "5" is too short to keep, so "level" has no value in the table
+        data.extend_from_slice(b"level\x005\x00enabled\x00true\x00");
+        let content = Content::parse(&data).unwrap();
+
+        assert_eq!(content.entry("level"), None);
+        assert_eq!(content.entry("enabled"), Some(EntryValue::Bool(true)));
+        assert_eq!(EntryValue::parse("42").as_f64(), Some(42.0));
+        assert_eq!(EntryValue::parse("basegame").as_str(), Some("basegame"));
+    }
+
+    #[test]
+    fn test_packed_strings() {
+        let content = Content {
+            header: Header {
+                type_name: "test".to_string(),
+                format_code: "abjx".to_string(),
+                raw_header: Vec::new(),
+                control_section_offset: None,
+                category_names_offset: None,
+            },
+            strings: vec![
+                "1airship".to_string(),
+                "plain".to_string(),
+                "5true".to_string(),
+            ],
+            entry_values: HashMap::new(),
+            metadata: HashMap::new(),
+        };
+
+        let packed = content.packed_strings();
+        assert_eq!(packed.len(), 2);
+        assert_eq!(
+            packed.packed_values_for("1airship"),
+            Some(
+                &[
+                    UnpackedValue::Integer(1),
+                    UnpackedValue::String("airship".into())
+                ][..]
+            )
+        );
+        assert!(packed.packed_values_for("plain").is_none());
+        assert_eq!(
+            packed.iter().map(|(o, _)| o).collect::<Vec<_>>(),
+            vec!["1airship", "5true"]
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(packed.packed_values_for("5true").unwrap()).unwrap();
+            assert_eq!(json, "[5,true]");
+        }
+    }
+
+    #[test]
+    fn test_guid_map() {
+        let strings = [
+            "Weapon_AR",
+            "A1B2C3D4E5F60718293A4B5C6D7E8F90",
+            "12",
+            "lowercase",
+            "FFEEDDCCBBAA99887766554433221100",
+            "Enemy_Bat",
+            "NameData_Meathead, D342D6EE47173677CE1C068BADA88F69, Saddleback",
+            "A1B2C3D4E5F60718293A4B5C6D7E8F90",
+            "NexusSerialized, 4D3ECE4B00112233445566778899AABB, Ripper Shotgun",
+        ];
+        let content = Content {
+            header: Header {
+                type_name: "test".to_string(),
+                format_code: "abjx".to_string(),
+                raw_header: Vec::new(),
+                control_section_offset: None,
+                category_names_offset: None,
+            },
+            strings: strings.iter().map(|s| s.to_string()).collect(),
+            entry_values: HashMap::new(),
+            metadata: HashMap::new(),
+        };
+
+        let map = content.guid_map();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map["A1B2C3D4E5F60718293A4B5C6D7E8F90"], "Weapon_AR");
+        assert_eq!(map["FFEEDDCCBBAA99887766554433221100"], "Enemy_Bat");
+        assert_eq!(map["D342D6EE47173677CE1C068BADA88F69"], "Saddleback");
+        assert_eq!(map["4D3ECE4B00112233445566778899AABB"], "Ripper Shotgun");
+    }
+
     #[test]
     fn test_is_type() {
         let data = make_test_ncs("vending_machine", "abhj");
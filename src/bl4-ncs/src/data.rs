@@ -24,6 +24,9 @@ pub const HEADER_SIZE: usize = 16;
 /// Inner header size (before block table)
 pub const INNER_HEADER_MIN: usize = 0x40;
 
+/// Most padding bytes [`Header::locate`] skips before the header
+pub const MAX_PREAMBLE: usize = 16;
+
 /// Maximum decompressed size per block (256KB)
 const BLOCK_DECOMP_SIZE: usize = 0x40000;
 
@@ -66,6 +69,35 @@ impl Header {
         })
     }
 
+    /// Parse the header, skipping up to [`MAX_PREAMBLE`] bytes of padding
+    /// (such as a BOM) in front of it
+    ///
+    /// Returns the header's start offset along with it; the payload begins
+    /// [`HEADER_SIZE`] bytes after that. If no header is found in the
+    /// window, the error is the one [`Header::from_bytes`] gives at
+    /// offset 0.
+    pub fn locate(data: &[u8]) -> Result<(usize, Self)> {
+        let first_error = match Self::from_bytes(data) {
+            Ok(header) => return Ok((0, header)),
+            Err(e) => e,
+        };
+
+        for start in 1..=MAX_PREAMBLE {
+            let Some(candidate) = data.get(start..) else {
+                break;
+            };
+            // "_NCS/" is a manifest, not a data header
+            if candidate.first() == Some(&b'_') {
+                continue;
+            }
+            if let Ok(header) = Self::from_bytes(candidate) {
+                return Ok((start, header));
+            }
+        }
+
+        Err(first_error)
+    }
+
     #[inline]
     pub fn is_compressed(&self) -> bool {
         self.compression_flag != 0
@@ -84,10 +116,13 @@ pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
 }
 
 /// Decompress an NCS chunk using a specific Oodle backend
+///
+/// A few bytes of padding before the header are skipped, see
+/// [`Header::locate`].
 pub fn decompress_with(data: &[u8], decompressor: &dyn OodleDecompressor) -> Result<Vec<u8>> {
-    let header = Header::from_bytes(data)?;
+    let (start, header) = Header::locate(data)?;
 
-    let payload = &data[HEADER_SIZE..];
+    let payload = &data[start + HEADER_SIZE..];
     if payload.len() < header.compressed_size as usize {
         return Err(Error::DataTooShort {
             needed: header.compressed_size as usize,
@@ -387,6 +422,43 @@ mod tests {
         assert_eq!(result, payload);
     }
 
+    #[test]
+    fn test_header_locate_after_padding() {
+        let payload = b"Hello, World!";
+        let size = payload.len() as u32;
+        let mut data = vec![0xEF, 0xBB, 0xBF, 0x00];
+        data.extend(make_ncs_header(1, 0, size, size));
+        data.extend_from_slice(payload);
+
+        assert!(Header::from_bytes(&data).is_err());
+        let (start, header) = Header::locate(&data).unwrap();
+        assert_eq!(start, 4);
+        assert_eq!(header.compressed_size, size);
+        assert_eq!(decompress(&data).unwrap(), payload);
+
+        // No padding reports offset 0
+        let (start, _) = Header::locate(&data[4..]).unwrap();
+        assert_eq!(start, 0);
+    }
+
+    #[test]
+    fn test_header_locate_outside_window() {
+        let mut data = vec![0u8; MAX_PREAMBLE + 1];
+        data.extend(make_ncs_header(1, 0, 4, 4));
+        data.extend_from_slice(b"data");
+
+        assert!(matches!(
+            Header::locate(&data),
+            Err(Error::InvalidNcsMagic(0, 0, 0))
+        ));
+
+        // A manifest is not mistaken for a header
+        let mut data = vec![0u8];
+        data.extend_from_slice(b"_NCS/");
+        data.extend_from_slice(&[0u8; 16]);
+        assert!(Header::locate(&data).is_err());
+    }
+
     #[test]
     fn test_decompress_inner_too_short() {
         // Compressed flag = 1, but inner data is too short for Oodle header
@@ -2,7 +2,7 @@
 
 use super::types::{DataTable, DataTableManifest, DataTableRow};
 use crate::document::Value;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Strip GUID suffix from a field name.
 ///
@@ -59,7 +59,7 @@ fn extract_row(value: &Value) -> Option<DataTableRow> {
         _ => return None,
     };
 
-    let mut fields = HashMap::new();
+    let mut fields = BTreeMap::new();
 
     if let Some(Value::Map(row_value)) = map.get("row_value") {
         for (key, val) in row_value {
@@ -111,7 +111,7 @@ fn extract_table(key: &str, value: &Value) -> Option<DataTable> {
 pub fn extract_data_tables(data: &[u8]) -> Option<DataTableManifest> {
     let doc = crate::parse::parse(data)?;
 
-    let mut tables = HashMap::new();
+    let mut tables = BTreeMap::new();
 
     for table in doc.tables.values() {
         for record in &table.records {
@@ -312,7 +312,7 @@ mod tests {
 
     #[test]
     fn test_extract_row_basic() {
-        let mut row_value = HashMap::new();
+        let mut row_value = BTreeMap::new();
         row_value.insert(
             "fire_52_4d6e5a8840f57dbd840197b3cb05686d".to_string(),
             Value::Leaf("0.800000".to_string()),
@@ -322,7 +322,7 @@ mod tests {
             Value::Leaf("0.800000".to_string()),
         );
 
-        let mut map = HashMap::new();
+        let mut map = BTreeMap::new();
         map.insert("row_name".to_string(), Value::Leaf("WeaponDamageScale".to_string()));
         map.insert("row_value".to_string(), Value::Map(row_value));
 
@@ -334,7 +334,7 @@ mod tests {
 
     #[test]
     fn test_extract_row_no_row_value() {
-        let mut map = HashMap::new();
+        let mut map = BTreeMap::new();
         map.insert("row_name".to_string(), Value::Leaf("Pistol".to_string()));
 
         let row = extract_row(&Value::Map(map)).unwrap();
@@ -345,14 +345,14 @@ mod tests {
     #[test]
     fn test_extract_table() {
         let mut data_arr = Vec::new();
-        let mut row_map = HashMap::new();
+        let mut row_map = BTreeMap::new();
         row_map.insert("row_name".to_string(), Value::Leaf("Row1".to_string()));
-        let mut rv = HashMap::new();
+        let mut rv = BTreeMap::new();
         rv.insert("cost_normal".to_string(), Value::Leaf("600".to_string()));
         row_map.insert("row_value".to_string(), Value::Map(rv));
         data_arr.push(Value::Map(row_map));
 
-        let mut entry_map = HashMap::new();
+        let mut entry_map = BTreeMap::new();
         entry_map.insert(
             "gbx_ue_data_table".to_string(),
             Value::Leaf("My_Table".to_string()),
@@ -380,7 +380,7 @@ mod tests {
                 DataTableRow {
                     row_name: "Alpha".to_string(),
                     fields: {
-                        let mut f = HashMap::new();
+                        let mut f = BTreeMap::new();
                         f.insert("damage".to_string(), "100".to_string());
                         f.insert("speed".to_string(), "5.0".to_string());
                         f
@@ -389,7 +389,7 @@ mod tests {
                 DataTableRow {
                     row_name: "Beta".to_string(),
                     fields: {
-                        let mut f = HashMap::new();
+                        let mut f = BTreeMap::new();
                         f.insert("damage".to_string(), "200".to_string());
                         // speed missing — should produce empty cell
                         f.insert("comment".to_string(), "high damage".to_string());
@@ -408,7 +408,7 @@ mod tests {
 
     #[test]
     fn test_data_table_manifest_accessors() {
-        let mut tables = HashMap::new();
+        let mut tables = BTreeMap::new();
         tables.insert(
             "test_table".to_string(),
             DataTable {
@@ -418,11 +418,11 @@ mod tests {
                 rows: vec![
                     DataTableRow {
                         row_name: "Row1".to_string(),
-                        fields: HashMap::new(),
+                        fields: BTreeMap::new(),
                     },
                     DataTableRow {
                         row_name: "Row2".to_string(),
-                        fields: HashMap::new(),
+                        fields: BTreeMap::new(),
                     },
                 ],
             },
@@ -1,19 +1,22 @@
 //! Data table type definitions
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// A single row in a data table
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DataTableRow {
     /// Row identifier (e.g., "WeaponDamageScale", "Pistol", "Badass")
     pub row_name: String,
     /// Field name → value mapping (GUID suffixes stripped from keys)
-    pub fields: HashMap<String, String>,
+    pub fields: BTreeMap<String, String>,
 }
 
 /// A parsed UE data table definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DataTable {
     /// Internal entry key (lowercase, e.g., "weapon_elementaldamagescale")
     pub key: String,
@@ -26,10 +29,11 @@ pub struct DataTable {
 }
 
 /// Collection of all data tables from a gbx_ue_data_table NCS file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DataTableManifest {
     /// Table key → DataTable
-    pub tables: HashMap<String, DataTable>,
+    pub tables: BTreeMap<String, DataTable>,
 }
 
 impl DataTableManifest {
@@ -41,9 +45,7 @@ impl DataTableManifest {
 
     /// Get all table keys, sorted
     pub fn keys(&self) -> Vec<&str> {
-        let mut keys: Vec<&str> = self.tables.keys().map(|s| s.as_str()).collect();
-        keys.sort();
-        keys
+        self.tables.keys().map(|s| s.as_str()).collect()
     }
 
     /// Total number of tables
@@ -0,0 +1,240 @@
+//! Dependency graph between NCS files
+//!
+//! Each decompressed file names its type in the first header string and the
+//! tables it depends on in the rest (see [`crate::parse::extract_deps`]). A
+//! dependency resolves when another file in the set has that type name.
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// A type name with the files that define it and the deps they declare
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DependencyNode {
+    pub name: String,
+    /// Files whose first header string is `name`, sorted
+    pub paths: Vec<PathBuf>,
+    /// Declared deps across all of those files, sorted and deduplicated
+    pub deps: Vec<String>,
+}
+
+/// Type-level dependency graph for a set of NCS files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DependencyGraph {
+    /// Nodes sorted by name
+    pub nodes: Vec<DependencyNode>,
+}
+
+impl DependencyGraph {
+    /// Build a graph from `(path, type_name, deps)` triples
+    pub fn from_files<I>(files: I) -> Self
+    where
+        I: IntoIterator<Item = (PathBuf, String, Vec<String>)>,
+    {
+        let mut by_name: BTreeMap<String, (BTreeSet<PathBuf>, BTreeSet<String>)> = BTreeMap::new();
+        for (path, name, deps) in files {
+            let (paths, all_deps) = by_name.entry(name).or_default();
+            paths.insert(path);
+            all_deps.extend(deps);
+        }
+
+        let nodes = by_name
+            .into_iter()
+            .map(|(name, (paths, deps))| DependencyNode {
+                name,
+                paths: paths.into_iter().collect(),
+                deps: deps.into_iter().collect(),
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    /// Look up a node by type name
+    pub fn node(&self, name: &str) -> Option<&DependencyNode> {
+        self.nodes
+            .binary_search_by(|n| n.name.as_str().cmp(name))
+            .ok()
+            .map(|i| &self.nodes[i])
+    }
+
+    /// Every declared `(from, dep)` edge, including unresolved ones
+    pub fn edges(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.nodes
+            .iter()
+            .flat_map(|n| n.deps.iter().map(move |d| (n.name.as_str(), d.as_str())))
+    }
+
+    /// Edges whose dep names no type in the graph
+    pub fn missing(&self) -> Vec<(&str, &str)> {
+        self.edges()
+            .filter(|(_, dep)| self.node(dep).is_none())
+            .collect()
+    }
+
+    /// Order type names so every one comes after the types it depends on
+    ///
+    /// Unresolved deps and self-references are ignored. On a cycle, returns
+    /// `Err` with the names that could not be ordered: those on a cycle and
+    /// those depending on one.
+    pub fn topological_order(&self) -> Result<Vec<&str>, Vec<&str>> {
+        let mut remaining: BTreeMap<&str, BTreeSet<&str>> = self
+            .nodes
+            .iter()
+            .map(|n| {
+                let deps = n
+                    .deps
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|d| *d != n.name && self.node(d).is_some())
+                    .collect();
+                (n.name.as_str(), deps)
+            })
+            .collect();
+
+        let mut order = Vec::with_capacity(remaining.len());
+        loop {
+            let ready: Vec<&str> = remaining
+                .iter()
+                .filter(|(_, deps)| deps.is_empty())
+                .map(|(name, _)| *name)
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for name in &ready {
+                remaining.remove(name);
+            }
+            for deps in remaining.values_mut() {
+                for name in &ready {
+                    deps.remove(name);
+                }
+            }
+            order.extend(ready);
+        }
+
+        if remaining.is_empty() {
+            Ok(order)
+        } else {
+            Err(remaining.into_keys().collect())
+        }
+    }
+
+    /// Render as a Graphviz digraph; unresolved deps are drawn dashed
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph ncs {\n");
+        for node in &self.nodes {
+            let _ = writeln!(out, "  {:?};", node.name);
+        }
+        let missing: BTreeSet<&str> = self.missing().into_iter().map(|(_, d)| d).collect();
+        for name in &missing {
+            let _ = writeln!(out, "  {:?} [style=dashed];", name);
+        }
+        for (from, to) in self.edges() {
+            let _ = writeln!(out, "  {:?} -> {:?};", from, to);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Build the dependency graph for every decompressed `.bin` file under `dir`
+///
+/// Compressed NCS files are decompressed first. Files that can't be read or
+/// have no header strings are skipped.
+pub fn build_dependency_graph<P: AsRef<Path>>(dir: P) -> DependencyGraph {
+    let files = walkdir::WalkDir::new(dir.as_ref())
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "bin"))
+        .filter_map(|e| {
+            let path = e.into_path();
+            let (name, deps) = read_deps(&path)?;
+            Some((path, name, deps))
+        });
+    DependencyGraph::from_files(files)
+}
+
+fn read_deps(path: &Path) -> Option<(String, Vec<String>)> {
+    let mut data = std::fs::read(path).ok()?;
+    if crate::find_ncs_start(&data).is_some() {
+        data = crate::decompress_ncs(&data).ok()?;
+    }
+    let deps = crate::parse::extract_deps(&data);
+    if deps.is_none() {
+        log::debug!("{}: no header strings, skipping", path.display());
+    }
+    deps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(files: &[(&str, &[&str])]) -> DependencyGraph {
+        DependencyGraph::from_files(files.iter().map(|(name, deps)| {
+            (
+                PathBuf::from(format!("{}.bin", name)),
+                name.to_string(),
+                deps.iter().map(|d| d.to_string()).collect(),
+            )
+        }))
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let g = graph(&[
+            ("inv", &["barrel", "inv_comp", "inv"]),
+            ("barrel", &["rarity"]),
+            ("rarity", &[]),
+            ("inv_comp", &["rarity", "unknown"]),
+        ]);
+        assert_eq!(
+            g.topological_order().unwrap(),
+            vec!["rarity", "barrel", "inv_comp", "inv"]
+        );
+        assert_eq!(g.missing(), vec![("inv_comp", "unknown")]);
+        assert_eq!(g.edges().count(), 6);
+    }
+
+    #[test]
+    fn test_topological_order_cycle() {
+        let g = graph(&[("a", &["b"]), ("b", &["a"]), ("c", &["a"]), ("d", &[])]);
+        assert_eq!(g.topological_order().unwrap_err(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_same_type_in_several_files() {
+        let g = DependencyGraph::from_files([
+            (
+                PathBuf::from("inv1.bin"),
+                "inv".to_string(),
+                vec!["b".to_string()],
+            ),
+            (
+                PathBuf::from("inv0.bin"),
+                "inv".to_string(),
+                vec!["a".to_string()],
+            ),
+        ]);
+        let node = g.node("inv").unwrap();
+        assert_eq!(
+            node.paths,
+            vec![PathBuf::from("inv0.bin"), PathBuf::from("inv1.bin")]
+        );
+        assert_eq!(node.deps, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let g = graph(&[("inv", &["barrel", "gone"]), ("barrel", &[])]);
+        assert_eq!(
+            g.to_dot(),
+            "digraph ncs {\n  \"barrel\";\n  \"inv\";\n  \"gone\" [style=dashed];\n  \
+             \"inv\" -> \"barrel\";\n  \"inv\" -> \"gone\";\n}\n"
+        );
+    }
+}
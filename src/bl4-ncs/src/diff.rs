@@ -0,0 +1,320 @@
+//! Record-level comparison of two parsed NCS documents
+//!
+//! Entries are matched by table name and entry key. Repeated keys within a
+//! table (extension records) are matched in file order and reported as
+//! `key#2`, `key#3`, and so on.
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::document::{Document, Entry, Table, Value};
+
+/// Differences between two documents, with unchanged tables omitted
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DocumentDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableDiff>,
+}
+
+impl DocumentDiff {
+    /// True if the documents are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.removed_tables.is_empty()
+            && self.changed_tables.is_empty()
+    }
+}
+
+/// Entry changes within a table present in both documents
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TableDiff {
+    pub name: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<EntryDiff>,
+}
+
+impl TableDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Field changes for an entry present in both documents
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct EntryDiff {
+    pub key: String,
+    pub fields: Vec<FieldChange>,
+}
+
+/// A single changed value, addressed by a dotted path within the entry
+///
+/// Dependency entries appear as `deps.<table>.<key>`. `old`/`new` are None
+/// when the field only exists on one side.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FieldChange {
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// Compare two documents table by table and entry by entry
+pub fn diff_documents(a: &Document, b: &Document) -> DocumentDiff {
+    let mut diff = DocumentDiff::default();
+
+    for name in a.table_names() {
+        match b.table(name) {
+            Some(new) => {
+                let table_diff = diff_tables(&a.tables[name], new);
+                if !table_diff.is_empty() {
+                    diff.changed_tables.push(table_diff);
+                }
+            }
+            None => diff.removed_tables.push(name.to_string()),
+        }
+    }
+    for name in b.table_names() {
+        if a.table(name).is_none() {
+            diff.added_tables.push(name.to_string());
+        }
+    }
+
+    diff
+}
+
+/// Entries keyed by `key` (or `key#N` for the Nth repeat), sorted by key
+fn keyed_entries(table: &Table) -> BTreeMap<String, &Entry> {
+    let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut keyed = BTreeMap::new();
+
+    for entry in table.entries() {
+        let count = seen.entry(entry.key.as_str()).or_default();
+        *count += 1;
+        let key = if *count == 1 {
+            entry.key.clone()
+        } else {
+            format!("{}#{}", entry.key, count)
+        };
+        keyed.insert(key, entry);
+    }
+    keyed
+}
+
+fn diff_tables(old: &Table, new: &Table) -> TableDiff {
+    let old_entries = keyed_entries(old);
+    let new_entries = keyed_entries(new);
+    let mut diff = TableDiff {
+        name: old.name.clone(),
+        ..TableDiff::default()
+    };
+
+    for (key, old_entry) in &old_entries {
+        match new_entries.get(key) {
+            Some(new_entry) => {
+                let fields = diff_entries(old_entry, new_entry);
+                if !fields.is_empty() {
+                    diff.changed.push(EntryDiff {
+                        key: key.clone(),
+                        fields,
+                    });
+                }
+            }
+            None => diff.removed.push(key.clone()),
+        }
+    }
+    diff.added = new_entries
+        .keys()
+        .filter(|key| !old_entries.contains_key(*key))
+        .cloned()
+        .collect();
+
+    diff
+}
+
+fn diff_entries(old: &Entry, new: &Entry) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    diff_values("", Some(&old.value), Some(&new.value), &mut changes);
+
+    let deps = |entry: &Entry| -> BTreeMap<String, &Value> {
+        entry
+            .dep_entries
+            .iter()
+            .map(|d| (format!("deps.{}.{}", d.dep_table_name, d.key), &d.value))
+            .collect()
+    };
+    let (old_deps, new_deps) = (deps(old), deps(new));
+    let paths: BTreeSet<&String> = old_deps.keys().chain(new_deps.keys()).collect();
+    for path in paths {
+        diff_values(
+            path,
+            old_deps.get(path).copied(),
+            new_deps.get(path).copied(),
+            &mut changes,
+        );
+    }
+
+    changes
+}
+
+/// Recurse into maps present on both sides; anything else is compared whole
+fn diff_values(path: &str, old: Option<&Value>, new: Option<&Value>, out: &mut Vec<FieldChange>) {
+    if let (Some(Value::Map(old_map)), Some(Value::Map(new_map))) = (old, new) {
+        let keys: BTreeSet<&String> = old_map.keys().chain(new_map.keys()).collect();
+        for key in keys {
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            diff_values(&child, old_map.get(key), new_map.get(key), out);
+        }
+        return;
+    }
+
+    if old != new {
+        out.push(FieldChange {
+            // Non-map entry values have no field names of their own
+            path: if path.is_empty() { "value" } else { path }.to_string(),
+            old: old.cloned(),
+            new: new.cloned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{DepEntry, Record};
+
+    fn leaf(s: &str) -> Value {
+        Value::Leaf(s.to_string())
+    }
+
+    fn entry(key: &str, fields: &[(&str, &str)]) -> Entry {
+        let map = fields
+            .iter()
+            .map(|(k, v)| (k.to_string(), leaf(v)))
+            .collect();
+        Entry {
+            key: key.to_string(),
+            value: Value::Map(map),
+            dep_entries: Vec::new(),
+        }
+    }
+
+    fn doc(tables: Vec<(&str, Vec<Entry>)>) -> Document {
+        let tables = tables
+            .into_iter()
+            .map(|(name, entries)| {
+                let table = Table {
+                    name: name.to_string(),
+                    deps: Vec::new(),
+                    pair_remap: Vec::new(),
+                    value_remap: Vec::new(),
+                    records: vec![Record {
+                        tags: Vec::new(),
+                        entries,
+                    }],
+                };
+                (name.to_string(), table)
+            })
+            .collect::<BTreeMap<_, _>>();
+        Document {
+            tables,
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_identical_documents() {
+        let a = doc(vec![("inv", vec![entry("gun", &[("rarity", "1")])])]);
+        assert!(diff_documents(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_added_removed_and_changed() {
+        let a = doc(vec![
+            (
+                "inv",
+                vec![
+                    entry("gun", &[("rarity", "1"), ("mfr", "JAK")]),
+                    entry("old_part", &[]),
+                ],
+            ),
+            ("gone", vec![]),
+        ]);
+        let b = doc(vec![
+            (
+                "inv",
+                vec![
+                    entry("gun", &[("rarity", "5"), ("mfr", "JAK"), ("new", "x")]),
+                    entry("new_part", &[]),
+                ],
+            ),
+            ("fresh", vec![]),
+        ]);
+
+        let diff = diff_documents(&a, &b);
+        assert_eq!(diff.added_tables, vec!["fresh"]);
+        assert_eq!(diff.removed_tables, vec!["gone"]);
+
+        let inv = &diff.changed_tables[0];
+        assert_eq!(inv.added, vec!["new_part"]);
+        assert_eq!(inv.removed, vec!["old_part"]);
+        assert_eq!(inv.changed.len(), 1);
+
+        let gun = &inv.changed[0];
+        assert_eq!(gun.key, "gun");
+        assert_eq!(
+            gun.fields,
+            vec![
+                FieldChange {
+                    path: "new".to_string(),
+                    old: None,
+                    new: Some(leaf("x")),
+                },
+                FieldChange {
+                    path: "rarity".to_string(),
+                    old: Some(leaf("1")),
+                    new: Some(leaf("5")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repeated_keys_and_deps() {
+        let mut dep_entry = entry("gun", &[]);
+        dep_entry.dep_entries.push(DepEntry {
+            dep_table_name: "parts".to_string(),
+            dep_index: 0,
+            key: "barrel".to_string(),
+            value: leaf("1"),
+        });
+        let a = doc(vec![("inv", vec![entry("gun", &[]), dep_entry.clone()])]);
+
+        dep_entry.dep_entries[0].value = leaf("2");
+        let b = doc(vec![("inv", vec![entry("gun", &[]), dep_entry])]);
+
+        let diff = diff_documents(&a, &b);
+        let changed = &diff.changed_tables[0].changed;
+        assert_eq!(changed[0].key, "gun#2");
+        assert_eq!(changed[0].fields[0].path, "deps.parts.barrel");
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_value(&diff).unwrap();
+            assert_eq!(
+                json["changed_tables"][0]["changed"][0]["fields"][0]["new"],
+                "2"
+            );
+        }
+    }
+}
@@ -4,41 +4,406 @@
 //! Each NCS file contains one or more named tables, each containing
 //! records with entries and optional dependency entries.
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::ops::Range;
 
 /// Parsed NCS document containing all tables from a single NCS file
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// This is the public result of parsing the binary section; records and
+/// their entries can be walked directly without going through the debug
+/// commands:
+///
+/// ```no_run
+/// let data = std::fs::read("inv0.bin").unwrap(); // decompressed NCS
+/// let doc = bl4_ncs::parse_ncs_binary(&data).unwrap();
+///
+/// for name in doc.table_names() {
+///     let table = doc.table(name).unwrap();
+///     for (i, record) in table.records.iter().enumerate() {
+///         let keys: Vec<&str> = record.keys().collect();
+///         println!("{}[{}]: {} tags, entries {:?}", name, i, record.tags.len(), keys);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Document {
-    pub tables: HashMap<String, Table>,
+    /// Tables keyed by name (the first header string of each table block)
+    pub tables: BTreeMap<String, Table>,
+    /// Byte range of every record in the parsed file, in file order
+    ///
+    /// Offsets are into the data passed to the parser, not the binary
+    /// section. Each range starts at the record's length prefix and ends
+    /// where its declared length does, so it covers exactly the bytes to
+    /// highlight or rewrite. Tables are stored one after another, so each
+    /// table's records are consecutive here; in a single-table file,
+    /// `record_spans[i]` belongs to `records[i]`. Empty for documents that
+    /// weren't parsed from a file.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub record_spans: Vec<Range<usize>>,
+    /// Records that failed to parse cleanly, in file order
+    ///
+    /// A non-empty list means the document is incomplete or some records
+    /// hold misread fields; everything else still decoded.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub parse_errors: Vec<RecordError>,
+}
+
+/// A malformed record found while parsing, with its byte offset in the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum RecordError {
+    /// Decoding read `bits` past the record's declared end; the record is
+    /// kept, but its last fields are likely wrong
+    Overrun { offset: usize, bits: usize },
+    /// The record's length prefix was impossible; `skipped` bytes were
+    /// passed over to reach the next plausible record
+    Resynced { offset: usize, skipped: usize },
+    /// The record's length prefix was impossible and no plausible record
+    /// followed within [`crate::NcsParseOptions::max_resync_bytes`], so the
+    /// rest of its table was dropped
+    Unrecoverable { offset: usize },
+}
+
+impl RecordError {
+    /// Byte offset of the record's length prefix
+    pub fn offset(&self) -> usize {
+        match self {
+            RecordError::Overrun { offset, .. }
+            | RecordError::Resynced { offset, .. }
+            | RecordError::Unrecoverable { offset } => *offset,
+        }
+    }
+
+    /// Move the offset by `base`, for errors found within a sub-slice
+    pub(crate) fn shift(&mut self, base: usize) {
+        match self {
+            RecordError::Overrun { offset, .. }
+            | RecordError::Resynced { offset, .. }
+            | RecordError::Unrecoverable { offset } => *offset += base,
+        }
+    }
+}
+
+impl Document {
+    /// Look up a table by name
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(name)
+    }
+
+    /// Table names in sorted order
+    pub fn table_names(&self) -> Vec<&str> {
+        self.tables.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Find the record holding the entry a [`Value::Ref`] points to
+    ///
+    /// A ref names an entry key. Keys are compared ASCII case-insensitively,
+    /// since the parser lowercases entry keys but refs keep their original
+    /// casing. Tables are searched in name order and records in file order,
+    /// and the first record with a matching entry wins. Dep entries are not
+    /// searched. Each call scans the whole document; build a
+    /// [`Document::ref_index`] to resolve many refs.
+    pub fn resolve_ref(&self, name: &str) -> Option<&Record> {
+        self.tables
+            .values()
+            .flat_map(|t| t.records.iter())
+            .find(|r| r.entries.iter().any(|e| e.key.eq_ignore_ascii_case(name)))
+    }
+
+    /// Index every entry key for repeated [`Value::Ref`] lookups
+    pub fn ref_index(&self) -> RefIndex<'_> {
+        RefIndex::new(self)
+    }
+
+    /// Memory-map a decompressed NCS file and parse it without reading it into a `Vec`
+    ///
+    /// Returns `Ok(None)` when the contents don't parse, like
+    /// [`crate::parse_ncs_binary`]. The file must not be modified while it is
+    /// being parsed.
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap<P: AsRef<std::path::Path>>(path: P) -> crate::Result<Option<Document>> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read-only and dropped before returning; the
+        // caller guarantees nothing truncates the file meanwhile.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(crate::parse::parse(&map))
+    }
+}
+
+/// Entry keys of a [`Document`] mapped to where they are first defined
+///
+/// Resolves refs the same way as [`Document::resolve_ref`], but with one
+/// hash lookup per ref instead of a scan of every table.
+#[derive(Debug, Clone)]
+pub struct RefIndex<'a> {
+    doc: &'a Document,
+    /// Lowercased key → (table name, record index, entry index)
+    keys: HashMap<String, (&'a str, usize, usize)>,
+}
+
+impl<'a> RefIndex<'a> {
+    /// Index the entries of `doc`, keeping the first definition of each key
+    pub fn new(doc: &'a Document) -> Self {
+        let mut keys = HashMap::new();
+        for (name, table) in &doc.tables {
+            for (r, record) in table.records.iter().enumerate() {
+                for (e, entry) in record.entries.iter().enumerate() {
+                    keys.entry(entry.key.to_ascii_lowercase())
+                        .or_insert((name.as_str(), r, e));
+                }
+            }
+        }
+        Self { doc, keys }
+    }
+
+    /// Table name and record index of the record a ref points to
+    pub fn locate(&self, name: &str) -> Option<(&'a str, usize)> {
+        self.lookup(name).map(|(table, r, _)| (table, r))
+    }
+
+    /// The record a ref points to
+    pub fn resolve(&self, name: &str) -> Option<&'a Record> {
+        let (table, r, _) = self.lookup(name)?;
+        Some(&self.doc.tables[table].records[r])
+    }
+
+    /// The entry a ref names
+    pub fn entry(&self, name: &str) -> Option<&'a Entry> {
+        let (table, r, e) = self.lookup(name)?;
+        Some(&self.doc.tables[table].records[r].entries[e])
+    }
+
+    fn lookup(&self, name: &str) -> Option<(&'a str, usize, usize)> {
+        self.keys.get(&name.to_ascii_lowercase()).copied()
+    }
 }
 
 /// A single table with dependency references and records
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Table {
+    /// Table name from the header strings
     pub name: String,
+    /// Names of the tables this table's dep entries refer to
     pub deps: Vec<String>,
+    /// Header remap for key and [`Tag::KeyName`] string indices, see
+    /// [`Table::resolve_pair`]; empty if the table has none
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub pair_remap: Vec<u32>,
+    /// Header remap for leaf value string indices, see
+    /// [`Table::resolve_value`]; empty if the table has none
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub value_remap: Vec<u32>,
+    /// Records in file order
     pub records: Vec<Record>,
 }
 
+impl Table {
+    /// All entries across every record, in file order
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.records.iter().flat_map(|r| r.entries.iter())
+    }
+
+    /// Find the first entry with the given key in any record
+    pub fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries().find(|e| e.key == key)
+    }
+
+    /// Map a raw key index from this table's records to a key string index
+    ///
+    /// Entry keys, dep entry keys, [`Tag::KeyName`] pairs and packed name lists
+    /// are written as indices into this remap (the file's first header
+    /// remap array). The decoder reads them with the remap's index width and
+    /// looks the result up in the key string block. None if `idx` is outside
+    /// the remap, including when the table has none; the decoder then uses
+    /// the raw index as is.
+    pub fn resolve_pair(&self, idx: usize) -> Option<u32> {
+        self.pair_remap.get(idx).copied()
+    }
+
+    /// Map a raw leaf value index from this table's records to a value
+    /// string index
+    ///
+    /// The second header remap array. It is only applied to leaf values,
+    /// whose value kind index follows the remapped value index; why some
+    /// tables carry it and others don't is not known yet. None if `idx` is
+    /// outside the remap, and as with [`Table::resolve_pair`] the decoder
+    /// falls back to the raw index.
+    pub fn resolve_value(&self, idx: usize) -> Option<u32> {
+        self.value_remap.get(idx).copied()
+    }
+}
+
 /// A record containing entries decoded from the binary section
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Record {
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Tag metadata read before the entries
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub tags: Vec<Tag>,
+    /// Entries in this record, in file order
     pub entries: Vec<Entry>,
 }
 
+impl Record {
+    /// Keys of this record's entries, in file order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.key.as_str())
+    }
+
+    /// Find an entry in this record by key
+    pub fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    /// Multi-line dump of this record's tags, entries and dep entries
+    ///
+    /// Values are written as an indented tree with map keys sorted. Each
+    /// [`Value::Ref`] is followed by the table and record it resolves to in
+    /// `doc` (see [`Document::resolve_ref`]), or `unresolved`. Entries and
+    /// dep entries with a `serialindex` get it parsed on its own line above
+    /// the value.
+    pub fn pretty(&self, doc: &Document) -> String {
+        let refs = doc.ref_index();
+        let mut out = String::new();
+
+        writeln!(out, "tags ({}):", self.tags.len()).unwrap();
+        for tag in &self.tags {
+            write_tag(&mut out, tag, &refs);
+        }
+
+        writeln!(out, "entries ({}):", self.entries.len()).unwrap();
+        for (i, entry) in self.entries.iter().enumerate() {
+            writeln!(out, "  [{}] {}", i, entry.key).unwrap();
+            write_serial_index(&mut out, entry.serial_index(), 2);
+            write!(out, "    value:").unwrap();
+            write_value(&mut out, &entry.value, 3, &refs);
+
+            for dep in &entry.dep_entries {
+                writeln!(
+                    out,
+                    "    dep {}[{}] {}",
+                    dep.dep_table_name, dep.dep_index, dep.key
+                )
+                .unwrap();
+                write_serial_index(&mut out, dep.serial_index(), 3);
+                write!(out, "      value:").unwrap();
+                write_value(&mut out, &dep.value, 4, &refs);
+            }
+        }
+
+        out
+    }
+}
+
+fn write_tag(out: &mut String, tag: &Tag, refs: &RefIndex) {
+    match tag {
+        Tag::KeyName { pair } => writeln!(out, "  a key_name {}", pair).unwrap(),
+        Tag::U32 { value } => writeln!(out, "  b u32 {}", value).unwrap(),
+        Tag::F32 {
+            u32_value,
+            f32_value,
+        } => writeln!(out, "  c f32 {} (0x{:08x})", f32_value, u32_value).unwrap(),
+        Tag::NameListD { list } => writeln!(out, "  d names [{}]", list.join(", ")).unwrap(),
+        Tag::NameListE { list } => writeln!(out, "  e names [{}]", list.join(", ")).unwrap(),
+        Tag::NameListF { list } => writeln!(out, "  f names [{}]", list.join(", ")).unwrap(),
+        Tag::Variant { variant } => {
+            write!(out, "  p variant:").unwrap();
+            write_value(out, variant, 2, refs);
+        }
+    }
+}
+
+fn write_serial_index(out: &mut String, serial_index: Option<SerialIndex>, indent: usize) {
+    let Some(si) = serial_index else {
+        return;
+    };
+    write!(out, "{}serialindex: {}", "  ".repeat(indent), si.index).unwrap();
+    let details: Vec<String> = [
+        ("scope", &si.scope),
+        ("category", &si.category),
+        ("status", &si.status),
+    ]
+    .into_iter()
+    .filter_map(|(name, field)| field.as_ref().map(|v| format!("{} {}", name, v)))
+    .collect();
+    if !details.is_empty() {
+        write!(out, " ({})", details.join(", ")).unwrap();
+    }
+    out.push('\n');
+}
+
+/// Write `value` after a `key:` already on the line, nesting at `indent`
+fn write_value(out: &mut String, value: &Value, indent: usize, refs: &RefIndex) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Null => out.push_str(" null\n"),
+        Value::Leaf(s) => writeln!(out, " {}", s).unwrap(),
+        Value::Ref { r#ref } => {
+            let target = refs
+                .locate(r#ref)
+                .map(|(table, i)| format!("{}[{}]", table, i))
+                .unwrap_or_else(|| "unresolved".to_string());
+            writeln!(out, " ref({}) -> {}", r#ref, target).unwrap();
+        }
+        Value::Array(arr) if arr.is_empty() => out.push_str(" []\n"),
+        Value::Map(map) if map.is_empty() => out.push_str(" {}\n"),
+        Value::Array(arr) => {
+            out.push('\n');
+            for v in arr {
+                write!(out, "{}-", pad).unwrap();
+                write_value(out, v, indent + 1, refs);
+            }
+        }
+        Value::Map(map) => {
+            out.push('\n');
+            for (k, v) in map {
+                write!(out, "{}{}:", pad, k).unwrap();
+                write_value(out, v, indent + 1, refs);
+            }
+        }
+    }
+}
+
 /// An entry with a key, fields map, and optional dependency entries
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Entry {
     pub key: String,
     pub value: Value,
     pub dep_entries: Vec<DepEntry>,
 }
 
+impl Entry {
+    /// The `serialindex` field inline in this entry's value, if any
+    pub fn serial_index(&self) -> Option<SerialIndex> {
+        SerialIndex::find(&self.value)
+    }
+}
+
 /// A dependency entry linking to another table
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DepEntry {
     pub dep_table_name: String,
     pub dep_index: u32,
@@ -46,14 +411,90 @@ pub struct DepEntry {
     pub value: Value,
 }
 
+impl DepEntry {
+    /// The `serialindex` field inline in this dep entry's value, if any
+    pub fn serial_index(&self) -> Option<SerialIndex> {
+        SerialIndex::find(&self.value)
+    }
+}
+
+/// A parsed `serialindex` field
+///
+/// The full form is a map with `index`, `_scope`, `_category` and `status`
+/// (see docs/06-ncs-format.md); some files store only the bare index, which
+/// leaves the other fields `None`. Values wrapped in `'...'` are unquoted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SerialIndex {
+    pub index: u32,
+    /// `_scope`: "Root" for item types, "Sub" for parts
+    pub scope: Option<String>,
+    /// `_category`, e.g. "inv_type"
+    pub category: Option<String>,
+    /// `status`: "Active" or "Inactive"
+    pub status: Option<String>,
+}
+
+impl SerialIndex {
+    /// Parse the value stored under a `serialindex` key
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Map(map) => {
+                let field = |name: &str| match map.get(name) {
+                    Some(Value::Leaf(s)) => Some(unquote(s).to_string()),
+                    _ => None,
+                };
+                Some(SerialIndex {
+                    index: field("index")?.parse().ok()?,
+                    scope: field("_scope"),
+                    category: field("_category"),
+                    status: field("status"),
+                })
+            }
+            Value::Leaf(s) => Some(SerialIndex {
+                index: unquote(s).parse().ok()?,
+                scope: None,
+                category: None,
+                status: None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Find the first `serialindex` key in a value tree and parse it
+    ///
+    /// A map holding `serialindex` directly answers for its whole subtree,
+    /// even when that field doesn't parse.
+    pub fn find(value: &Value) -> Option<Self> {
+        match value {
+            Value::Map(map) => match map.get("serialindex") {
+                Some(si) => Self::from_value(si),
+                None => map.values().find_map(Self::find),
+            },
+            Value::Array(arr) => arr.iter().find_map(Self::find),
+            _ => None,
+        }
+    }
+}
+
+/// The text between the first and last `'` (or after a lone one), or the whole string
+fn unquote(s: &str) -> &str {
+    match (s.find('\''), s.rfind('\'')) {
+        (Some(start), Some(end)) if end > start => &s[start + 1..end],
+        (Some(start), _) => &s[start + 1..],
+        _ => s,
+    }
+}
+
 /// Value types produced by decode_node
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum Value {
     Null,
     Leaf(String),
     Array(Vec<Value>),
-    Map(HashMap<String, Value>),
+    Map(BTreeMap<String, Value>),
     Ref { r#ref: String },
 }
 
@@ -62,27 +503,40 @@ pub enum Value {
 /// Tags carry per-record metadata like key names, numeric values,
 /// name lists, and inline variant nodes. Tags are stored separately
 /// from entries for round-trip fidelity.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "__tag")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "__tag"))]
 pub enum Tag {
-    #[serde(rename = "a")]
+    #[cfg_attr(feature = "serde", serde(rename = "a"))]
     KeyName { pair: String },
-    #[serde(rename = "b")]
+    #[cfg_attr(feature = "serde", serde(rename = "b"))]
     U32 { value: u32 },
-    #[serde(rename = "c")]
+    #[cfg_attr(feature = "serde", serde(rename = "c"))]
     F32 { u32_value: u32, f32_value: f32 },
-    #[serde(rename = "d")]
+    #[cfg_attr(feature = "serde", serde(rename = "d"))]
     NameListD { list: Vec<String> },
-    #[serde(rename = "e")]
+    #[cfg_attr(feature = "serde", serde(rename = "e"))]
     NameListE { list: Vec<String> },
-    #[serde(rename = "f")]
+    #[cfg_attr(feature = "serde", serde(rename = "f"))]
     NameListF { list: Vec<String> },
-    #[serde(rename = "p")]
+    /// Tag `p` (0x70): a full node, decoded like an entry value
+    ///
+    /// The payload starts with a type index into the row flags, and the
+    /// low 2 bits of those flags pick the subtype:
+    /// - 0: no payload ([`Value::Null`])
+    /// - 1: value string index + value kind index ([`Value::Leaf`])
+    /// - 2: nodes, each preceded by a continuation bit ([`Value::Array`])
+    /// - 3: self key, then key/node pairs behind continuation bits ([`Value::Map`])
+    ///
+    /// Row flag 0x80 adds a self key to any subtype, which wraps the
+    /// payload in a one-entry map.
+    #[cfg_attr(feature = "serde", serde(rename = "p"))]
     Variant { variant: Value },
 }
 
 /// Serial index entry extracted from parsed data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SerialIndexEntry {
     pub table_name: String,
     pub dep_table: String,
@@ -91,7 +545,8 @@ pub struct SerialIndexEntry {
 }
 
 /// A part entry with category context for the parts database
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CategorizedPart {
     pub category: u32,
     pub index: u32,
@@ -102,7 +557,8 @@ pub struct CategorizedPart {
 ///
 /// These parts (elements, stat mods, rarity components, etc.) are shared
 /// across all item categories rather than belonging to a single category.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SharedPart {
     pub dep_table: String,
     pub index: u32,
@@ -243,14 +699,18 @@ pub fn extract_shared_parts(doc: &Document) -> Vec<SharedPart> {
 /// Extract serial indices from a parsed document
 ///
 /// Looks for entries and dep_entries containing "serialindex" fields
-/// with numeric index values.
+/// with numeric index values. An entry or dep_entry whose value is a
+/// [`Value::Ref`] takes its index from the referenced entry (see
+/// [`Document::resolve_ref`]). Only one hop is followed, so a ref to
+/// another ref yields nothing.
 pub fn extract_serial_indices(doc: &Document) -> Vec<SerialIndexEntry> {
+    let refs = doc.ref_index();
     let mut results = Vec::new();
 
     for (table_name, table) in &doc.tables {
         for record in &table.records {
             for entry in &record.entries {
-                if let Some(index) = extract_index_from_value(&entry.value) {
+                if let Some(index) = resolve_index(&refs, &entry.value) {
                     results.push(SerialIndexEntry {
                         table_name: table_name.clone(),
                         dep_table: String::new(),
@@ -260,7 +720,7 @@ pub fn extract_serial_indices(doc: &Document) -> Vec<SerialIndexEntry> {
                 }
 
                 for dep_entry in &entry.dep_entries {
-                    if let Some(index) = extract_index_from_value(&dep_entry.value) {
+                    if let Some(index) = resolve_index(&refs, &dep_entry.value) {
                         results.push(SerialIndexEntry {
                             table_name: table_name.clone(),
                             dep_table: dep_entry.dep_table_name.clone(),
@@ -294,57 +754,16 @@ fn build_category_key_map(doc: &Document) -> HashMap<String, u32> {
     map
 }
 
-fn extract_index_from_value(value: &Value) -> Option<u32> {
-    match value {
-        Value::Map(map) => {
-            if let Some(si_value) = map.get("serialindex") {
-                return extract_index_from_serialindex(si_value);
-            }
-            for v in map.values() {
-                if let Some(idx) = extract_index_from_value(v) {
-                    return Some(idx);
-                }
-            }
-            None
-        }
-        Value::Array(arr) => {
-            for v in arr {
-                if let Some(idx) = extract_index_from_value(v) {
-                    return Some(idx);
-                }
-            }
-            None
-        }
-        _ => None,
+/// Serial index held inline, or on the entry a top-level ref points to
+fn resolve_index(refs: &RefIndex, value: &Value) -> Option<u32> {
+    if let Value::Ref { r#ref: name } = value {
+        return extract_index_from_value(&refs.entry(name)?.value);
     }
+    extract_index_from_value(value)
 }
 
-fn extract_index_from_serialindex(value: &Value) -> Option<u32> {
-    match value {
-        Value::Map(map) => {
-            if let Some(Value::Leaf(idx_str)) = map.get("index") {
-                let clean = if let Some(pos) = idx_str.find('\'') {
-                    let end = idx_str.rfind('\'').unwrap_or(idx_str.len());
-                    &idx_str[pos + 1..end]
-                } else {
-                    idx_str.as_str()
-                };
-                clean.parse().ok()
-            } else {
-                None
-            }
-        }
-        Value::Leaf(s) => {
-            let clean = if let Some(pos) = s.find('\'') {
-                let end = s.rfind('\'').unwrap_or(s.len());
-                &s[pos + 1..end]
-            } else {
-                s.as_str()
-            };
-            clean.parse().ok()
-        }
-        _ => None,
-    }
+fn extract_index_from_value(value: &Value) -> Option<u32> {
+    SerialIndex::find(value).map(|si| si.index)
 }
 
 #[cfg(test)]
@@ -352,6 +771,22 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "mmap")]
+    fn test_open_mmap() {
+        let path = std::env::temp_dir().join(format!("bl4-ncs-mmap-{}.bin", std::process::id()));
+        std::fs::write(&path, [0u8; 64]).unwrap();
+        let result = Document::open_mmap(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.unwrap().is_none());
+
+        assert!(matches!(
+            Document::open_mmap(path),
+            Err(crate::Error::Io(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn test_value_serialization() {
         let leaf = Value::Leaf("hello".to_string());
         let json = serde_json::to_string(&leaf).unwrap();
@@ -371,11 +806,11 @@ mod tests {
 
     #[test]
     fn test_extract_serial_index() {
-        let mut si_map = HashMap::new();
+        let mut si_map = BTreeMap::new();
         si_map.insert("index".to_string(), Value::Leaf("42".to_string()));
         si_map.insert("status".to_string(), Value::Leaf("Active".to_string()));
 
-        let mut entry_map = HashMap::new();
+        let mut entry_map = BTreeMap::new();
         entry_map.insert("serialindex".to_string(), Value::Map(si_map));
 
         let result = extract_index_from_value(&Value::Map(entry_map));
@@ -384,10 +819,10 @@ mod tests {
 
     #[test]
     fn test_extract_typed_serial_index() {
-        let mut si_map = HashMap::new();
+        let mut si_map = BTreeMap::new();
         si_map.insert("index".to_string(), Value::Leaf("int'237'".to_string()));
 
-        let mut entry_map = HashMap::new();
+        let mut entry_map = BTreeMap::new();
         entry_map.insert("serialindex".to_string(), Value::Map(si_map));
 
         let result = extract_index_from_value(&Value::Map(entry_map));
@@ -395,6 +830,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_tag_serialization() {
         let tag_a = Tag::KeyName {
             pair: "test_key".to_string(),
@@ -426,6 +862,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_tag_deserialization_roundtrip() {
         let tags = vec![
             Tag::KeyName {
@@ -457,6 +894,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_value_deserialization_roundtrip() {
         let values = vec![
             Value::Null,
@@ -477,15 +915,17 @@ mod tests {
 
     #[test]
     fn test_extract_serial_indices_from_dep_entries() {
-        let mut si_map = HashMap::new();
+        let mut si_map = BTreeMap::new();
         si_map.insert("index".to_string(), Value::Leaf("5".to_string()));
 
         let doc = Document {
-            tables: HashMap::from([(
+            tables: BTreeMap::from([(
                 "test_table".to_string(),
                 Table {
                     name: "test_table".to_string(),
                     deps: vec!["dep_table".to_string()],
+                    pair_remap: Vec::new(),
+                    value_remap: Vec::new(),
                     records: vec![Record {
                         tags: vec![],
                         entries: vec![Entry {
@@ -496,7 +936,7 @@ mod tests {
                                 dep_index: 0,
                                 key: "dep_key".to_string(),
                                 value: Value::Map({
-                                    let mut m = HashMap::new();
+                                    let mut m = BTreeMap::new();
                                     m.insert("serialindex".to_string(), Value::Map(si_map.clone()));
                                     m
                                 }),
@@ -505,6 +945,8 @@ mod tests {
                     }],
                 },
             )]),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
         };
 
         let indices = extract_serial_indices(&doc);
@@ -514,10 +956,156 @@ mod tests {
         assert_eq!(indices[0].dep_table, "dep_table");
     }
 
+    fn entry(key: &str, value: Value) -> Entry {
+        Entry {
+            key: key.to_string(),
+            value,
+            dep_entries: vec![],
+        }
+    }
+
+    fn ref_value(name: &str) -> Value {
+        Value::Ref {
+            r#ref: name.to_string(),
+        }
+    }
+
+    fn table(name: &str, entries: Vec<Entry>) -> (String, Table) {
+        let table = Table {
+            name: name.to_string(),
+            deps: vec![],
+            pair_remap: Vec::new(),
+            value_remap: Vec::new(),
+            records: vec![Record {
+                tags: vec![],
+                entries,
+            }],
+        };
+        (name.to_string(), table)
+    }
+
+    #[test]
+    fn test_resolve_ref() {
+        let doc = Document {
+            tables: BTreeMap::from([
+                table("b", vec![entry("shared", Value::Null)]),
+                table(
+                    "a",
+                    vec![entry("other", Value::Null), entry("shared", Value::Null)],
+                ),
+            ]),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        };
+
+        let record = doc.resolve_ref("Shared").unwrap();
+        assert_eq!(record.keys().collect::<Vec<_>>(), vec!["other", "shared"]);
+        assert!(doc.resolve_ref("missing").is_none());
+
+        let refs = doc.ref_index();
+        assert_eq!(refs.locate("Shared"), Some(("a", 0)));
+        assert!(std::ptr::eq(refs.resolve("SHARED").unwrap(), record));
+        assert_eq!(refs.entry("shared").unwrap().key, "shared");
+        assert!(refs.locate("missing").is_none());
+    }
+
+    #[test]
+    fn test_extract_serial_indices_follows_refs() {
+        let mut with_ref = entry("jak_ps", ref_value("JAK_PS_Base"));
+        with_ref.dep_entries.push(DepEntry {
+            dep_table_name: "inv_comp".to_string(),
+            dep_index: 0,
+            key: "barrel".to_string(),
+            value: ref_value("barrel_base"),
+        });
+        let doc = Document {
+            tables: BTreeMap::from([
+                table(
+                    "inv",
+                    vec![with_ref, entry("dangling", ref_value("nowhere"))],
+                ),
+                table(
+                    "inv_base",
+                    vec![
+                        entry("jak_ps_base", make_serialindex_value(3)),
+                        entry("barrel_base", make_serialindex_value(12)),
+                        entry("hop", ref_value("jak_ps_base")),
+                    ],
+                ),
+                table("chain", vec![entry("twice", ref_value("hop"))]),
+            ]),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        };
+
+        let mut indices: Vec<(String, u32)> = extract_serial_indices(&doc)
+            .into_iter()
+            .filter(|e| e.table_name != "inv_base")
+            .map(|e| (e.part_name, e.index))
+            .collect();
+        indices.sort();
+        assert_eq!(
+            indices,
+            vec![("barrel".to_string(), 12), ("jak_ps".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn test_serial_index_fields() {
+        let leaf = |s: &str| Value::Leaf(s.to_string());
+        let dep = DepEntry {
+            dep_table_name: "inv_comp".to_string(),
+            dep_index: 0,
+            key: "part_barrel_01".to_string(),
+            value: Value::Map(BTreeMap::from([(
+                "serialindex".to_string(),
+                Value::Map(BTreeMap::from([
+                    ("index".to_string(), leaf("'42'")),
+                    ("_scope".to_string(), leaf("Sub")),
+                    ("_category".to_string(), leaf("inv_type")),
+                    ("status".to_string(), leaf("Active")),
+                ])),
+            )])),
+        };
+
+        assert_eq!(
+            dep.serial_index(),
+            Some(SerialIndex {
+                index: 42,
+                scope: Some("Sub".to_string()),
+                category: Some("inv_type".to_string()),
+                status: Some("Active".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_serial_index_forms() {
+        let bare = SerialIndex::from_value(&Value::Leaf("7".to_string())).unwrap();
+        assert_eq!(bare.index, 7);
+        assert!(bare.scope.is_none());
+        let unterminated = SerialIndex::from_value(&Value::Leaf("'8".to_string()));
+        assert_eq!(unterminated.map(|si| si.index), Some(8));
+
+        // Nested under another field
+        let nested = Value::Array(vec![Value::Null, make_serialindex_value(9)]);
+        assert_eq!(SerialIndex::find(&nested).map(|si| si.index), Some(9));
+
+        let no_index = Value::Map(BTreeMap::from([(
+            "serialindex".to_string(),
+            Value::Map(BTreeMap::from([(
+                "status".to_string(),
+                Value::Leaf("Active".to_string()),
+            )])),
+        )]));
+        assert!(SerialIndex::find(&no_index).is_none());
+        assert!(SerialIndex::find(&Value::Null).is_none());
+    }
+
     fn make_serialindex_value(index: u32) -> Value {
-        Value::Map(HashMap::from([(
+        Value::Map(BTreeMap::from([(
             "serialindex".to_string(),
-            Value::Map(HashMap::from([(
+            Value::Map(BTreeMap::from([(
                 "index".to_string(),
                 Value::Leaf(index.to_string()),
             )])),
@@ -527,11 +1115,13 @@ mod tests {
     #[test]
     fn test_extension_records_merge_into_category() {
         let doc = Document {
-            tables: HashMap::from([(
+            tables: BTreeMap::from([(
                 "inv".to_string(),
                 Table {
                     name: "inv".to_string(),
                     deps: vec!["inv_comp".to_string()],
+                    pair_remap: Vec::new(),
+                    value_remap: Vec::new(),
                     records: vec![
                         Record {
                             tags: vec![],
@@ -570,6 +1160,8 @@ mod tests {
                     ],
                 },
             )]),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
         };
 
         let parts = extract_categorized_parts(&doc);
@@ -586,6 +1178,87 @@ mod tests {
     }
 
     #[test]
+    fn test_document_accessors() {
+        let entry = |key: &str| Entry {
+            key: key.to_string(),
+            value: Value::Null,
+            dep_entries: vec![],
+        };
+        let table = Table {
+            name: "rarity".to_string(),
+            deps: vec![],
+            pair_remap: Vec::new(),
+            value_remap: Vec::new(),
+            records: vec![
+                Record {
+                    tags: vec![],
+                    entries: vec![entry("common"), entry("rare")],
+                },
+                Record {
+                    tags: vec![],
+                    entries: vec![entry("legendary")],
+                },
+            ],
+        };
+        let mut tables = BTreeMap::new();
+        tables.insert("rarity".to_string(), table);
+        tables.insert(
+            "element".to_string(),
+            Table {
+                name: "element".to_string(),
+                deps: vec![],
+                pair_remap: Vec::new(),
+                value_remap: Vec::new(),
+                records: vec![],
+            },
+        );
+        let doc = Document {
+            tables,
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        };
+
+        assert_eq!(doc.table_names(), vec!["element", "rarity"]);
+        let table = doc.table("rarity").unwrap();
+        assert_eq!(table.entries().count(), 3);
+        assert!(table.entry("legendary").is_some());
+        assert!(table.entry("missing").is_none());
+        assert_eq!(
+            table.records[0].keys().collect::<Vec<_>>(),
+            vec!["common", "rare"]
+        );
+        assert!(table.records[1].entry("common").is_none());
+        assert!(doc.table("missing").is_none());
+    }
+
+    #[test]
+    fn test_table_remap_bounds() {
+        let table = Table {
+            name: "rarity".to_string(),
+            deps: vec![],
+            pair_remap: vec![4, 0, 7],
+            value_remap: vec![2],
+            records: vec![],
+        };
+
+        assert_eq!(table.resolve_pair(0), Some(4));
+        assert_eq!(table.resolve_pair(2), Some(7));
+        assert_eq!(table.resolve_pair(3), None);
+        assert_eq!(table.resolve_pair(usize::MAX), None);
+        assert_eq!(table.resolve_value(0), Some(2));
+        assert_eq!(table.resolve_value(1), None);
+
+        let plain = Table {
+            pair_remap: Vec::new(),
+            value_remap: Vec::new(),
+            ..table
+        };
+        assert_eq!(plain.resolve_pair(0), None);
+        assert_eq!(plain.resolve_value(0), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
     fn test_record_tags_skip_empty() {
         let record = Record {
             tags: vec![],
@@ -601,4 +1274,51 @@ mod tests {
         let json = serde_json::to_string(&record_with_tags).unwrap();
         assert!(json.contains("\"tags\""), "non-empty tags should be present");
     }
+
+    #[test]
+    fn test_record_pretty() {
+        let mut part = entry("part_barrel_01", make_serialindex_value(7));
+        part.dep_entries.push(DepEntry {
+            dep_table_name: "inv_comp".to_string(),
+            dep_index: 2,
+            key: "barrel".to_string(),
+            value: Value::Map(BTreeMap::from([
+                ("base".to_string(), ref_value("Barrel_Base")),
+                ("aliases".to_string(), Value::Array(vec![ref_value("gone")])),
+            ])),
+        });
+        let mut doc = Document {
+            tables: BTreeMap::from([
+                table("inv", vec![part]),
+                table("inv_comp", vec![entry("barrel_base", Value::Null)]),
+            ]),
+            record_spans: Vec::new(),
+            parse_errors: Vec::new(),
+        };
+        doc.tables.get_mut("inv").unwrap().records[0].tags = vec![
+            Tag::KeyName {
+                pair: "part".to_string(),
+            },
+            Tag::U32 { value: 3 },
+        ];
+
+        let record = &doc.tables["inv"].records[0];
+        assert_eq!(
+            record.pretty(&doc),
+            "tags (2):\n\
+             \x20 a key_name part\n\
+             \x20 b u32 3\n\
+             entries (1):\n\
+             \x20 [0] part_barrel_01\n\
+             \x20   serialindex: 7\n\
+             \x20   value:\n\
+             \x20     serialindex:\n\
+             \x20       index: 7\n\
+             \x20   dep inv_comp[2] barrel\n\
+             \x20     value:\n\
+             \x20       aliases:\n\
+             \x20         - ref(gone) -> unresolved\n\
+             \x20       base: ref(Barrel_Base) -> inv_comp[0]\n"
+        );
+    }
 }
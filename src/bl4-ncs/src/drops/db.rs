@@ -20,6 +20,7 @@ pub struct DropsDb {
 
 impl DropsDb {
     /// Load drops database from a manifest file
+    #[cfg(feature = "serde")]
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, std::io::Error> {
         let content = std::fs::read_to_string(path)?;
         let manifest: DropsManifest = serde_json::from_str(&content)
@@ -611,6 +611,7 @@ pub fn generate_drop_pools_tsv(manifest: &DropsManifest) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_collect_leaf_strings_leaf() {
@@ -636,7 +637,7 @@ mod tests {
 
     #[test]
     fn test_collect_leaf_strings_map() {
-        let mut map = HashMap::new();
+        let mut map = BTreeMap::new();
         map.insert("k1".to_string(), Value::Leaf("v1".to_string()));
         map.insert("k2".to_string(), Value::Leaf("v2".to_string()));
         let value = Value::Map(map);
@@ -656,7 +657,7 @@ mod tests {
 
     #[test]
     fn test_collect_leaf_strings_nested() {
-        let mut inner_map = HashMap::new();
+        let mut inner_map = BTreeMap::new();
         inner_map.insert("deep".to_string(), Value::Leaf("found_it".to_string()));
 
         let value = Value::Array(vec![
@@ -1,15 +1,17 @@
 //! Drop-related type definitions
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Boss name mappings loaded from embedded data
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BossNameMapping {
     /// Main mapping from ItemPoolList suffix to display name
     pub boss_names: HashMap<String, String>,
     /// Alias mappings for fuzzy matching
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub aliases: HashMap<String, String>,
 }
 
@@ -208,7 +210,8 @@ impl BossNameMapping {
 }
 
 /// Drop source type
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum DropSource {
     /// Dedicated boss drop
     Boss,
@@ -235,12 +238,16 @@ impl std::fmt::Display for DropSource {
 }
 
 /// A single drop entry mapping a source to an item
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DropEntry {
     /// Source name (internal boss name, "Black Market", mission name, etc.)
     pub source: String,
     /// Display name for the source (human-readable, from NameData)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
     pub source_display: Option<String>,
     /// Source type
     pub source_type: DropSource,
@@ -261,19 +268,20 @@ pub struct DropEntry {
 }
 
 /// Drop probability tiers
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DropProbabilities {
-    #[serde(rename = "Primary")]
+    #[cfg_attr(feature = "serde", serde(rename = "Primary"))]
     pub primary: f64,
-    #[serde(rename = "Secondary")]
+    #[cfg_attr(feature = "serde", serde(rename = "Secondary"))]
     pub secondary: f64,
-    #[serde(rename = "Tertiary")]
+    #[cfg_attr(feature = "serde", serde(rename = "Tertiary"))]
     pub tertiary: f64,
-    #[serde(rename = "Shiny")]
+    #[cfg_attr(feature = "serde", serde(rename = "Shiny"))]
     pub shiny: f64,
-    #[serde(rename = "TrueBoss")]
+    #[cfg_attr(feature = "serde", serde(rename = "TrueBoss"))]
     pub true_boss: f64,
-    #[serde(rename = "TrueBossShiny")]
+    #[cfg_attr(feature = "serde", serde(rename = "TrueBossShiny"))]
     pub true_boss_shiny: f64,
 }
 
@@ -291,7 +299,8 @@ impl Default for DropProbabilities {
 }
 
 /// The drops database manifest format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DropsManifest {
     pub version: u32,
     pub drops: Vec<DropEntry>,
@@ -318,7 +327,8 @@ pub struct DropLocation {
 }
 
 /// World drop pool information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WorldDropPool {
     /// Pool name (e.g., "Shields", "Pistols")
     pub name: String,
@@ -0,0 +1,133 @@
+//! Typed NCS format codes
+//!
+//! The format code is the short ASCII string after the type name in a
+//! decompressed NCS header. Each letter names a tag type that may appear in
+//! the binary section, so different codes imply different record layouts.
+//!
+//! ## Parseability
+//!
+//! [`crate::parse_ncs_binary`] reads the layout from the type-code table in
+//! the binary section rather than branching on the format code, so every
+//! known code below (and most `Other` codes) decodes through the same path.
+//! [`crate::NcsContent`] string extraction is format-independent.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+/// A known NCS format code, or the raw string for anything else
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FormatCode {
+    /// Extended entries with dependents (achievement, preferredparts)
+    Abjx,
+    /// Indexed entries (itempoollist, aim_assist)
+    Abij,
+    /// Labeled entries (inv_name_part)
+    Abjl,
+    /// Hash-indexed entries (inv_params)
+    Abhj,
+    /// Property-based entries (audio_event)
+    Abpe,
+    /// Actor definitions (gbxactor)
+    Abef,
+    /// Full tag-based encoding (inv)
+    Abcefhijl,
+    /// Any code not listed above
+    Other(String),
+}
+
+impl FormatCode {
+    /// The code as it appears in the file
+    pub fn as_str(&self) -> &str {
+        match self {
+            FormatCode::Abjx => "abjx",
+            FormatCode::Abij => "abij",
+            FormatCode::Abjl => "abjl",
+            FormatCode::Abhj => "abhj",
+            FormatCode::Abpe => "abpe",
+            FormatCode::Abef => "abef",
+            FormatCode::Abcefhijl => "abcefhijl",
+            FormatCode::Other(code) => code,
+        }
+    }
+
+    /// Check if the code includes a tag letter (e.g. 'i' for indexed entries)
+    pub fn has_tag(&self, tag: char) -> bool {
+        self.as_str().contains(tag)
+    }
+
+    /// True for codes with a dedicated variant
+    pub fn is_known(&self) -> bool {
+        !matches!(self, FormatCode::Other(_))
+    }
+}
+
+impl From<&str> for FormatCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "abjx" => FormatCode::Abjx,
+            "abij" => FormatCode::Abij,
+            "abjl" => FormatCode::Abjl,
+            "abhj" => FormatCode::Abhj,
+            "abpe" => FormatCode::Abpe,
+            "abef" => FormatCode::Abef,
+            "abcefhijl" => FormatCode::Abcefhijl,
+            other => FormatCode::Other(other.to_string()),
+        }
+    }
+}
+
+impl FromStr for FormatCode {
+    type Err = Infallible;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Ok(FormatCode::from(code))
+    }
+}
+
+impl fmt::Display for FormatCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for FormatCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_code_roundtrip() {
+        for code in ["abjx", "abij", "abjl", "abhj", "abpe", "abef", "abcefhijl"] {
+            let format: FormatCode = code.parse().unwrap();
+            assert!(format.is_known());
+            assert_eq!(format.as_str(), code);
+        }
+    }
+
+    #[test]
+    fn test_format_code_other() {
+        let format = FormatCode::from("abhX");
+        assert_eq!(format, FormatCode::Other("abhX".to_string()));
+        assert!(!format.is_known());
+        assert_eq!(format.to_string(), "abhX");
+        #[cfg(feature = "serde")]
+        assert_eq!(serde_json::to_string(&format).unwrap(), "\"abhX\"");
+    }
+
+    #[test]
+    fn test_format_code_has_tag() {
+        assert!(FormatCode::Abij.has_tag('i'));
+        assert!(!FormatCode::Abjx.has_tag('i'));
+        assert!(FormatCode::Abcefhijl.has_tag('l'));
+    }
+}
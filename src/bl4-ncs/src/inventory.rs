@@ -22,9 +22,12 @@
 //! }
 //! ```
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::strings::extract_ascii_strings;
+
 /// Known weapon manufacturers
 const MANUFACTURERS: &[&str] = &["BOR", "DAD", "JAK", "MAL", "ORD", "TED", "TOR", "VLA"];
 
@@ -32,7 +35,8 @@ const MANUFACTURERS: &[&str] = &["BOR", "DAD", "JAK", "MAL", "ORD", "TED", "TOR"
 const WEAPON_TYPES: &[&str] = &["AR", "HW", "PS", "SG", "SM", "SR"];
 
 /// Serial index entry for a part
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SerialIndex {
     /// Part name (e.g., "part_barrel_01")
     pub part: String,
@@ -45,7 +49,8 @@ pub struct SerialIndex {
 }
 
 /// Extracted part indices from inv.bin
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PartIndices {
     /// Item type this set of parts belongs to (e.g., "DAD_PS", "Armor_Shield")
     pub item_type: String,
@@ -54,7 +59,8 @@ pub struct PartIndices {
 }
 
 /// Parsed inventory containing all item types and their parts
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Inventory {
     /// All item types with their valid parts
     pub items: Vec<ItemParts>,
@@ -63,7 +69,8 @@ pub struct Inventory {
 }
 
 /// Item type with its valid parts
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ItemParts {
     /// Item identifier (e.g., "DAD_PS", "Armor_Shield")
     pub item_id: String,
@@ -72,13 +79,14 @@ pub struct ItemParts {
     /// All valid parts for this item
     pub parts: Vec<String>,
     /// Legendary compositions
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
     pub legendary_compositions: Vec<LegendaryComposition>,
 }
 
 /// Item category
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ItemCategory {
     /// Weapon (MANU_TYPE pattern)
     Weapon {
@@ -92,12 +100,13 @@ pub enum ItemCategory {
 }
 
 /// Legendary composition with mandatory parts
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LegendaryComposition {
     /// Composition name (e.g., "comp_05_legendary_Zipgun")
     pub name: String,
     /// Unique naming part (e.g., "uni_zipper")
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub unique_name: Option<String>,
     /// Mandatory unique parts
     pub mandatory_parts: Vec<String>,
@@ -346,7 +355,8 @@ fn is_part_name(s: &str) -> bool {
 
 
 /// Raw string entry from NCS data
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RawStringEntry {
     /// Index in string table
     pub string_index: usize,
@@ -383,7 +393,8 @@ pub fn raw_strings_to_tsv(strings: &[RawStringEntry]) -> String {
 ///
 /// For each numeric string, captures the preceding non-numeric string.
 /// No filtering - outputs everything for downstream processing.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StringNumericPair {
     /// Index of the preceding string
     pub string_index: usize,
@@ -431,27 +442,10 @@ pub fn string_numeric_pairs_to_tsv(pairs: &[StringNumericPair]) -> String {
 
 /// Extract null-terminated strings from binary data
 fn extract_null_strings(data: &[u8]) -> Vec<String> {
-    let mut strings = Vec::new();
-    let mut current = Vec::new();
-
-    for &b in data {
-        if b == 0 {
-            if !current.is_empty() {
-                if let Ok(s) = std::str::from_utf8(&current) {
-                    if !s.is_empty() {
-                        strings.push(s.to_string());
-                    }
-                }
-                current.clear();
-            }
-        } else if (32..=126).contains(&b) {
-            current.push(b);
-        } else {
-            current.clear();
-        }
-    }
-
-    strings
+    extract_ascii_strings(data, 1)
+        .into_iter()
+        .map(|(_, s)| s)
+        .collect()
 }
 
 /// Parse a weapon type identifier (e.g., "DAD_PS")
@@ -555,6 +549,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "serde")]
     fn test_item_category_serialize() {
         let weapon = ItemCategory::Weapon {
             manufacturer: "DAD".to_string(),
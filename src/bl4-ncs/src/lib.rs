@@ -20,15 +20,26 @@
 //! - Bytes 0-4: "_NCS/" magic
 //! - Bytes 6-7: Entry count
 //! - Remaining: Metadata and string table
+//!
+//! # Features
+//!
+//! - `serde` (default): `Serialize`/`Deserialize` for parsed types and
+//!   [`DropsDb::load`] for JSON drop manifests. Build with
+//!   `--no-default-features` to use the parser without serde.
+//! - `mmap`: [`ParsedDocument::open_mmap`] parses a decompressed file through
+//!   a memory map instead of reading it into a `Vec`.
 
 mod bit_reader;
 mod content;
 mod data;
 pub mod data_table;
+mod dependency;
+mod diff;
 pub mod document;
 pub mod drops;
 mod extract;
 mod field;
+mod format_code;
 mod hash;
 mod header;
 pub mod inventory;
@@ -37,11 +48,18 @@ pub mod name_data;
 pub mod oodle;
 pub mod pak;
 pub mod parse;
+mod scan;
+mod strings;
 mod types;
 mod unpack;
 
 // Re-export main types
-pub use content::{Content as NcsContent, Header as NcsContentHeader};
+pub use content::{
+    parse_category_names as parse_ncs_category_names,
+    parse_control_section as parse_ncs_control_section, Content as NcsContent,
+    ControlSection as NcsControlSection, EntryValue as NcsEntryValue, Header as NcsContentHeader,
+    QuickStats as NcsQuickStats,
+};
 pub use data_table::{
     extract_data_tables, extract_data_tables_from_dir, table_to_tsv, write_data_tables, DataTable,
     DataTableManifest, DataTableRow,
@@ -57,6 +75,7 @@ pub use data::{
 };
 pub use extract::{extract_from_pak, ExtractionResult, NcsFile};
 pub use field::{known as fields, Field, Type as FieldType};
+pub use format_code::FormatCode;
 pub use hash::fnv1a_hash;
 pub use manifest::{
     scan as scan_for_ncs_manifests, Entry as NcsManifestEntry, Manifest as NcsManifest,
@@ -80,10 +99,23 @@ pub use document::{
     Document as ParsedDocument, Table as ParsedTable, Record as ParsedRecord2,
     Entry as ParsedEntry, DepEntry as ParsedDepEntry, Value as ParsedValue,
     Tag as ParsedTag, SerialIndexEntry as DocumentSerialIndexEntry,
+    SerialIndex as DocumentSerialIndex, RecordError as ParsedRecordError,
+    RefIndex as ParsedRefIndex,
     CategorizedPart,
 };
-pub use parse::parse as parse_ncs_binary;
-pub use types::{UnpackedString, UnpackedValue};
+pub use dependency::{build_dependency_graph, DependencyGraph, DependencyNode};
+pub use diff::{diff_documents, DocumentDiff, EntryDiff, FieldChange, TableDiff};
+pub use parse::{
+    count_records as count_ncs_records, parse as parse_ncs_binary, parse_document, parse_document_with_options,
+    parse_with_options as parse_ncs_binary_with_options, ParseOptions as NcsParseOptions,
+    StringTable as NcsStringTable,
+};
+pub use scan::{
+    scan_directory, ScanOptions as NcsScanOptions, ScanResult as NcsScanResult,
+    ScannedFile as NcsScannedFile,
+};
+pub use strings::{extract_ascii_strings, is_printable_ascii};
+pub use types::{PackedStrings, UnpackedString, UnpackedValue};
 pub use unpack::{find_packed_strings, unpack_string};
 
 /// Magic bytes for NCS format: "NCS" (bytes 1-3 of header)
@@ -98,6 +130,9 @@ pub const OODLE_MAGIC: u32 = 0xb7756362;
 /// Header size in bytes
 pub const NCS_HEADER_SIZE: usize = data::HEADER_SIZE;
 
+/// Most padding bytes skipped before an NCS header
+pub const NCS_MAX_PREAMBLE: usize = data::MAX_PREAMBLE;
+
 /// Manifest header size
 pub const NCS_MANIFEST_HEADER_SIZE: usize = manifest::HEADER_SIZE;
 
@@ -132,10 +167,27 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Check if data starts with NCS data magic
+///
+/// Requires the magic at offset 0; use [`find_ncs_start`] to also accept
+/// files with padding in front of the header.
 pub fn is_ncs(data: &[u8]) -> bool {
     data.len() >= 4 && data[1..4] == NCS_MAGIC && data[0] != b'_'
 }
 
+/// Offset of the NCS data header, allowing up to [`NCS_MAX_PREAMBLE`] bytes
+/// of padding before it
+///
+/// None if no header is found (see [`NcsHeader::locate`]) or the data is a
+/// manifest. Everything that takes compressed NCS data, like
+/// [`decompress_ncs`], skips the same padding, so a `Some` here means the
+/// data can be passed on as is.
+pub fn find_ncs_start(data: &[u8]) -> Option<usize> {
+    match NcsHeader::locate(data) {
+        Ok((start, _)) if data[start] != b'_' => Some(start),
+        _ => None,
+    }
+}
+
 /// Check if data starts with NCS manifest magic
 pub fn is_ncs_manifest(data: &[u8]) -> bool {
     data.len() >= 5 && data[0..5] == NCS_MANIFEST_MAGIC
@@ -157,6 +209,27 @@ mod tests {
         assert!(!is_ncs(&[0x01, 0x4e, 0x43]));
     }
 
+    #[test]
+    fn test_find_ncs_start() {
+        let payload = b"Hello, World!";
+        let size = payload.len() as u32;
+        let mut data = vec![0xEF, 0xBB, 0xBF];
+        data.push(0x01);
+        data.extend_from_slice(&NCS_MAGIC);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&size.to_le_bytes());
+        data.extend_from_slice(&size.to_le_bytes());
+        data.extend_from_slice(payload);
+
+        assert!(!is_ncs(&data));
+        assert_eq!(find_ncs_start(&data), Some(3));
+        assert_eq!(find_ncs_start(&data[3..]), Some(0));
+        assert_eq!(decompress_ncs(&data).unwrap(), payload);
+
+        assert_eq!(find_ncs_start(b"_NCS/\0\0\0\0\0\0\0\0\0\0\0\0"), None);
+        assert_eq!(find_ncs_start(&[0x01, 0x4e, 0x43]), None);
+    }
+
     #[test]
     fn test_is_ncs_manifest() {
         assert!(is_ncs_manifest(&[0x5f, 0x4e, 0x43, 0x53, 0x2f, 0x00]));
@@ -316,6 +389,7 @@ mod investigate_failures {
 #[cfg(test)]
 mod parse_real_ncs {
     use super::test_paths;
+    use crate::is_printable_ascii;
     use crate::data::{decompress, scan};
     use crate::content::Content;
 
@@ -367,7 +441,7 @@ mod parse_real_ncs {
                         println!("  First 64 bytes: {}", hex);
                         // Show as string
                         let s: String = decompressed.iter().take(100)
-                            .map(|&b| if b >= 32 && b < 127 { b as char } else { '.' })
+                            .map(|&b| if is_printable_ascii(b) { b as char } else { '.' })
                             .collect();
                         println!("  As string: {}", s);
                     }
@@ -622,6 +696,7 @@ mod test_extraction {
 #[cfg(test)]
 mod investigate_missing {
     use super::test_paths;
+    use crate::is_printable_ascii;
     use crate::manifest::scan as scan_manifests;
 
     #[test]
@@ -676,7 +751,7 @@ mod investigate_missing {
         println!("\nData after last known NCS chunk (offset ~10602549):");
         let start = 10602549 + 1000; // A bit after the last chunk
         let preview: String = data[start..start+200].iter()
-            .map(|&b| if b >= 32 && b < 127 { b as char } else { '.' })
+            .map(|&b| if is_printable_ascii(b) { b as char } else { '.' })
             .collect();
         println!("  {}", preview);
     }
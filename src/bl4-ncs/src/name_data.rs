@@ -7,12 +7,14 @@
 //!
 //! Example: `NameData_Meathead, D342D6EE47173677CE1C068BADA88F69, Saddleback`
 
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
 /// A single NameData entry mapping an internal type to a display name
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NameDataEntry {
     /// Internal type (e.g., "Meathead", "Thresher", "Bat")
     pub internal_type: String,
@@ -23,15 +25,16 @@ pub struct NameDataEntry {
 }
 
 /// Collection of NameData mappings
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NameDataMap {
     /// All extracted entries
     pub entries: Vec<NameDataEntry>,
     /// Index: internal_type (lowercase) → list of entries
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     by_type: HashMap<String, Vec<usize>>,
     /// Index: display_name (lowercase) → entry index
-    #[serde(skip)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     by_display: HashMap<String, usize>,
 }
 
@@ -3,6 +3,14 @@
 //! The decompressed NCS payload begins with a 16-byte header followed by
 //! null-terminated header strings, then the body (type code table + data).
 
+use super::ParseOptions;
+
+/// Default cap on the header's declared entry count
+pub const DEFAULT_MAX_BLOB_ENTRIES: u32 = 100_000;
+
+/// Default cap on the header string block size in bytes
+pub const DEFAULT_MAX_STRING_BYTES: u32 = 10_000_000;
+
 /// Blob header at start of decompressed NCS data (16 bytes)
 #[derive(Debug, Clone, Copy)]
 pub struct BlobHeader {
@@ -16,6 +24,11 @@ impl BlobHeader {
     pub const SIZE: usize = 16;
 
     pub fn parse(data: &[u8]) -> Option<Self> {
+        Self::parse_with_options(data, &ParseOptions::default())
+    }
+
+    /// Parse the header, rejecting counts above the given limits
+    pub fn parse_with_options(data: &[u8], options: &ParseOptions) -> Option<Self> {
         if data.len() < Self::SIZE {
             return None;
         }
@@ -30,10 +43,10 @@ impl BlobHeader {
         if header.reserved != 0 {
             return None;
         }
-        if header.entry_count > 100_000 {
+        if header.entry_count > options.max_blob_entries {
             return None;
         }
-        if header.string_bytes > 10_000_000 {
+        if header.string_bytes > options.max_string_bytes {
             return None;
         }
 
@@ -62,6 +75,26 @@ pub fn extract_header_strings(data: &[u8], blob: &BlobHeader) -> Vec<String> {
     parse_null_terminated_strings(block)
 }
 
+/// Count the header strings without allocating them
+///
+/// Only the string block (`string_table_offset..body_offset`) is scanned, so
+/// printable bytes in the body are never counted. Always equal to
+/// `extract_header_strings(data, blob).len()`.
+pub fn count_strings(data: &[u8], blob: &BlobHeader) -> usize {
+    let Some(block) = data.get(blob.string_table_offset()..blob.body_offset()) else {
+        return 0;
+    };
+
+    // split() yields an empty segment after a trailing terminator that
+    // parse_null_terminated_strings never emits
+    let trailing = usize::from(block.last().is_none_or(|&b| b == 0));
+    let valid = block
+        .split(|&b| b == 0)
+        .filter(|s| std::str::from_utf8(s).is_ok())
+        .count();
+    valid - trailing
+}
+
 /// Parse null-terminated strings from a byte block
 pub fn parse_null_terminated_strings(block: &[u8]) -> Vec<String> {
     let mut out = Vec::new();
@@ -114,6 +147,56 @@ mod tests {
         assert!(BlobHeader::parse(&data).is_none());
     }
 
+    #[test]
+    fn test_blob_header_respects_options() {
+        let mut data = vec![0u8; 16];
+        data[0] = 5; // entry_count = 5
+        data[8] = 10; // string_bytes = 10
+
+        let options = ParseOptions {
+            max_blob_entries: 4,
+            ..ParseOptions::default()
+        };
+        assert!(BlobHeader::parse_with_options(&data, &options).is_none());
+
+        let options = ParseOptions {
+            max_string_bytes: 9,
+            ..ParseOptions::default()
+        };
+        assert!(BlobHeader::parse_with_options(&data, &options).is_none());
+    }
+
+    #[test]
+    fn test_count_strings_stops_at_body() {
+        let mut data = vec![0u8; 16];
+        data[8] = 11; // string_bytes = 11
+        data.extend_from_slice(b"inv\0\0parts\0");
+        data.extend_from_slice(b"printable\0body\0bytes");
+        let blob = BlobHeader::parse(&data).unwrap();
+
+        assert_eq!(count_strings(&data, &blob), 3);
+        assert_eq!(
+            count_strings(&data, &blob),
+            extract_header_strings(&data, &blob).len()
+        );
+    }
+
+    #[test]
+    fn test_count_strings_edge_cases() {
+        let mut data = vec![0u8; 16];
+        let blob = BlobHeader::parse(&data).unwrap();
+        assert_eq!(count_strings(&data, &blob), 0);
+
+        data[8] = 3; // unterminated final string
+        data.extend_from_slice(b"a\0b");
+        let blob = BlobHeader::parse(&data).unwrap();
+        assert_eq!(count_strings(&data, &blob), 2);
+
+        data[8] = 40; // block runs past the end
+        let blob = BlobHeader::parse(&data).unwrap();
+        assert_eq!(count_strings(&data, &blob), 0);
+    }
+
     #[test]
     fn test_parse_null_terminated_strings() {
         let data = b"hello\0world\0test\0";
@@ -3,9 +3,23 @@
 //! Implements the NCS table data decode algorithm: tables → records → entries.
 
 use crate::bit_reader::{bit_width, BitReader};
-use crate::document::{DepEntry, Document, Entry, Record, Table, Tag, Value};
+use crate::document::{DepEntry, Document, Entry, Record, RecordError, Table, Tag, Value};
 use crate::parse::remap::FixedWidthIntArray;
-use std::collections::HashMap;
+use crate::parse::ParseOptions;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Default cap on packed name list length (tags d/e/f) before giving up
+pub const DEFAULT_MAX_NAME_LIST_LEN: usize = 4096;
+
+/// Default cap on bytes scanned for the next record after a bad length prefix
+pub const DEFAULT_MAX_RESYNC_BYTES: usize = 4096;
+
+/// Shortest record a resync will accept: the length prefix and a 'z' tag
+const MIN_RECORD_BYTES: usize = 5;
+
+/// Bytes a record's tag section can start with
+const TAG_BYTES: &[u8] = b"abcdefpz";
 
 /// All string tables and precomputed bit widths needed during decoding
 struct DecodeContext<'a> {
@@ -18,6 +32,8 @@ struct DecodeContext<'a> {
     key_index_bits: u8,
     type_index_bits: u8,
     row_flags: &'a [u32],
+    max_name_list_len: usize,
+    max_resync_bytes: usize,
 }
 
 /// Per-table remap and dependency state
@@ -126,7 +142,10 @@ fn decode_node_value(
             Some(Value::Array(arr))
         }
         3 => {
-            let mut map = HashMap::new();
+            // Field count is never fixed by schema (e.g. serialindex usually has
+            // status/index/_category/_scope but varies by file type); each field
+            // is preceded by a continuation bit and a 0 bit terminates the map.
+            let mut map = BTreeMap::new();
             while reader.position() < record_end_bit {
                 if !reader.read_bit()? {
                     break;
@@ -144,7 +163,7 @@ fn decode_node_value(
 /// Wrap a value with a self_key if present and non-trivial
 fn wrap_with_self_key(self_key: String, value: Value) -> Option<Value> {
     if !self_key.is_empty() && !self_key.eq_ignore_ascii_case("none") {
-        let mut wrapper = HashMap::new();
+        let mut wrapper = BTreeMap::new();
         wrapper.insert(self_key, value);
         Some(Value::Map(wrapper))
     } else {
@@ -153,20 +172,29 @@ fn wrap_with_self_key(self_key: String, value: Value) -> Option<Value> {
 }
 
 /// Read a packed name list (used by tags d/e/f)
+///
+/// Lists are terminated by an empty or "none" key. Returns `None` if the list
+/// reaches `ctx.max_name_list_len` without a terminator, since that means the
+/// stream is corrupt rather than holding a legitimately huge list.
 fn read_packed_name_list(
     reader: &mut BitReader,
     ctx: &DecodeContext,
     pair_remap: Option<&FixedWidthIntArray>,
 ) -> Option<Vec<String>> {
     let mut list = Vec::new();
-    for _ in 0..4096 {
+    while list.len() < ctx.max_name_list_len {
         let s = read_pair_vec_string(reader, ctx, pair_remap)?;
         if s.is_empty() || s.eq_ignore_ascii_case("none") {
-            break;
+            return Some(list);
         }
         list.push(s);
     }
-    Some(list)
+    log::warn!(
+        "name list hit cap of {} entries without terminator at bit {}",
+        ctx.max_name_list_len,
+        reader.position()
+    );
+    None
 }
 
 /// Read an entry or dep_entry value based on a 2-bit opcode
@@ -223,6 +251,7 @@ fn parse_tags(
                 .map(|list| Tag::NameListE { list }),
             b'f' => read_packed_name_list(reader, ctx, tctx.pair_remap)
                 .map(|list| Tag::NameListF { list }),
+            // Subtype comes from the node's row flags; see Tag::Variant
             b'p' => decode_node(reader, ctx, tctx, record_end_bit)
                 .map(|variant| Tag::Variant { variant }),
             _ => break,
@@ -324,43 +353,173 @@ fn parse_dep_entries(
     dep_entries
 }
 
+/// Re-synchronize the reader at a record's declared end
+///
+/// Records carry a byte count, so a misparse inside one record must not leak
+/// into the next. Returns false (and logs) if parsing ran past the end.
+fn finish_record(reader: &mut BitReader, record_start: usize, record_end_bit: usize) -> bool {
+    let consumed = reader.position();
+    reader.seek(record_end_bit);
+
+    if consumed > record_end_bit {
+        log::warn!(
+            "NCS record at bit {} overran its byte_count by {} bits; resyncing",
+            record_start,
+            consumed - record_end_bit
+        );
+        return false;
+    }
+    true
+}
+
+/// What the reader found where the next record should start
+enum NextRecord {
+    /// A record's start and declared end, in bits
+    Record(usize, usize),
+    /// A length prefix at this bit that runs past the end of the data
+    BadLength(usize),
+    /// The table's 0 terminator, or the end of the data
+    End,
+}
+
+/// Read the next record's length prefix, leaving the reader after it
+fn next_record(reader: &mut BitReader) -> NextRecord {
+    reader.align_byte();
+    if !reader.has_bits(32) {
+        return NextRecord::End;
+    }
+
+    let record_start = reader.position();
+    let Some(record_len_bytes) = reader.read_bits(32) else {
+        return NextRecord::End;
+    };
+    if record_len_bytes == 0 {
+        if reader.has_bits(8) {
+            reader.read_bits(8);
+        }
+        return NextRecord::End;
+    }
+
+    let record_end_bit = (record_start + record_len_bytes as usize * 8) & !7;
+    if record_end_bit > reader.len_bits() {
+        return NextRecord::BadLength(record_start);
+    }
+    NextRecord::Record(record_start, record_end_bit)
+}
+
+/// Seek to the next plausible record after a bad length prefix at `bad_start`
+///
+/// Scans up to `max_bytes` bytes for a length prefix that fits the data, is
+/// followed by a tag byte, and ends where another such record or the 0
+/// terminator begins. Returns the number of bytes skipped.
+fn resync(reader: &mut BitReader, bad_start: usize, max_bytes: usize) -> Option<usize> {
+    let data = reader.data();
+    let bad_byte = bad_start / 8;
+    let end = (bad_byte + 1).saturating_add(max_bytes).min(data.len());
+    let found = (bad_byte + 1..end).find(|&pos| is_plausible_record(data, pos))?;
+    reader.seek(found * 8);
+    Some(found - bad_byte)
+}
+
+fn is_plausible_record(data: &[u8], pos: usize) -> bool {
+    let Some(len) = record_len_at(data, pos) else {
+        return false;
+    };
+    if !TAG_BYTES.contains(&data[pos + 4]) {
+        return false;
+    }
+    let next = pos + len;
+    read_u32_le(data, next) == Some(0) || record_len_at(data, next).is_some()
+}
+
+/// Length of a record at `pos` if it's long enough and fits the data
+fn record_len_at(data: &[u8], pos: usize) -> Option<usize> {
+    let len = read_u32_le(data, pos)? as usize;
+    (len >= MIN_RECORD_BYTES && pos + len <= data.len()).then_some(len)
+}
+
+fn read_u32_le(data: &[u8], pos: usize) -> Option<u32> {
+    let bytes = data.get(pos..pos.checked_add(4)?)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Resync after a bad length prefix, recording the outcome in `errors`
+///
+/// Returns false when no record could be found and the table must end.
+fn recover_record(
+    reader: &mut BitReader,
+    record_start: usize,
+    max_bytes: usize,
+    errors: &mut Vec<RecordError>,
+) -> bool {
+    let offset = record_start / 8;
+    match resync(reader, record_start, max_bytes) {
+        Some(skipped) => {
+            log::warn!(
+                "NCS record at byte {} has a bad length; skipped {} bytes to resync",
+                offset,
+                skipped
+            );
+            errors.push(RecordError::Resynced { offset, skipped });
+            true
+        }
+        None => {
+            log::warn!(
+                "NCS record at byte {} has a bad length; ending table",
+                offset
+            );
+            errors.push(RecordError::Unrecoverable { offset });
+            false
+        }
+    }
+}
+
 /// Parse all records from a table's record section
+///
+/// Each record's byte range within the reader's data is pushed to `spans`,
+/// and each record that fails to parse cleanly to `errors`. A bad length
+/// prefix no longer ends the table: parsing picks up again at the next
+/// plausible record within `max_resync_bytes`.
 fn parse_records(
     reader: &mut BitReader,
     ctx: &DecodeContext,
     tctx: &TableContext,
+    spans: &mut Vec<Range<usize>>,
+    errors: &mut Vec<RecordError>,
 ) -> Vec<Record> {
     let mut records = Vec::new();
 
     loop {
-        reader.align_byte();
-        if !reader.has_bits(32) {
-            break;
-        }
-
-        let record_start = reader.position();
-        let Some(record_len_bytes) = reader.read_bits(32) else {
-            break;
-        };
-        if record_len_bytes == 0 {
-            if reader.has_bits(8) {
-                reader.read_bits(8);
+        let (record_start, record_end_bit) = match next_record(reader) {
+            NextRecord::Record(start, end) => (start, end),
+            NextRecord::BadLength(start) => {
+                if recover_record(reader, start, ctx.max_resync_bytes, errors) {
+                    continue;
+                }
+                break;
             }
-            break;
-        }
-
-        let record_end_bit = (record_start + record_len_bytes as usize * 8) & !7;
-        if record_end_bit > reader.total_bits() {
-            break;
-        }
+            NextRecord::End => break,
+        };
+        log::trace!(
+            "NCS record {} at bit {} ({} of {} bits remaining)",
+            records.len(),
+            record_start,
+            reader.remaining_bits(),
+            reader.len_bits()
+        );
 
         let tags = parse_tags(reader, ctx, tctx, record_end_bit);
         let entries = parse_entries(reader, ctx, tctx, record_end_bit);
 
-        if reader.position() < record_end_bit {
-            reader.seek(record_end_bit);
+        let overrun = reader.position().saturating_sub(record_end_bit);
+        if !finish_record(reader, record_start, record_end_bit) {
+            errors.push(RecordError::Overrun {
+                offset: record_start / 8,
+                bits: overrun,
+            });
         }
 
+        spans.push(record_start / 8..record_end_bit / 8);
         records.push(Record { tags, entries });
     }
 
@@ -379,6 +538,8 @@ pub struct DecodeInput<'a> {
     pub key_strings_declared: u32,
     pub row_flags: &'a [u32],
     pub binary_offset: usize,
+    /// Resource limits for untrusted input
+    pub options: &'a ParseOptions,
 }
 
 /// Decode all table data from the binary section
@@ -400,24 +561,26 @@ pub fn decode_table_data(input: &DecodeInput) -> Option<Document> {
         key_index_bits: bit_width(input.key_strings_declared.max(1)),
         type_index_bits: bit_width(input.row_flags.len() as u32),
         row_flags: input.row_flags,
+        max_name_list_len: input.options.max_name_list_len,
+        max_resync_bytes: input.options.max_resync_bytes,
     };
 
     let table_id_bits = ctx.header_index_bits;
-    let mut tables = HashMap::new();
+    let mut tables = BTreeMap::new();
+    let mut record_spans = Vec::new();
+    let mut parse_errors = Vec::new();
 
     while reader.has_bits(table_id_bits as usize) {
-        let table_id = reader.read_bits(table_id_bits)?;
-        if table_id == 0 {
+        let Some(header) = read_table_header(&mut reader, input, table_id_bits)? else {
             break;
-        }
-
-        let table_name = input.header_strings.get(table_id as usize)?.clone();
-
-        let (dep_names, dep_count) =
-            read_table_deps(&mut reader, table_id_bits, input.header_strings);
-
-        let remap_a = FixedWidthIntArray::read(&mut reader)?;
-        let remap_b = FixedWidthIntArray::read(&mut reader)?;
+        };
+        let TableHeader {
+            name: table_name,
+            dep_names,
+            dep_count,
+            pair_remap: remap_a,
+            value_remap: remap_b,
+        } = header;
 
         let tctx = TableContext {
             pair_remap: if remap_a.is_active() { Some(&remap_a) } else { None },
@@ -430,21 +593,126 @@ pub fn decode_table_data(input: &DecodeInput) -> Option<Document> {
             dep_names,
         };
 
-        reader.align_byte();
-
-        let records = parse_records(&mut reader, &ctx, &tctx);
+        let records = parse_records(
+            &mut reader,
+            &ctx,
+            &tctx,
+            &mut record_spans,
+            &mut parse_errors,
+        );
 
         tables.insert(
             table_name.clone(),
             Table {
                 name: table_name,
                 deps: tctx.dep_names,
+                pair_remap: active_values(remap_a),
+                value_remap: active_values(remap_b),
                 records,
             },
         );
     }
 
-    Some(Document { tables })
+    // Spans and errors were measured within the binary section
+    for span in &mut record_spans {
+        *span = span.start + input.binary_offset..span.end + input.binary_offset;
+    }
+    for error in &mut parse_errors {
+        error.shift(input.binary_offset);
+    }
+
+    Some(Document {
+        tables,
+        record_spans,
+        parse_errors,
+    })
+}
+
+/// Values of a remap the decoder applied, or nothing if it was inactive
+fn active_values(remap: FixedWidthIntArray) -> Vec<u32> {
+    if remap.is_active() {
+        remap.values
+    } else {
+        Vec::new()
+    }
+}
+
+/// Fields read before a table's records
+struct TableHeader {
+    name: String,
+    dep_names: Vec<String>,
+    dep_count: usize,
+    pair_remap: FixedWidthIntArray,
+    value_remap: FixedWidthIntArray,
+}
+
+/// Read a table's id, deps and remaps, leaving the reader at its first record
+///
+/// `Some(None)` is the 0 table id that ends the table list; None means the
+/// header is malformed.
+fn read_table_header(
+    reader: &mut BitReader,
+    input: &DecodeInput,
+    table_id_bits: u8,
+) -> Option<Option<TableHeader>> {
+    let table_id = reader.read_bits(table_id_bits)?;
+    if table_id == 0 {
+        return Some(None);
+    }
+
+    let name = input.header_strings.get(table_id as usize)?.clone();
+    let (dep_names, dep_count) = read_table_deps(reader, table_id_bits, input.header_strings);
+
+    let max_count = input.options.max_array_count;
+    let pair_remap = FixedWidthIntArray::read_with_limit(reader, max_count)?;
+    let value_remap = FixedWidthIntArray::read_with_limit(reader, max_count)?;
+    reader.align_byte();
+
+    Some(Some(TableHeader {
+        name,
+        dep_names,
+        dep_count,
+        pair_remap,
+        value_remap,
+    }))
+}
+
+/// Count records without decoding them
+///
+/// Reads the same table headers and record length prefixes as
+/// [`decode_table_data`] but seeks past every record body, so it finds the
+/// same records at a fraction of the cost.
+pub fn count_table_records(input: &DecodeInput) -> Option<usize> {
+    if input.binary_offset >= input.data.len() {
+        return None;
+    }
+
+    let mut reader = BitReader::new(&input.data[input.binary_offset..]);
+    let table_id_bits = bit_width(input.header_strings.len() as u32);
+    let mut count = 0;
+
+    while reader.has_bits(table_id_bits as usize) {
+        if read_table_header(&mut reader, input, table_id_bits)?.is_none() {
+            break;
+        }
+        loop {
+            match next_record(&mut reader) {
+                NextRecord::Record(_, record_end_bit) => {
+                    reader.seek(record_end_bit);
+                    count += 1;
+                }
+                NextRecord::BadLength(start) => {
+                    let max_bytes = input.options.max_resync_bytes;
+                    if resync(&mut reader, start, max_bytes).is_none() {
+                        break;
+                    }
+                }
+                NextRecord::End => break,
+            }
+        }
+    }
+
+    Some(count)
 }
 
 /// Read dependency table IDs until a 0-terminator
@@ -493,6 +761,8 @@ mod tests {
             key_index_bits: bit_width(key_strings.len().max(1) as u32),
             type_index_bits: bit_width(row_flags.len() as u32),
             row_flags,
+            max_name_list_len: DEFAULT_MAX_NAME_LIST_LEN,
+            max_resync_bytes: DEFAULT_MAX_RESYNC_BYTES,
         }
     }
 
@@ -522,6 +792,7 @@ mod tests {
             key_strings_declared: 0,
             row_flags: &row_flags,
             binary_offset: 0,
+            options: &ParseOptions::default(),
         });
 
         let doc = result.unwrap();
@@ -701,6 +972,129 @@ mod tests {
         assert!(list.is_empty());
     }
 
+    #[test]
+    fn test_read_packed_name_list_missing_terminator() {
+        let key_strings: Vec<String> = vec!["none".to_string(), "foo".to_string()];
+        let row_flags = vec![0u32];
+        let mut ctx = make_decode_context(&key_strings, &[], &[], &row_flags);
+        ctx.max_name_list_len = 4;
+
+        // 1-bit indices, all 1 ("foo") - never terminates
+        let data = [0xFF, 0xFF];
+        let mut reader = BitReader::new(&data);
+        assert!(read_packed_name_list(&mut reader, &ctx, None).is_none());
+    }
+
+    #[test]
+    fn test_read_packed_name_list_respects_cap() {
+        let key_strings: Vec<String> = vec!["none".to_string(), "foo".to_string()];
+        let row_flags = vec![0u32];
+        let mut ctx = make_decode_context(&key_strings, &[], &[], &row_flags);
+        ctx.max_name_list_len = 3;
+
+        // Three "foo" entries would exceed the cap before the terminator is seen
+        let data = [0b0000_0111];
+        let mut reader = BitReader::new(&data);
+        assert!(read_packed_name_list(&mut reader, &ctx, None).is_none());
+
+        // Two entries + terminator fits
+        let data = [0b0000_0011];
+        let mut reader = BitReader::new(&data);
+        let list = read_packed_name_list(&mut reader, &ctx, None).unwrap();
+        assert_eq!(list, vec!["foo", "foo"]);
+    }
+
+    #[test]
+    fn test_finish_record_skips_padding() {
+        let data = [0u8; 16];
+        let mut reader = BitReader::new(&data);
+        reader.seek(40);
+
+        assert!(finish_record(&mut reader, 0, 64));
+        assert_eq!(reader.position(), 64);
+    }
+
+    #[test]
+    fn test_finish_record_resyncs_after_overrun() {
+        let data = [0u8; 16];
+        let mut reader = BitReader::new(&data);
+        reader.seek(70);
+
+        assert!(!finish_record(&mut reader, 0, 64));
+        assert_eq!(reader.position(), 64);
+    }
+
+    #[test]
+    fn test_parse_records_bounded_by_byte_count() {
+        // The first record declares 8 bytes but only uses the 4-byte length,
+        // the 'z' tag terminator and a 0 entry op; then the 0 terminator.
+        let mut data = vec![8, 0, 0, 0, b'z', 0, 0, 0];
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        let mut reader = BitReader::new(&data);
+        let key_strings = vec!["none".to_string()];
+        let row_flags = vec![0u32];
+        let ctx = make_decode_context(&key_strings, &[], &[], &row_flags);
+        let tctx = make_table_context();
+
+        let mut spans = Vec::new();
+        let mut errors = Vec::new();
+        let records = parse_records(&mut reader, &ctx, &tctx, &mut spans, &mut errors);
+        assert_eq!(records.len(), 1);
+        assert!(records[0].tags.is_empty());
+        assert!(records[0].entries.is_empty());
+        assert_eq!(spans, vec![0..8]);
+        assert!(errors.is_empty());
+        assert_eq!(reader.position(), data.len() * 8);
+    }
+
+    #[test]
+    fn test_parse_records_resyncs_past_bad_length() {
+        // A good record, one whose length runs past the data, a good record
+        // after 2 bytes of garbage, then the 0 terminator
+        let mut data = vec![5, 0, 0, 0, b'z'];
+        data.extend_from_slice(&[0xFF, 0xFF, 0, 0, b'z']);
+        data.extend_from_slice(&[0xAB, 0xCD]);
+        data.extend_from_slice(&[6, 0, 0, 0, b'z', 0]);
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        let mut reader = BitReader::new(&data);
+        let key_strings = vec!["none".to_string()];
+        let row_flags = vec![0u32];
+        let ctx = make_decode_context(&key_strings, &[], &[], &row_flags);
+        let tctx = make_table_context();
+
+        let mut spans = Vec::new();
+        let mut errors = Vec::new();
+        let records = parse_records(&mut reader, &ctx, &tctx, &mut spans, &mut errors);
+        assert_eq!(records.len(), 2);
+        assert_eq!(spans, vec![0..5, 12..18]);
+        assert_eq!(
+            errors,
+            vec![RecordError::Resynced {
+                offset: 5,
+                skipped: 7
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_records_gives_up_past_resync_limit() {
+        let mut data = vec![0xFF, 0xFF, 0, 0, b'z', 0xAB];
+        data.extend_from_slice(&[5, 0, 0, 0, b'z']);
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        let mut reader = BitReader::new(&data);
+        let key_strings = vec!["none".to_string()];
+        let row_flags = vec![0u32];
+        let mut ctx = make_decode_context(&key_strings, &[], &[], &row_flags);
+        ctx.max_resync_bytes = 4;
+        let tctx = make_table_context();
+
+        let mut spans = Vec::new();
+        let mut errors = Vec::new();
+        let records = parse_records(&mut reader, &ctx, &tctx, &mut spans, &mut errors);
+        assert!(records.is_empty());
+        assert_eq!(errors, vec![RecordError::Unrecoverable { offset: 0 }]);
+    }
+
     #[test]
     fn test_read_table_deps() {
         let header_strings = vec![
@@ -770,6 +1164,193 @@ mod tests {
         }
     }
 
+    /// Pack (value, bit_count) pairs into bytes, LSB-first like BitReader
+    fn pack_bits(fields: &[(u32, u8)]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut pos = 0usize;
+        for &(value, bits) in fields {
+            for i in 0..bits {
+                if pos / 8 >= out.len() {
+                    out.push(0);
+                }
+                if (value >> i) & 1 == 1 {
+                    out[pos / 8] |= 1 << (pos % 8);
+                }
+                pos += 1;
+            }
+        }
+        out
+    }
+
+    /// Encode `{serialindex: {field: value, ...}}` followed by a 3-bit sentinel
+    ///
+    /// row_flags: [0] = map (kind 3), [1] = leaf (kind 1); 1-bit type index,
+    /// 3-bit key index, 2-bit value index, 1-bit value kind.
+    fn encode_serialindex(fields: &[(u32, u32)]) -> Vec<u8> {
+        let mut bits = vec![(0, 1), (0, 3), (1, 1), (1, 3), (0, 1), (0, 3)];
+        for &(key, value) in fields {
+            bits.extend([(1, 1), (key, 3), (1, 1), (value, 2), (0, 1)]);
+        }
+        bits.extend([(0, 1), (0, 1), (0b101, 3)]);
+        pack_bits(&bits)
+    }
+
+    fn decode_serialindex_fields(fields: &[(u32, u32)]) -> (BTreeMap<String, Value>, u32) {
+        let key_strings: Vec<String> = [
+            "none",
+            "serialindex",
+            "status",
+            "index",
+            "_category",
+            "_scope",
+            "extra",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let value_strings: Vec<String> = ["active", "42", "inv", "root"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let value_kinds = vec![String::new()];
+        let row_flags = vec![3u32, 1u32];
+        let ctx = make_decode_context(&key_strings, &value_strings, &value_kinds, &row_flags);
+        let tctx = make_table_context();
+
+        let data = encode_serialindex(fields);
+        let mut reader = BitReader::new(&data);
+        let value = decode_node(&mut reader, &ctx, &tctx, data.len() * 8).unwrap();
+        let sentinel = reader.read_bits(3).unwrap();
+
+        let Value::Map(mut outer) = value else {
+            panic!("Expected outer map");
+        };
+        let Some(Value::Map(si)) = outer.remove("serialindex") else {
+            panic!("Expected serialindex map");
+        };
+        (si, sentinel)
+    }
+
+    #[test]
+    fn test_serialindex_three_fields() {
+        let (si, sentinel) = decode_serialindex_fields(&[(2, 0), (3, 1), (4, 2)]);
+        assert_eq!(si.len(), 3);
+        assert!(matches!(si.get("index"), Some(Value::Leaf(v)) if v == "42"));
+        assert!(!si.contains_key("_scope"));
+        assert_eq!(sentinel, 0b101);
+    }
+
+    #[test]
+    fn test_serialindex_four_fields() {
+        let (si, sentinel) = decode_serialindex_fields(&[(2, 0), (3, 1), (4, 2), (5, 3)]);
+        assert_eq!(si.len(), 4);
+        assert!(matches!(si.get("_scope"), Some(Value::Leaf(v)) if v == "root"));
+        assert_eq!(sentinel, 0b101);
+    }
+
+    #[test]
+    fn test_serialindex_five_fields() {
+        let (si, sentinel) =
+            decode_serialindex_fields(&[(2, 0), (3, 1), (4, 2), (5, 3), (6, 0)]);
+        assert_eq!(si.len(), 5);
+        assert!(matches!(si.get("extra"), Some(Value::Leaf(v)) if v == "active"));
+        assert!(matches!(si.get("index"), Some(Value::Leaf(v)) if v == "42"));
+        assert_eq!(sentinel, 0b101);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_decoded_map_serializes_stably() {
+        let fields = [(2, 0), (3, 1), (4, 2), (5, 3), (6, 0)];
+        let first = serde_json::to_string(&decode_serialindex_fields(&fields).0).unwrap();
+        let second = serde_json::to_string(&decode_serialindex_fields(&fields).0).unwrap();
+        assert_eq!(first, second);
+
+        // Keys serialize sorted, whatever order the file stores them in
+        let mut reversed = fields;
+        reversed.reverse();
+        let third = serde_json::to_string(&decode_serialindex_fields(&reversed).0).unwrap();
+        assert_eq!(first, third);
+        assert!(first.starts_with("{\"_category\":\"inv\",\"_scope\":\"root\""));
+    }
+
+    /// Decode a single `p` tag (followed by `z`) with one row flag per subtype
+    ///
+    /// row_flags: [0] = null, [1] = leaf, [2] = array, [3] = map; 2-bit type
+    /// index, 2-bit key index, 1-bit value index, 1-bit value kind.
+    fn decode_variant_tag(payload: &[(u32, u8)]) -> Value {
+        let key_strings: Vec<String> = ["none", "k1", "k2"].iter().map(|s| s.to_string()).collect();
+        let value_strings = vec!["v0".to_string(), "v1".to_string()];
+        let value_kinds = vec![String::new()];
+        let row_flags = vec![0u32, 1, 2, 3];
+        let ctx = make_decode_context(&key_strings, &value_strings, &value_kinds, &row_flags);
+        let tctx = make_table_context();
+
+        let mut fields = vec![(u32::from(b'p'), 8)];
+        fields.extend_from_slice(payload);
+        fields.push((u32::from(b'z'), 8));
+        let data = pack_bits(&fields);
+
+        let mut reader = BitReader::new(&data);
+        let mut tags = parse_tags(&mut reader, &ctx, &tctx, data.len() * 8);
+        assert_eq!(tags.len(), 1);
+        match tags.remove(0) {
+            Tag::Variant { variant } => variant,
+            other => panic!("Expected Tag::Variant, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_tags_variant_null() {
+        assert_eq!(decode_variant_tag(&[(0, 2)]), Value::Null);
+    }
+
+    #[test]
+    fn test_parse_tags_variant_leaf() {
+        let value = decode_variant_tag(&[(1, 2), (1, 1), (0, 1)]);
+        assert_eq!(value, Value::Leaf("v1".to_string()));
+    }
+
+    #[test]
+    fn test_parse_tags_variant_array() {
+        let value = decode_variant_tag(&[
+            (2, 2),
+            (1, 1),
+            (1, 2),
+            (0, 1),
+            (0, 1),
+            (1, 1),
+            (1, 2),
+            (1, 1),
+            (0, 1),
+            (0, 1),
+        ]);
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Leaf("v0".to_string()),
+                Value::Leaf("v1".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_tags_variant_map() {
+        // Self key "none" is dropped, leaving the bare field map
+        let value = decode_variant_tag(&[
+            (3, 2),
+            (0, 2),
+            (1, 1),
+            (2, 2),
+            (1, 2),
+            (1, 1),
+            (0, 1),
+            (0, 1),
+        ]);
+        let expected = BTreeMap::from([("k2".to_string(), Value::Leaf("v1".to_string()))]);
+        assert_eq!(value, Value::Map(expected));
+    }
+
     #[test]
     fn test_remap_index_no_remap() {
         let (bits, mapped) = remap_index(None, 5, 8);
@@ -10,9 +10,50 @@ pub mod remap;
 pub mod typecodes;
 
 use crate::document::Document;
-use blob::{extract_header_strings, BlobHeader};
-use decode::{decode_table_data, DecodeInput};
-use typecodes::parse_type_code_table;
+use blob::{
+    extract_header_strings, BlobHeader, DEFAULT_MAX_BLOB_ENTRIES, DEFAULT_MAX_STRING_BYTES,
+};
+use decode::{
+    count_table_records, decode_table_data, DecodeInput, DEFAULT_MAX_NAME_LIST_LEN,
+    DEFAULT_MAX_RESYNC_BYTES,
+};
+use remap::DEFAULT_MAX_ARRAY_COUNT;
+use typecodes::{parse_type_code_table_with_options, DEFAULT_MAX_TYPE_CODES};
+
+/// Resource limits applied while decoding untrusted NCS data
+///
+/// Exceeding a limit rejects the file, except `max_name_list_len`, which
+/// stops reading the offending list, and `max_resync_bytes`, which ends the
+/// table being read. Raise them for unusually large files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Maximum `entry_count` declared in the blob header
+    pub max_blob_entries: u32,
+    /// Maximum size of the header string block in bytes
+    pub max_string_bytes: u32,
+    /// Maximum number of type codes in the body header
+    pub max_type_codes: u8,
+    /// Maximum entries in a packed name list (tags d/e/f)
+    pub max_name_list_len: usize,
+    /// Maximum declared count of a table's remap arrays
+    pub max_array_count: u32,
+    /// Maximum bytes scanned for the next record after a record with an
+    /// impossible length prefix; 0 ends the table at the first one
+    pub max_resync_bytes: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_blob_entries: DEFAULT_MAX_BLOB_ENTRIES,
+            max_string_bytes: DEFAULT_MAX_STRING_BYTES,
+            max_type_codes: DEFAULT_MAX_TYPE_CODES,
+            max_name_list_len: DEFAULT_MAX_NAME_LIST_LEN,
+            max_array_count: DEFAULT_MAX_ARRAY_COUNT,
+            max_resync_bytes: DEFAULT_MAX_RESYNC_BYTES,
+        }
+    }
+}
 
 /// Parse decompressed NCS data into a Document
 ///
@@ -22,33 +63,127 @@ use typecodes::parse_type_code_table;
 /// 3. Parses the TypeCodeTable (type codes, bit matrix, 3 string blocks)
 /// 4. Runs the decode loop to produce tables with records
 pub fn parse(data: &[u8]) -> Option<Document> {
-    let blob = BlobHeader::parse(data)?;
-    let header_strings = extract_header_strings(data, &blob);
+    parse_with_options(data, &ParseOptions::default())
+}
 
-    if header_strings.is_empty() {
-        return None;
+/// Parse decompressed NCS data with custom resource limits
+pub fn parse_with_options(data: &[u8], options: &ParseOptions) -> Option<Document> {
+    let (strings, binary_offset) = StringTable::from_data_with_options(data, options)?;
+    parse_document_with_options(data, &strings, binary_offset, options)
+}
+
+/// The string blocks and row flags the decode loop indexes into
+///
+/// [`StringTable::from_data`] reads them from the file itself; build one by
+/// hand to decode a binary section against a different set of strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringTable {
+    /// Table and dep names; index 0 ends the table list
+    pub header_strings: Vec<String>,
+    pub value_strings: Vec<String>,
+    /// Count the value index width is sized for (may exceed `value_strings.len()`)
+    pub value_strings_declared: u32,
+    pub value_kinds: Vec<String>,
+    pub value_kinds_declared: u32,
+    pub key_strings: Vec<String>,
+    pub key_strings_declared: u32,
+    /// Node flags per type index; the type index width is sized for this length
+    pub row_flags: Vec<u32>,
+}
+
+impl StringTable {
+    /// Read the header strings and type code table stored in `data`
+    ///
+    /// Returns the table and the offset of the binary section that follows.
+    pub fn from_data(data: &[u8]) -> Option<(Self, usize)> {
+        Self::from_data_with_options(data, &ParseOptions::default())
     }
 
-    let body_offset = blob.body_offset();
-    if body_offset >= data.len() {
-        return None;
+    /// [`StringTable::from_data`] with custom resource limits
+    pub fn from_data_with_options(data: &[u8], options: &ParseOptions) -> Option<(Self, usize)> {
+        let blob = BlobHeader::parse_with_options(data, options)?;
+        let header_strings = extract_header_strings(data, &blob);
+
+        if header_strings.is_empty() {
+            return None;
+        }
+
+        let body_offset = blob.body_offset();
+        if body_offset >= data.len() {
+            return None;
+        }
+
+        let tct = parse_type_code_table_with_options(&data[body_offset..], options)?;
+        let strings = Self {
+            header_strings,
+            value_strings: tct.value_strings,
+            value_strings_declared: tct.value_strings_declared_count,
+            value_kinds: tct.value_kinds,
+            value_kinds_declared: tct.value_kinds_declared_count,
+            key_strings: tct.key_strings,
+            key_strings_declared: tct.key_strings_declared_count,
+            row_flags: tct.header.row_flags,
+        };
+        Some((strings, body_offset + tct.data_offset))
     }
+}
 
-    let body = &data[body_offset..];
-    let tct = parse_type_code_table(body)?;
+/// Decode the binary section at `binary_offset` against a given string table
+///
+/// `strings` is the only source of names: every index read from the binary
+/// section resolves in it, and every index width comes from its lengths and
+/// declared counts. Nothing is read from the header or type code table in
+/// `data`. An out-of-range key index decodes as `<key:N>` and an out-of-range
+/// value index as an empty string; neither falls back to another block.
+pub fn parse_document(
+    data: &[u8],
+    strings: &StringTable,
+    binary_offset: usize,
+) -> Option<Document> {
+    parse_document_with_options(data, strings, binary_offset, &ParseOptions::default())
+}
+
+/// [`parse_document`] with custom resource limits
+pub fn parse_document_with_options(
+    data: &[u8],
+    strings: &StringTable,
+    binary_offset: usize,
+    options: &ParseOptions,
+) -> Option<Document> {
+    decode_table_data(&decode_input(data, strings, binary_offset, options))
+}
+
+/// Count the records in decompressed NCS data without decoding them
+///
+/// Walks the table headers and each record's length prefix, skipping record
+/// bodies, so the count matches the records [`parse`] would produce,
+/// including after resyncing past a bad prefix. Returns None where [`parse`]
+/// would fail to find the binary section.
+pub fn count_records(data: &[u8]) -> Option<usize> {
+    let options = ParseOptions::default();
+    let (strings, binary_offset) = StringTable::from_data_with_options(data, &options)?;
+    count_table_records(&decode_input(data, &strings, binary_offset, &options))
+}
 
-    decode_table_data(&DecodeInput {
+fn decode_input<'a>(
+    data: &'a [u8],
+    strings: &'a StringTable,
+    binary_offset: usize,
+    options: &'a ParseOptions,
+) -> DecodeInput<'a> {
+    DecodeInput {
         data,
-        header_strings: &header_strings,
-        value_strings: &tct.value_strings,
-        value_strings_declared: tct.value_strings_declared_count,
-        value_kinds: &tct.value_kinds,
-        value_kinds_declared: tct.value_kinds_declared_count,
-        key_strings: &tct.key_strings,
-        key_strings_declared: tct.key_strings_declared_count,
-        row_flags: &tct.header.row_flags,
-        binary_offset: body_offset + tct.data_offset,
-    })
+        header_strings: &strings.header_strings,
+        value_strings: &strings.value_strings,
+        value_strings_declared: strings.value_strings_declared,
+        value_kinds: &strings.value_kinds,
+        value_kinds_declared: strings.value_kinds_declared,
+        key_strings: &strings.key_strings,
+        key_strings_declared: strings.key_strings_declared,
+        row_flags: &strings.row_flags,
+        binary_offset,
+        options,
+    }
 }
 
 /// Extract dependency info from blob header strings
@@ -70,6 +205,7 @@ pub fn extract_deps(data: &[u8]) -> Option<(String, Vec<String>)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::document::RecordError;
 
     #[test]
     fn test_parse_too_short() {
@@ -77,6 +213,120 @@ mod tests {
         assert!(parse(&[0; 10]).is_none());
     }
 
+    #[test]
+    fn test_parse_document_uses_given_strings() {
+        // Table id 1 (2 bits), no deps, two empty remap arrays, an empty
+        // record list, then table id 0
+        let mut data = vec![0u8; 15];
+        data[0] = 0x01;
+        let mut strings = StringTable {
+            header_strings: vec!["none".into(), "alpha".into(), "beta".into()],
+            row_flags: vec![0],
+            ..StringTable::default()
+        };
+
+        let doc = parse_document(&data, &strings, 0).unwrap();
+        assert_eq!(doc.table_names(), vec!["alpha"]);
+        assert!(doc.tables["alpha"].records.is_empty());
+
+        strings.header_strings.swap(1, 2);
+        let doc = parse_document(&data, &strings, 0).unwrap();
+        assert_eq!(doc.table_names(), vec!["beta"]);
+    }
+
+    #[test]
+    fn test_parse_document_record_spans() {
+        // Three bytes before the binary section, then table id 1 with no
+        // deps and empty remaps as above, two records of 5 and 6 bytes, the
+        // record list terminator and table id 0
+        let mut data = vec![0xFF; 3];
+        let binary_offset = data.len();
+        data.extend_from_slice(&[0x01, 0, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[5, 0, 0, 0, b'z']);
+        data.extend_from_slice(&[6, 0, 0, 0, b'z', 0]);
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        let strings = StringTable {
+            header_strings: vec!["none".into(), "alpha".into()],
+            row_flags: vec![0],
+            ..StringTable::default()
+        };
+
+        let doc = parse_document(&data, &strings, binary_offset).unwrap();
+        assert_eq!(doc.tables["alpha"].records.len(), 2);
+        assert_eq!(doc.record_spans, vec![12..17, 17..23]);
+
+        for pair in doc.record_spans.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        for span in &doc.record_spans {
+            assert!(span.start >= binary_offset && span.end <= data.len());
+            let declared = u32::from_le_bytes(data[span.start..span.start + 4].try_into().unwrap());
+            assert_eq!(declared as usize, span.len());
+        }
+    }
+
+    #[test]
+    fn test_parse_document_reports_resynced_record() {
+        // As above, but a corrupt record sits between the two good ones
+        let mut data = vec![0xFF; 3];
+        let binary_offset = data.len();
+        data.extend_from_slice(&[0x01, 0, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[5, 0, 0, 0, b'z']);
+        data.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0x7F, 0xEE]);
+        data.extend_from_slice(&[6, 0, 0, 0, b'z', 0]);
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        let strings = StringTable {
+            header_strings: vec!["none".into(), "alpha".into()],
+            row_flags: vec![0],
+            ..StringTable::default()
+        };
+
+        let doc = parse_document(&data, &strings, binary_offset).unwrap();
+        assert_eq!(doc.tables["alpha"].records.len(), 2);
+        assert_eq!(doc.record_spans, vec![12..17, 22..28]);
+        assert_eq!(
+            doc.parse_errors,
+            vec![RecordError::Resynced {
+                offset: 17,
+                skipped: 5
+            }]
+        );
+
+        let options = ParseOptions::default();
+        let input = decode_input(&data, &strings, binary_offset, &options);
+        assert_eq!(count_table_records(&input), Some(2));
+    }
+
+    #[test]
+    fn test_count_table_records_matches_parse() {
+        let mut data = vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&[5, 0, 0, 0, b'z']);
+        data.extend_from_slice(&[6, 0, 0, 0, b'z', 0]);
+        data.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        let strings = StringTable {
+            header_strings: vec!["none".into(), "alpha".into()],
+            row_flags: vec![0],
+            ..StringTable::default()
+        };
+        let options = ParseOptions::default();
+        let input = decode_input(&data, &strings, 0, &options);
+
+        let doc = decode_table_data(&input).unwrap();
+        assert_eq!(count_table_records(&input), Some(doc.record_spans.len()));
+        assert_eq!(count_table_records(&input), Some(2));
+    }
+
+    #[test]
+    fn test_count_records_without_binary_section() {
+        assert!(count_records(&[0; 10]).is_none());
+    }
+
+    #[test]
+    fn test_string_table_from_data_too_short() {
+        assert!(StringTable::from_data(&[]).is_none());
+        assert!(StringTable::from_data(&[0; 10]).is_none());
+    }
+
     #[test]
     fn test_extract_deps_too_short() {
         assert!(extract_deps(&[]).is_none());
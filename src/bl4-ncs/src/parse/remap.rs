@@ -5,6 +5,9 @@
 
 use crate::bit_reader::{bit_width, BitReader};
 
+/// Default cap on a remap array's declared count before giving up
+pub const DEFAULT_MAX_ARRAY_COUNT: u32 = 1_000_000;
+
 /// Fixed-width integer array with 24-bit count + 8-bit width header
 #[derive(Debug, Clone, Default)]
 pub struct FixedWidthIntArray {
@@ -21,6 +24,16 @@ impl FixedWidthIntArray {
 
     /// Read from bit stream: 24-bit count, 8-bit width, then count values
     pub fn read(reader: &mut BitReader) -> Option<Self> {
+        Self::read_with_limit(reader, DEFAULT_MAX_ARRAY_COUNT)
+    }
+
+    /// Like [`FixedWidthIntArray::read`], rejecting arrays with more than
+    /// `max_count` values.
+    ///
+    /// The declared size is checked against the bits left in the reader
+    /// before anything is allocated, so a truncated or corrupt count fails
+    /// immediately instead of reserving memory for data that isn't there.
+    pub fn read_with_limit(reader: &mut BitReader, max_count: u32) -> Option<Self> {
         let count = reader.read_bits(24)?;
         let value_bit_width = reader.read_bits(8)? as u8;
         let index_bit_width = if count > 0 { bit_width(count) } else { 0 };
@@ -34,9 +47,13 @@ impl FixedWidthIntArray {
             });
         }
 
+        let total_bits = count as usize * value_bit_width as usize;
+        if !reader.has_bits(total_bits) {
+            return None;
+        }
+
         if value_bit_width > 32 {
-            let bits_to_skip = count as usize * value_bit_width as usize;
-            reader.skip_bits(bits_to_skip);
+            reader.skip_bits(total_bits);
             return Some(Self {
                 count,
                 value_bit_width,
@@ -45,7 +62,7 @@ impl FixedWidthIntArray {
             });
         }
 
-        if count > 1_000_000 {
+        if count > max_count {
             return None;
         }
 
@@ -62,13 +79,24 @@ impl FixedWidthIntArray {
         })
     }
 
+    /// Check whether `idx` refers to a decoded value
+    pub fn is_valid_index(&self, idx: usize) -> bool {
+        idx < self.values.len()
+    }
+
+    /// Bounds-checked value lookup
+    pub fn value(&self, idx: usize) -> Option<u32> {
+        self.values.get(idx).copied()
+    }
+
+    /// Iterate over decoded values
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        self.values.iter().copied()
+    }
+
     /// Map raw index through remap array to get remapped value
     pub fn remap(&self, raw_index: u32) -> Option<u32> {
-        if raw_index < self.count {
-            Some(self.values[raw_index as usize])
-        } else {
-            None
-        }
+        self.value(raw_index as usize)
     }
 }
 
@@ -109,6 +137,62 @@ mod tests {
         assert_eq!(arr.remap(3), None);
     }
 
+    #[test]
+    fn test_value_accessors() {
+        let arr = FixedWidthIntArray {
+            count: 2,
+            value_bit_width: 8,
+            index_bit_width: 1,
+            values: vec![7, 9],
+        };
+
+        assert!(arr.is_valid_index(1));
+        assert!(!arr.is_valid_index(2));
+        assert_eq!(arr.value(0), Some(7));
+        assert_eq!(arr.value(2), None);
+        assert_eq!(arr.value(usize::MAX), None);
+        assert_eq!(arr.iter().collect::<Vec<_>>(), vec![7, 9]);
+    }
+
+    #[test]
+    fn test_remap_skipped_values() {
+        // Width > 32 skips values, so count is set but nothing was decoded
+        let arr = FixedWidthIntArray {
+            count: 4,
+            value_bit_width: 40,
+            index_bit_width: 2,
+            values: Vec::new(),
+        };
+
+        assert_eq!(arr.remap(0), None);
+        assert!(!arr.is_valid_index(0));
+    }
+
+    #[test]
+    fn test_count_exceeds_remaining_data() {
+        // count=0xFFFFFF, width=32, but only 4 bytes of values follow
+        let data = [0xFF, 0xFF, 0xFF, 0x20, 0x01, 0x02, 0x03, 0x04];
+        let mut reader = BitReader::new(&data);
+        assert!(FixedWidthIntArray::read(&mut reader).is_none());
+
+        // Same for skipped widths: don't seek past the end
+        let data = [0xFF, 0xFF, 0xFF, 0x28, 0x00];
+        let mut reader = BitReader::new(&data);
+        assert!(FixedWidthIntArray::read(&mut reader).is_none());
+    }
+
+    #[test]
+    fn test_read_with_limit() {
+        let data = [0x03, 0x00, 0x00, 0x08, 0x0A, 0x14, 0x1E];
+
+        let mut reader = BitReader::new(&data);
+        assert!(FixedWidthIntArray::read_with_limit(&mut reader, 2).is_none());
+
+        let mut reader = BitReader::new(&data);
+        let arr = FixedWidthIntArray::read_with_limit(&mut reader, 3).unwrap();
+        assert_eq!(arr.values, vec![10, 20, 30]);
+    }
+
     #[test]
     fn test_empty_array() {
         // count=0
@@ -5,6 +5,10 @@
 //! section of decompressed NCS data.
 
 use super::blob::parse_null_terminated_strings;
+use super::ParseOptions;
+
+/// Default cap on the number of type codes in the body header
+pub const DEFAULT_MAX_TYPE_CODES: u8 = 64;
 
 /// Map a type code character to its global bit position.
 ///
@@ -62,6 +66,14 @@ pub struct TypeCodeTable {
 
 /// Parse the TypeCodeTable from the body section
 pub fn parse_type_code_table(body: &[u8]) -> Option<TypeCodeTable> {
+    parse_type_code_table_with_options(body, &ParseOptions::default())
+}
+
+/// Parse the TypeCodeTable, rejecting more than `options.max_type_codes` codes
+pub fn parse_type_code_table_with_options(
+    body: &[u8],
+    options: &ParseOptions,
+) -> Option<TypeCodeTable> {
     if body.len() < 3 {
         return None;
     }
@@ -70,7 +82,7 @@ pub fn parse_type_code_table(body: &[u8]) -> Option<TypeCodeTable> {
     let type_index_count = u16::from_le_bytes([body[1], body[2]]);
     let mut pos = 3;
 
-    if type_code_count == 0 || type_code_count > 64 {
+    if type_code_count == 0 || type_code_count > options.max_type_codes {
         return None;
     }
 
@@ -0,0 +1,266 @@
+//! Census of the NCS files in a directory
+//!
+//! [`scan_directory`] reads every `.bin` file under a directory in
+//! parallel and counts how many parse, by type and format code, along with
+//! the files that didn't.
+
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::content::{Content, QuickStats};
+use crate::data::decompress;
+use crate::find_ncs_start;
+use crate::format_code::FormatCode;
+
+/// Options for [`scan_directory`]
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Only tally parsed files whose type name contains this string
+    ///
+    /// Filtered-out files still count toward `total_files` and
+    /// `parsed_files`.
+    pub filter_type: Option<String>,
+}
+
+/// Result of scanning a directory
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ScanResult {
+    pub total_files: usize,
+    pub parsed_files: usize,
+    /// Paths of the tallied files, by type name
+    pub types: HashMap<String, Vec<String>>,
+    /// Tallied files per format code
+    pub formats: HashMap<FormatCode, usize>,
+    /// Files that could not be read from disk
+    pub read_failures: usize,
+    /// Files with an NCS header that failed to decompress
+    pub decompress_failures: usize,
+    /// Files that were readable but did not parse as NCS content
+    pub parse_failures: usize,
+    /// Paths of every file counted in the failure totals
+    pub failed_files: Vec<String>,
+    /// Strings found across parsed files
+    pub strings: usize,
+    /// Records across parsed files, counted from length prefixes without decoding
+    pub estimated_records: usize,
+    /// Parsed files whose binary section couldn't be located to count records
+    pub uncounted_files: usize,
+    /// Per-file stats for the tallied files, in path order
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub files: Vec<ScannedFile>,
+}
+
+impl ScanResult {
+    /// Fraction of files that parsed, or None for an empty scan
+    pub fn parse_rate(&self) -> Option<f64> {
+        (self.total_files > 0).then(|| self.parsed_files as f64 / self.total_files as f64)
+    }
+}
+
+/// A file tallied by [`scan_directory`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedFile {
+    pub path: PathBuf,
+    pub type_name: String,
+    pub stats: QuickStats,
+}
+
+/// Outcome of scanning a single `.bin` file
+enum Outcome {
+    Parsed {
+        type_name: String,
+        stats: QuickStats,
+    },
+    ReadFailed,
+    DecompressFailed,
+    ParseFailed,
+}
+
+fn scan_file(path: &Path) -> Outcome {
+    let Ok(data) = std::fs::read(path) else {
+        return Outcome::ReadFailed;
+    };
+
+    let decompressed = if find_ncs_start(&data).is_some() {
+        match decompress(&data) {
+            Ok(d) => d,
+            Err(_) => return Outcome::DecompressFailed,
+        }
+    } else {
+        data
+    };
+
+    match Content::parse(&decompressed) {
+        Some(content) => Outcome::Parsed {
+            type_name: content.type_name().to_string(),
+            stats: content.quick_stats(&decompressed),
+        },
+        None => Outcome::ParseFailed,
+    }
+}
+
+/// Read every `.bin` file under `dir` and count what parses
+///
+/// Files are parsed in parallel; the result does not depend on thread
+/// scheduling. Files that can't be read count as read failures rather
+/// than failing the scan, and an unreadable directory gives an empty
+/// result.
+pub fn scan_directory(dir: &Path, options: &ScanOptions) -> ScanResult {
+    let paths: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|e| e == "bin"))
+        .collect();
+
+    let outcomes: Vec<Outcome> = paths.par_iter().map(|p| scan_file(p)).collect();
+
+    let mut result = ScanResult {
+        total_files: paths.len(),
+        ..ScanResult::default()
+    };
+
+    for (path, outcome) in paths.into_iter().zip(outcomes) {
+        let path_str = path.to_string_lossy().to_string();
+        let (type_name, stats) = match outcome {
+            Outcome::Parsed { type_name, stats } => (type_name, stats),
+            Outcome::ReadFailed => {
+                result.read_failures += 1;
+                result.failed_files.push(path_str);
+                continue;
+            }
+            Outcome::DecompressFailed => {
+                result.decompress_failures += 1;
+                result.failed_files.push(path_str);
+                continue;
+            }
+            Outcome::ParseFailed => {
+                result.parse_failures += 1;
+                result.failed_files.push(path_str);
+                continue;
+            }
+        };
+
+        result.parsed_files += 1;
+        if let Some(filter) = &options.filter_type {
+            if !type_name.contains(filter.as_str()) {
+                continue;
+            }
+        }
+
+        result.strings += stats.string_count;
+        match stats.estimated_record_count {
+            Some(n) => result.estimated_records += n,
+            None => result.uncounted_files += 1,
+        }
+        result
+            .types
+            .entry(type_name.clone())
+            .or_default()
+            .push(path_str);
+        *result.formats.entry(stats.format.clone()).or_insert(0) += 1;
+        result.files.push(ScannedFile {
+            path,
+            type_name,
+            stats,
+        });
+    }
+
+    result.failed_files.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn make_test_ncs(type_name: &str, format_code: &str) -> Vec<u8> {
+        let mut data = vec![0u8; 5];
+        data.extend_from_slice(&[0x01, 0x8f, 0x0e, 0x00]);
+        data.extend_from_slice(type_name.as_bytes());
+        data.push(0);
+        data.extend_from_slice(&[0x03, 0x05, 0x00]);
+        data.extend_from_slice(format_code.as_bytes());
+        data.extend_from_slice(&[0x1d, 0x06, 0x01]);
+        data.extend_from_slice(b"test_entry\0");
+        data.extend_from_slice(b"12.000000\0none\0basegame\0");
+        data
+    }
+
+    fn fixture_dir() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(
+            dir.path().join("itempoollist0.bin"),
+            make_test_ncs("itempoollist", "abjx"),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sub/achievement0.bin"),
+            make_test_ncs("achievement", "abjx"),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sub/aim_assist0.bin"),
+            make_test_ncs("aim_assist", "abij"),
+        )
+        .unwrap();
+        fs::write(dir.path().join("junk.bin"), b"not ncs content").unwrap();
+        fs::write(dir.path().join("ignored.txt"), b"skip me").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scan_directory_counts() {
+        let dir = fixture_dir();
+        let result = scan_directory(dir.path(), &ScanOptions::default());
+
+        assert_eq!(result.total_files, 4);
+        assert_eq!(result.parsed_files, 3);
+        assert_eq!(result.parse_rate(), Some(0.75));
+        assert_eq!(result.parse_failures, 1);
+        assert_eq!(result.read_failures + result.decompress_failures, 0);
+        assert_eq!(result.failed_files.len(), 1);
+        assert!(result.failed_files[0].ends_with("junk.bin"));
+
+        assert_eq!(result.types.len(), 3);
+        assert_eq!(result.formats[&FormatCode::Abjx], 2);
+        assert_eq!(result.formats[&FormatCode::Abij], 1);
+        assert_eq!(result.uncounted_files, 3);
+
+        let names: Vec<&str> = result.files.iter().map(|f| f.type_name.as_str()).collect();
+        assert_eq!(names, ["itempoollist", "achievement", "aim_assist"]);
+    }
+
+    #[test]
+    fn test_scan_directory_filter() {
+        let dir = fixture_dir();
+        let options = ScanOptions {
+            filter_type: Some("a".to_string()),
+        };
+        let result = scan_directory(dir.path(), &options);
+
+        // Filtering narrows the tallies, not the parse counts
+        assert_eq!(result.parsed_files, 3);
+        assert_eq!(result.types.len(), 2);
+        assert!(!result.types.contains_key("itempoollist"));
+        assert_eq!(result.files.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_directory_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = scan_directory(dir.path(), &ScanOptions::default());
+        assert_eq!(result.total_files, 0);
+        assert_eq!(result.parse_rate(), None);
+        assert!(result.types.is_empty());
+        assert!(result.failed_files.is_empty());
+    }
+}
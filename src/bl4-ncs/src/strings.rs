@@ -0,0 +1,83 @@
+//! Printable-ASCII string extraction from raw NCS data
+
+/// Printable ASCII: space through `~` (0x20..=0x7E)
+pub fn is_printable_ascii(b: u8) -> bool {
+    b.is_ascii_graphic() || b == b' '
+}
+
+/// Extract null-terminated printable ASCII strings with their byte offsets
+///
+/// A run is kept only when a NUL ends it and it has at least `min_len`
+/// bytes; any other non-printable byte discards the current run, and a
+/// trailing run without a terminator is dropped.
+pub fn extract_ascii_strings(data: &[u8], min_len: usize) -> Vec<(usize, String)> {
+    let mut strings = Vec::new();
+    let mut start = 0;
+
+    for (i, &b) in data.iter().enumerate() {
+        if is_printable_ascii(b) {
+            continue;
+        }
+        if b == 0 && i > start && i - start >= min_len {
+            // Printable ASCII is always valid UTF-8
+            let s = String::from_utf8_lossy(&data[start..i]).into_owned();
+            strings.push((start, s));
+        }
+        start = i + 1;
+    }
+
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_ascii_strings_offsets() {
+        let data = b"hello\0world\0test\0";
+        let strings = extract_ascii_strings(data, 1);
+        assert_eq!(
+            strings,
+            vec![
+                (0, "hello".to_string()),
+                (6, "world".to_string()),
+                (12, "test".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_ascii_strings_embedded_nulls() {
+        // Consecutive NULs produce no empty strings
+        let data = b"\0\0ab\0\0\0cd\0";
+        let strings = extract_ascii_strings(data, 1);
+        assert_eq!(strings, vec![(2, "ab".to_string()), (7, "cd".to_string())]);
+    }
+
+    #[test]
+    fn test_extract_ascii_strings_non_ascii_interrupts() {
+        // 0xFF and 0x7F break the run; only the part after them survives
+        let data = b"bad\xFFgood\0del\x7Fok\0caf\xC3\xA9\0";
+        let strings = extract_ascii_strings(data, 1);
+        assert_eq!(
+            strings,
+            vec![(4, "good".to_string()), (13, "ok".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_extract_ascii_strings_min_len_and_trailing() {
+        let data = b"a\0abc\0unterminated";
+        assert_eq!(extract_ascii_strings(data, 3), vec![(2, "abc".to_string())]);
+        assert_eq!(extract_ascii_strings(data, 1).len(), 2);
+    }
+
+    #[test]
+    fn test_is_printable_ascii() {
+        assert!(is_printable_ascii(b' '));
+        assert!(is_printable_ascii(b'~'));
+        assert!(!is_printable_ascii(0x1F));
+        assert!(!is_printable_ascii(0x7F));
+    }
+}
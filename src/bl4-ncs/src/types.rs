@@ -1,7 +1,12 @@
 //! Core types for NCS parsing support
 
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
 /// Represents an unpacked value from a packed NCS string
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum UnpackedValue {
     Integer(i64),
     Float(f64),
@@ -16,3 +21,43 @@ pub struct UnpackedString {
     pub values: Vec<UnpackedValue>,
     pub was_packed: bool,
 }
+
+/// Packed strings found in a string table, queryable by original string
+#[derive(Debug, Clone, Default)]
+pub struct PackedStrings {
+    pub strings: Vec<UnpackedString>,
+}
+
+impl PackedStrings {
+    /// Unpack every string in `strings`, keeping only those that were packed
+    pub fn from_strings(strings: &[String]) -> Self {
+        Self {
+            strings: crate::unpack::find_packed_strings(strings),
+        }
+    }
+
+    /// Get the unpacked values for a packed string
+    pub fn packed_values_for(&self, original: &str) -> Option<&[UnpackedValue]> {
+        self.strings
+            .iter()
+            .find(|u| u.original == original)
+            .map(|u| u.values.as_slice())
+    }
+
+    /// Iterate over (original, values) pairs in string-table order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[UnpackedValue])> {
+        self.strings
+            .iter()
+            .map(|u| (u.original.as_str(), u.values.as_slice()))
+    }
+
+    /// Number of packed strings
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether no packed strings were found
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
@@ -346,36 +346,19 @@ pub fn get_bank_impl(state: &AppState) -> Result<BankInfo, String> {
     let loaded = current.as_ref().ok_or("No save file loaded")?;
     let save = &loaded.save;
 
-    let mut items = Vec::new();
-
     // Bank items in profile.sav
-    for slot in 0..400 {
-        let serial_path = format!(
-            "domains.local.shared.inventory.items.bank.slot_{}.serial",
-            slot
-        );
-        let flags_path = format!(
-            "domains.local.shared.inventory.items.bank.slot_{}.state_flags",
-            slot
-        );
-
-        if let Ok(serial_val) = save.get(&serial_path) {
-            if let Some(serial) = serial_val.as_str() {
-                if serial.is_empty() {
-                    continue;
-                }
-                let state_flags = save
-                    .get(&flags_path)
-                    .ok()
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0) as u32;
-
-                let flags = bl4::StateFlags::from_raw(state_flags);
-                let item = decode_inventory_item(slot, serial, state_flags, &flags);
-                items.push(item);
-            }
-        }
-    }
+    let items: Vec<InventoryItem> = save
+        .bank_items()
+        .map(|bank_item| {
+            let state_flags = bank_item.state_flags.to_raw();
+            decode_inventory_item(
+                bank_item.slot as u32,
+                &bank_item.serial,
+                state_flags,
+                &bank_item.state_flags,
+            )
+        })
+        .collect();
 
     let count = items.len() as u32;
     // TODO: Check SDU level from character save
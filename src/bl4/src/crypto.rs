@@ -15,6 +15,9 @@ const BASE_KEY: [u8; 32] = [
     0x27, 0x25, 0x64, 0x2E, 0xD5, 0x49, 0x06, 0x29, 0x05, 0x78, 0xBD, 0x60, 0xBA, 0x4A, 0xA7, 0x87,
 ];
 
+/// Size of the footer appended after the zlib stream (adler32 + uncompressed length)
+pub const FOOTER_SIZE: usize = 8;
+
 /// Errors that can occur during encryption/decryption
 #[derive(Debug, thiserror::Error)]
 pub enum CryptoError {
@@ -91,6 +94,21 @@ pub fn derive_key(steam_id: &str) -> Result<[u8; 32], CryptoError> {
 /// - Input: AES-256-ECB encrypted, PKCS7 padded
 /// - After decryption: zlib compressed YAML data
 pub fn decrypt_sav(encrypted_data: &[u8], steam_id: &str) -> Result<Vec<u8>, CryptoError> {
+    let unpadded = decrypt_sav_raw(encrypted_data, steam_id)?;
+
+    // Decompress zlib data
+    let mut decoder = ZlibDecoder::new(&unpadded[..]);
+    let mut yaml_data = Vec::new();
+    decoder.read_to_end(&mut yaml_data)?;
+
+    Ok(yaml_data)
+}
+
+/// Decrypt a .sav file without decompressing it
+///
+/// Returns the zlib stream followed by the 8-byte footer, suitable for
+/// [`verify_checksum`].
+pub fn decrypt_sav_raw(encrypted_data: &[u8], steam_id: &str) -> Result<Vec<u8>, CryptoError> {
     // Validate input size (must be multiple of 16 for AES block cipher)
     if !encrypted_data.len().is_multiple_of(16) {
         return Err(CryptoError::InvalidSize(encrypted_data.len()));
@@ -110,14 +128,38 @@ pub fn decrypt_sav(encrypted_data: &[u8], steam_id: &str) -> Result<Vec<u8>, Cry
 
     // Try to remove PKCS7 padding, but fall back to using padded data
     // (Python code does this - some saves may not use standard padding)
-    let unpadded = pkcs7_unpad(&decrypted).unwrap_or_else(|_| decrypted.clone());
+    Ok(pkcs7_unpad(&decrypted).unwrap_or(decrypted))
+}
+
+/// Compute the save checksum for a YAML payload
+///
+/// BL4 stores an Adler-32 of the *uncompressed* YAML bytes in the footer
+/// that follows the zlib stream.
+pub fn compute_checksum(yaml_data: &[u8]) -> u32 {
+    adler::adler32_slice(yaml_data)
+}
+
+/// Verify a decrypted (still compressed) save blob against its footer
+///
+/// The blob is `zlib stream || adler32(yaml) LE || yaml.len() LE`. The zlib
+/// stream is decompressed and both the checksum and length must match.
+pub fn verify_checksum(blob: &[u8]) -> bool {
+    if blob.len() < FOOTER_SIZE {
+        return false;
+    }
+    let (stream, footer) = blob.split_at(blob.len() - FOOTER_SIZE);
+    let expected_checksum = u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]);
+    let expected_len = u32::from_le_bytes([footer[4], footer[5], footer[6], footer[7]]);
 
-    // Decompress zlib data
-    let mut decoder = ZlibDecoder::new(&unpadded[..]);
     let mut yaml_data = Vec::new();
-    decoder.read_to_end(&mut yaml_data)?;
+    if ZlibDecoder::new(stream)
+        .read_to_end(&mut yaml_data)
+        .is_err()
+    {
+        return false;
+    }
 
-    Ok(yaml_data)
+    yaml_data.len() == expected_len as usize && compute_checksum(&yaml_data) == expected_checksum
 }
 
 /// Encrypt YAML bytes to a .sav file
@@ -133,7 +175,7 @@ pub fn encrypt_sav(yaml_data: &[u8], steam_id: &str) -> Result<Vec<u8>, CryptoEr
     let mut compressed = encoder.finish()?;
 
     // Append footer: adler32 checksum (4 bytes) + uncompressed length (4 bytes)
-    let adler32 = adler::adler32_slice(yaml_data);
+    let adler32 = compute_checksum(yaml_data);
     let uncompressed_len = yaml_data.len() as u32;
 
     compressed.extend_from_slice(&adler32.to_le_bytes());
@@ -170,6 +212,27 @@ mod tests {
         assert_eq!(key[8..], BASE_KEY[8..]);
     }
 
+    #[test]
+    fn test_compute_checksum() {
+        // Adler-32 reference vector
+        assert_eq!(compute_checksum(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(compute_checksum(b""), 1);
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let steam_id = "76561197960521364";
+        let encrypted = encrypt_sav(b"state:\n  cash: 1\n", steam_id).unwrap();
+        let mut blob = decrypt_sav_raw(&encrypted, steam_id).unwrap();
+        assert!(verify_checksum(&blob));
+
+        let last = blob.len() - 1;
+        blob[last] ^= 0xFF;
+        assert!(!verify_checksum(&blob));
+
+        assert!(!verify_checksum(&[0u8; 4]));
+    }
+
     #[test]
     fn test_roundtrip() {
         let steam_id = "76561197960521364";
@@ -35,11 +35,34 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! `use bl4::prelude::*;` brings in the commonly used save, serial and
+//! reference items; see [`prelude`] for the list and its stability promise.
+//!
+//! ## Features
+//!
+//! - `fs` (default): the `backup` module, which reads and writes files next
+//!   to the save.
+//! - `wasm`: `wasm-bindgen` wrappers in `bl4::wasm`.
+//! - `zstd`: read and write zstd-compressed save YAML
+//!   (`SaveFile::from_reader`, `to_writer_compressed`). gzip needs no feature.
+//! - `sqlite`: SQLite parts databases (`PartsDatabase::from_sqlite`,
+//!   `write_sqlite`, `SqlitePartsDatabase`) via bundled `rusqlite`.
+//!
+//! With `default-features = false` the crate does no filesystem, thread, or
+//! clock access and builds for `wasm32-unknown-unknown`. Everything else stays
+//! available and works on bytes and strings only: [`crypto`] (`decrypt_sav`,
+//! `encrypt_sav`), [`save`] (`SaveFile::from_yaml`, `to_yaml`, `get`, `set`,
+//! `diff`, [`ChangeSet`], [`StateFlags`]), [`serial`], [`parts`],
+//! [`manifest`] and [`reference`]. `SaveFile::from_reader` and `to_writer`
+//! take any `std::io` reader or writer, so an in-memory buffer works there too.
 
+#[cfg(feature = "fs")]
 pub mod backup;
 pub mod crypto;
 pub mod manifest;
 pub mod parts;
+pub mod prelude;
 pub mod reference;
 pub mod save;
 pub mod serial;
@@ -48,10 +71,11 @@ pub mod serial;
 pub mod wasm;
 
 // Re-export commonly used items
+#[cfg(feature = "fs")]
 #[doc(inline)]
 pub use backup::{smart_backup, update_after_edit, BackupError};
 #[doc(inline)]
-pub use crypto::{decrypt_sav, derive_key, encrypt_sav, CryptoError};
+pub use crypto::{decrypt_sav, decrypt_sav_raw, derive_key, encrypt_sav, CryptoError};
 #[doc(inline)]
 pub use parts::{
     category_from_varbit, category_name, code_from_level, first_varint_from_weapon_info,
@@ -59,23 +83,36 @@ pub use parts::{
     varbit_from_category, weapon_level_code,
 };
 #[doc(inline)]
-pub use save::{ChangeSet, SaveError, SaveFile, StateFlags};
+pub use save::{
+    character_xp_for_level, BackpackItem, BankItem, ChangeSet, ChangeWarning, Compression,
+    EquipSlot, EquippedItem, ExperienceEntry, InventorySummary, Loadout, PathChange, SaveError,
+    SaveFile, SaveKind, SaveSchema, StateFlags, EXPERIENCE_TRACKS, MAX_BACKPACK_SLOTS,
+    MAX_BANK_SLOTS, MAX_CHARACTER_LEVEL, SAFE_MAX_CASH, SAFE_MAX_ERIDIUM, UNKNOWN_ITEM_TYPE,
+};
 #[doc(inline)]
-pub use serial::{ItemSerial, Legality, RarityEstimate, ResolvedPart, ResolvedString, SerialError, SerialFormat, ValidationCheck, ValidationResult};
+pub use serial::{
+    ItemSerial, ItemSerialBuilder, Legality, RarityEstimate, ResolvedPart, ResolvedString,
+    SerialError, SerialFormat, ValidationCheck, ValidationResult,
+};
 
 // Manifest data lookups
 #[doc(inline)]
 pub use manifest::{
-    all_categories, all_manufacturers, drop_pool, part_name, stats as manifest_stats,
-    world_pool_legendary_count, DropPool,
+    all_categories, all_manufacturers, drop_pool, embedded_parts_database, part_name,
+    stats as manifest_stats, world_pool_legendary_count, DropPool, PartRecord, PartsDatabase,
 };
+#[cfg(feature = "sqlite")]
+#[doc(inline)]
+pub use manifest::SqlitePartsDatabase;
 
 // Reference data (rarities, elements, weapon types, manufacturers, gear types)
 #[doc(inline)]
 pub use reference::{
-    element_by_code, gear_type_by_code, legendary_by_name, manufacturer_by_code,
-    manufacturer_by_name, manufacturer_name_by_code, rarity_by_code, rarity_by_tier,
-    rarity_probability, stat_description, weapon_type_by_code, weapon_type_by_name, ElementType,
-    GearType, LegendaryItem, Manufacturer, RarityTier, WeaponType, ELEMENT_TYPES, GEAR_TYPES,
-    KNOWN_LEGENDARIES, MANUFACTURERS, RARITY_TIERS, WEAPON_TYPES,
+    element_by_code, gear_type_by_code, identify, legendary_by_internal, legendary_by_name,
+    manufacturer_by_code, manufacturer_by_name, manufacturer_name_by_code, parse_part_name,
+    rarity_by_code, rarity_by_tier, rarity_from_internal, rarity_probability, resolve_item_name,
+    stat_description, weapon_type_by_code, weapon_type_by_name, ElementType, GearType,
+    ItemIdentity, ItemNameIndex, LegendaryItem, Manufacturer, PartNameInfo, RarityTier,
+    ResolvedItem, WeaponType, ELEMENT_TYPES, GEAR_TYPES, KNOWN_LEGENDARIES, MANUFACTURERS,
+    RARITY_TIERS, WEAPON_TYPES,
 };
@@ -92,6 +92,175 @@ fn parse_tsv_parts(tsv: &str) -> HashMap<(i64, i64), (String, String)> {
         .collect()
 }
 
+/// A single row of the parts database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRecord {
+    pub category: i64,
+    pub index: i64,
+    pub name: String,
+    pub slot: String,
+}
+
+/// Parts database in `category\tindex\tname\tslot` TSV layout
+#[derive(Debug, Clone, Default)]
+pub struct PartsDatabase {
+    /// Parts sorted by (category, index)
+    pub parts: Vec<PartRecord>,
+}
+
+impl PartsDatabase {
+    /// Parse a monolithic parts TSV, skipping the header and malformed rows
+    pub fn from_tsv(tsv: &str) -> Self {
+        let mut parts: Vec<PartRecord> = tsv
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let mut cols = line.splitn(4, '\t');
+                Some(PartRecord {
+                    category: cols.next()?.parse().ok()?,
+                    index: cols.next()?.parse().ok()?,
+                    name: cols.next()?.to_string(),
+                    slot: cols.next().unwrap_or("unknown").to_string(),
+                })
+            })
+            .collect();
+        parts.sort_by_key(|p| (p.category, p.index));
+        Self { parts }
+    }
+
+    /// Number of parts in the database
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Whether the database has no parts
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Parts belonging to a category, in index order
+    pub fn category(&self, category: i64) -> impl Iterator<Item = &PartRecord> {
+        self.parts.iter().filter(move |p| p.category == category)
+    }
+
+    /// Look up a part by category and index
+    pub fn get(&self, category: i64, index: i64) -> Option<&PartRecord> {
+        self.parts
+            .binary_search_by_key(&(category, index), |p| (p.category, p.index))
+            .ok()
+            .map(|i| &self.parts[i])
+    }
+}
+
+/// Table written by [`PartsDatabase::write_sqlite`]; the primary key doubles
+/// as the `(category, index)` lookup index
+#[cfg(feature = "sqlite")]
+const PARTS_SQLITE_SCHEMA: &str = "CREATE TABLE parts (
+    category INTEGER NOT NULL,
+    part_index INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    slot TEXT NOT NULL,
+    PRIMARY KEY (category, part_index)
+) WITHOUT ROWID";
+
+#[cfg(feature = "sqlite")]
+impl PartsDatabase {
+    /// Load every part from a SQLite database built by [`PartsDatabase::write_sqlite`]
+    ///
+    /// One indexed scan instead of parsing a directory of TSVs. Use
+    /// [`SqlitePartsDatabase`] to query single parts without loading the rest.
+    pub fn from_sqlite<P: AsRef<std::path::Path>>(path: P) -> rusqlite::Result<Self> {
+        SqlitePartsDatabase::open(path)?.load()
+    }
+
+    /// Write the parts to a SQLite database, replacing any parts table already in it
+    pub fn write_sqlite<P: AsRef<std::path::Path>>(&self, path: P) -> rusqlite::Result<()> {
+        let mut conn = rusqlite::Connection::open(path)?;
+        let tx = conn.transaction()?;
+        tx.execute("DROP TABLE IF EXISTS parts", [])?;
+        tx.execute(PARTS_SQLITE_SCHEMA, [])?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR REPLACE INTO parts (category, part_index, name, slot)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for part in &self.parts {
+                insert.execute(rusqlite::params![
+                    part.category,
+                    part.index,
+                    part.name,
+                    part.slot
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+}
+
+/// A SQLite parts database queried in place
+///
+/// Lookups go through the `(category, index)` primary key, so nothing is
+/// loaded up front.
+#[cfg(feature = "sqlite")]
+pub struct SqlitePartsDatabase {
+    conn: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqlitePartsDatabase {
+    const COLUMNS: &'static str = "SELECT category, part_index, name, slot FROM parts";
+
+    /// Open a database built by [`PartsDatabase::write_sqlite`] read-only
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> rusqlite::Result<Self> {
+        let conn = rusqlite::Connection::open_with_flags(
+            path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Look up a part by category and index
+    pub fn get(&self, category: i64, index: i64) -> rusqlite::Result<Option<PartRecord>> {
+        use rusqlite::OptionalExtension;
+
+        let sql = format!("{} WHERE category = ?1 AND part_index = ?2", Self::COLUMNS);
+        self.conn
+            .query_row(&sql, [category, index], Self::record)
+            .optional()
+    }
+
+    /// Parts belonging to a category, in index order
+    pub fn category(&self, category: i64) -> rusqlite::Result<Vec<PartRecord>> {
+        let sql = format!("{} WHERE category = ?1 ORDER BY part_index", Self::COLUMNS);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map([category], Self::record)?;
+        rows.collect()
+    }
+
+    /// Read every part into memory
+    pub fn load(&self) -> rusqlite::Result<PartsDatabase> {
+        let sql = format!("{} ORDER BY category, part_index", Self::COLUMNS);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let parts = stmt
+            .query_map([], Self::record)?
+            .collect::<Result<_, _>>()?;
+        Ok(PartsDatabase { parts })
+    }
+
+    fn record(row: &rusqlite::Row) -> rusqlite::Result<PartRecord> {
+        Ok(PartRecord {
+            category: row.get(0)?,
+            index: row.get(1)?,
+            name: row.get(2)?,
+            slot: row.get(3)?,
+        })
+    }
+}
+
+/// Parts database embedded at build time
+static EMBEDDED_PARTS: Lazy<PartsDatabase> =
+    Lazy::new(|| PartsDatabase::from_tsv(PARTS_DATABASE_TSV));
+
 /// Drop pool data for legendary items per (manufacturer, gear_type) pair
 #[derive(Debug, Clone)]
 pub struct DropPool {
@@ -224,6 +393,11 @@ pub fn part_slot(category: i64, index: i64) -> Option<&'static str> {
         .map(|(_, slot)| slot.as_str())
 }
 
+/// Get the parts database baked into the crate by `build.rs`
+pub fn embedded_parts_database() -> &'static PartsDatabase {
+    &EMBEDDED_PARTS
+}
+
 /// Get a manufacturer's full name from its code
 pub fn manufacturer_name(code: &str) -> Option<&'static str> {
     MANUFACTURERS.get(code).map(|s| s.as_str())
@@ -484,6 +658,54 @@ mod tests {
         let _ = part_name(2, 1);
     }
 
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_roundtrip() {
+        let db = PartsDatabase::from_tsv(
+            "category\tindex\tname\tslot\n\
+             3\t2\tJAK_PS.part_grip_01\tgrip\n\
+             3\t1\tJAK_PS.part_barrel_01\tbarrel\n\
+             9\t1\tJAK_SG.part_barrel_01\tbarrel\n",
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("parts.db");
+        db.write_sqlite(&path).unwrap();
+        // Rebuilding replaces the table rather than failing on duplicates
+        db.write_sqlite(&path).unwrap();
+
+        assert_eq!(PartsDatabase::from_sqlite(&path).unwrap().parts, db.parts);
+
+        let sqlite = SqlitePartsDatabase::open(&path).unwrap();
+        assert_eq!(sqlite.get(3, 1).unwrap().as_ref(), db.get(3, 1));
+        assert_eq!(sqlite.get(3, 99).unwrap(), None);
+        let barrel = sqlite.category(3).unwrap().remove(0);
+        assert_eq!(barrel.name, "JAK_PS.part_barrel_01");
+        assert_eq!(sqlite.category(3).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_embedded_parts_database() {
+        let db = embedded_parts_database();
+        assert!(db.len() > 1000, "only {} embedded parts", db.len());
+
+        // Daedalus Pistol parts resolve and agree with the lookup tables
+        let first = db.category(2).next().expect("no parts for category 2");
+        assert_eq!(part_name(2, first.index), Some(first.name.as_str()));
+        assert_eq!(db.get(2, first.index), Some(first));
+    }
+
+    #[test]
+    fn test_parts_database_from_tsv() {
+        let db = PartsDatabase::from_tsv(
+            "category\tindex\tname\tslot\n9\t2\tJAK_SG.part_grip_01\tgrip\n9\t1\tJAK_SG.part_barrel_01\n",
+        );
+        assert_eq!(db.len(), 2);
+        assert_eq!(db.parts[0].index, 1);
+        assert_eq!(db.parts[0].slot, "unknown");
+        assert_eq!(db.get(9, 2).map(|p| p.slot.as_str()), Some("grip"));
+        assert!(db.get(9, 3).is_none());
+    }
+
     #[test]
     fn test_manufacturer_name() {
         assert_eq!(manufacturer_name("JAK"), Some("Jakobs"));
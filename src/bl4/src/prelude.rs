@@ -0,0 +1,54 @@
+//! The types and functions most tools need, in one import
+//!
+//! ```
+//! use bl4::prelude::*;
+//!
+//! let mut changes = ChangeSet::new();
+//! changes.set_cash(SAFE_MAX_CASH);
+//! assert!(StateFlags::backpack().is_valid());
+//! assert_eq!(rarity_by_tier(5).map(|r| r.name), Some("Legendary"));
+//! ```
+//!
+//! What's here:
+//!
+//! - Save files: [`decrypt_sav`], [`encrypt_sav`], [`SaveFile`], [`SaveKind`],
+//!   [`ChangeSet`], [`ChangeWarning`], [`PathChange`], [`SaveSchema`], [`StateFlags`], the
+//!   inventory, loadout and experience types, and the slot and currency limits.
+//! - Serials: [`ItemSerial`], [`ItemSerialBuilder`], [`Rarity`], [`Element`],
+//!   [`ResolvedPart`], [`Legality`].
+//! - Parts data: [`PartsDatabase`], [`PartRecord`], [`part_name`],
+//!   [`category_name`], [`manufacturer_name`].
+//! - Reference lookups: [`identify`], [`resolve_item_name`],
+//!   [`rarity_from_internal`], the `*_by_code`/`*_by_name`/`*_by_tier`
+//!   functions and the record types they return.
+//! - Errors: [`CryptoError`], [`SaveError`], [`SerialError`].
+//!
+//! Items are only added to the prelude in minor releases, never removed or
+//! renamed, so a glob import keeps compiling. Anything not listed is still
+//! public at its usual path; it's just not part of this promise.
+
+#[doc(no_inline)]
+pub use crate::crypto::{decrypt_sav, encrypt_sav, CryptoError};
+#[doc(no_inline)]
+pub use crate::manifest::{part_name, PartRecord, PartsDatabase};
+#[doc(no_inline)]
+pub use crate::parts::{category_name, manufacturer_name};
+#[doc(no_inline)]
+pub use crate::reference::{
+    element_by_code, gear_type_by_code, identify, legendary_by_internal, legendary_by_name,
+    manufacturer_by_code, manufacturer_by_name, rarity_by_code, rarity_by_tier,
+    rarity_from_internal, resolve_item_name, weapon_type_by_code, weapon_type_by_name, ElementType,
+    GearType, ItemIdentity, ItemNameIndex, LegendaryItem, Manufacturer, RarityTier, ResolvedItem,
+    WeaponType,
+};
+#[doc(no_inline)]
+pub use crate::save::{
+    character_xp_for_level, BackpackItem, BankItem, ChangeSet, ChangeWarning, EquipSlot,
+    EquippedItem, ExperienceEntry, InventorySummary, Loadout, PathChange, SaveError, SaveFile,
+    SaveKind, SaveSchema, StateFlags, MAX_BACKPACK_SLOTS, MAX_BANK_SLOTS, MAX_CHARACTER_LEVEL,
+    SAFE_MAX_CASH, SAFE_MAX_ERIDIUM,
+};
+#[doc(no_inline)]
+pub use crate::serial::{
+    Element, ItemSerial, ItemSerialBuilder, Legality, Rarity, ResolvedPart, SerialError,
+};
@@ -0,0 +1,26 @@
+//! Hex color parsing shared by the reference tables
+
+/// Parse a `#RRGGBB` hex string into its RGB components
+pub(crate) fn parse_hex_rgb(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_rgb() {
+        assert_eq!(parse_hex_rgb("#FFA500"), Some((255, 165, 0)));
+        assert_eq!(parse_hex_rgb("#0080ff"), Some((0, 128, 255)));
+        assert_eq!(parse_hex_rgb("FFA500"), None);
+        assert_eq!(parse_hex_rgb("#FFA5"), None);
+        assert_eq!(parse_hex_rgb("#GGGGGG"), None);
+        assert_eq!(parse_hex_rgb("#+1+1+1"), None);
+    }
+}
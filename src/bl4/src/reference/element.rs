@@ -1,5 +1,7 @@
 //! Element type definitions
 
+use super::color::parse_hex_rgb;
+
 /// Element type information
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ElementType {
@@ -55,6 +57,13 @@ pub const ELEMENT_TYPES: &[ElementType] = &[
     },
 ];
 
+impl ElementType {
+    /// The display color as RGB components (black if `color` is malformed)
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        parse_hex_rgb(self.color).unwrap_or((0, 0, 0))
+    }
+}
+
 /// Get element by code
 pub fn element_by_code(code: &str) -> Option<&'static ElementType> {
     ELEMENT_TYPES.iter().find(|e| e.code == code)
@@ -70,4 +79,17 @@ mod tests {
         assert_eq!(element_by_code("cryo").map(|e| e.name), Some("Cryo"));
         assert_eq!(element_by_code("sonic").map(|e| e.name), Some("Sonic"));
     }
+
+    #[test]
+    fn test_color_hex() {
+        for element in ELEMENT_TYPES {
+            assert!(
+                parse_hex_rgb(element.color).is_some(),
+                "{} has malformed color {}",
+                element.name,
+                element.color
+            );
+        }
+        assert_eq!(element_by_code("fire").unwrap().rgb(), (0xFF, 0x45, 0x00));
+    }
 }
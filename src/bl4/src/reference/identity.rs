@@ -0,0 +1,121 @@
+//! Combined item identity lookup for display
+
+use super::{
+    gear_type_by_code, legendary_by_internal, manufacturer_by_code, parse_part_name,
+    rarity_from_internal, weapon_type_by_code, GearType, LegendaryItem, Manufacturer, RarityTier,
+    WeaponType, GEAR_TYPES,
+};
+
+/// Everything the reference tables know about an internal item or part string
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemIdentity {
+    pub manufacturer: Option<&'static Manufacturer>,
+    pub weapon_type: Option<&'static WeaponType>,
+    pub gear_type: Option<&'static GearType>,
+    /// From the embedded `comp_0N` code, if any
+    pub rarity: Option<&'static RarityTier>,
+    pub legendary: Option<&'static LegendaryItem>,
+}
+
+impl ItemIdentity {
+    /// Weapon type name, or gear type name for non-weapons
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.weapon_type
+            .map(|w| w.name)
+            .or_else(|| self.gear_type.map(|g| g.name))
+    }
+}
+
+/// Resolve an internal string against all reference tables
+///
+/// Accepts part names (`DAD_PS_Barrel_01`), dotted part and composition
+/// strings (`JAK_PS.comp_05_legendary_SeventhSense`), and gear prefixes
+/// (`BOR_REPAIR_KIT.part_borg`, `classmod_gravitar`). Fields that cannot be
+/// resolved are `None`.
+pub fn identify(internal: &str) -> ItemIdentity {
+    let mut identity = ItemIdentity {
+        rarity: rarity_from_internal(internal),
+        legendary: legendary_by_internal(internal),
+        ..ItemIdentity::default()
+    };
+
+    if let Some(info) = parse_part_name(internal) {
+        identity.manufacturer = Some(info.manufacturer);
+        identity.weapon_type = Some(info.weapon_type);
+        return identity;
+    }
+
+    // Everything before the first '.' names the item: MANU_TYPE, MANU_gear, or gear
+    let prefix = internal.split('.').next().unwrap_or_default();
+    let (first, rest) = prefix.split_once('_').unwrap_or((prefix, ""));
+    identity.manufacturer = manufacturer_by_code(&first.to_ascii_uppercase());
+    let type_part = if identity.manufacturer.is_some() {
+        rest
+    } else {
+        prefix
+    };
+
+    identity.weapon_type = weapon_type_by_code(&type_part.to_ascii_uppercase());
+    if identity.weapon_type.is_none() {
+        identity.gear_type = gear_type_by_prefix(&type_part.to_ascii_lowercase());
+    }
+    identity
+}
+
+/// Gear type whose code is the whole string or its first `_`-separated words
+fn gear_type_by_prefix(s: &str) -> Option<&'static GearType> {
+    gear_type_by_code(s).or_else(|| {
+        GEAR_TYPES.iter().find(|g| {
+            s.strip_prefix(g.code)
+                .is_some_and(|rest| rest.starts_with('_'))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identify_legendary() {
+        let id = identify("JAK_PS.comp_05_legendary_SeventhSense");
+        assert_eq!(id.manufacturer.map(|m| m.name), Some("Jakobs"));
+        assert_eq!(id.type_name(), Some("Pistol"));
+        assert_eq!(id.rarity.map(|r| r.name), Some("Legendary"));
+        assert_eq!(id.legendary.map(|l| l.name), Some("Seventh Sense"));
+        assert!(id.gear_type.is_none());
+    }
+
+    #[test]
+    fn test_identify_part_names() {
+        let id = identify("DAD_PS_Barrel_01");
+        assert_eq!(id.manufacturer.map(|m| m.code), Some("DAD"));
+        assert_eq!(id.weapon_type.map(|w| w.code), Some("PS"));
+        assert!(id.rarity.is_none());
+
+        let id = identify("DAD_AR.part_barrel_01");
+        assert_eq!(id.manufacturer.map(|m| m.code), Some("DAD"));
+        assert_eq!(id.weapon_type.map(|w| w.code), Some("AR"));
+
+        let id = identify("VLA_SM.comp_03_rare");
+        assert_eq!(id.rarity.map(|r| r.name), Some("Rare"));
+        assert!(id.legendary.is_none());
+    }
+
+    #[test]
+    fn test_identify_gear() {
+        let id = identify("BOR_REPAIR_KIT.part_borg");
+        assert_eq!(id.manufacturer.map(|m| m.code), Some("BOR"));
+        assert_eq!(id.type_name(), Some("Repair Kit"));
+
+        assert_eq!(identify("classmod_gravitar").type_name(), Some("Class Mod"));
+        assert_eq!(identify("shield.part_core").type_name(), Some("Shield"));
+        assert_eq!(identify("mal_grenade_gadget").type_name(), Some("Grenade"));
+    }
+
+    #[test]
+    fn test_identify_unknown() {
+        assert_eq!(identify("nonsense"), ItemIdentity::default());
+        assert_eq!(identify(""), ItemIdentity::default());
+    }
+}
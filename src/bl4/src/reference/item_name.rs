@@ -0,0 +1,217 @@
+//! Item lookup by the name the game shows
+
+use super::{identify, ItemIdentity, KNOWN_LEGENDARIES};
+
+/// An item matched by [`resolve_item_name`] or [`ItemNameIndex::resolve`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedItem {
+    /// Display name as listed in the matching table
+    pub name: String,
+    /// Internal string (e.g. `VLA_SM.comp_05_legendary_KaoSon`)
+    pub internal: String,
+    /// Manufacturer, weapon or gear type, and rarity of `internal`
+    pub identity: ItemIdentity,
+}
+
+impl ResolvedItem {
+    fn new(name: &str, internal: &str) -> Self {
+        ResolvedItem {
+            name: name.to_string(),
+            internal: internal.to_string(),
+            identity: identify(internal),
+        }
+    }
+}
+
+/// Display names for internal item strings, collected from NCS data
+///
+/// [`resolve_item_name`] only knows [`KNOWN_LEGENDARIES`]. Fill an index
+/// from extracted NCS display names (such as the `NexusSerialized` entries
+/// `bl4 ncs extract` reports) to resolve everything else.
+#[derive(Debug, Clone, Default)]
+pub struct ItemNameIndex {
+    items: Vec<ResolvedItem>,
+}
+
+impl ItemNameIndex {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a display name for an internal item string
+    ///
+    /// The same name may be added for several internals; lookups then
+    /// return all of them.
+    pub fn insert(&mut self, name: &str, internal: &str) {
+        self.items.push(ResolvedItem::new(name, internal));
+    }
+
+    /// Number of names in the index
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the index has no names
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Like [`resolve_item_name`], also matching the names in this index
+    pub fn resolve(&self, name: &str) -> Vec<ResolvedItem> {
+        resolve(name, &self.items)
+    }
+}
+
+/// Find items by display name (e.g. "Kaoson", "Linebacker")
+///
+/// Exact matches in [`KNOWN_LEGENDARIES`] win, ignoring case, spaces and
+/// punctuation. Otherwise names are matched loosely: a name containing the
+/// query, then a name within a couple of typos of it. Every candidate at the
+/// closest level is returned, so an ambiguous name gives several items and
+/// an unknown one gives none. Use [`ItemNameIndex::resolve`] to search NCS
+/// display names too.
+pub fn resolve_item_name(name: &str) -> Vec<ResolvedItem> {
+    resolve(name, &[])
+}
+
+fn resolve(name: &str, extra: &[ResolvedItem]) -> Vec<ResolvedItem> {
+    let query = normalize(name);
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let legendaries = KNOWN_LEGENDARIES
+        .iter()
+        .map(|l| ResolvedItem::new(l.name, l.internal));
+    let exact: Vec<ResolvedItem> = legendaries
+        .clone()
+        .filter(|item| normalize(&item.name) == query)
+        .collect();
+    if !exact.is_empty() {
+        return exact;
+    }
+
+    let mut best = usize::MAX;
+    let mut found: Vec<ResolvedItem> = Vec::new();
+    for item in legendaries.chain(extra.iter().cloned()) {
+        let Some(score) = match_score(&query, &normalize(&item.name)) else {
+            continue;
+        };
+        if score < best {
+            best = score;
+            found.clear();
+        }
+        if score == best && !found.iter().any(|f| f.internal == item.internal) {
+            found.push(item);
+        }
+    }
+    found
+}
+
+/// Lowercase ASCII letters and digits only, so "Heart Gun" matches "heartgun"
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// How closely a normalized name matches a normalized query; lower is closer
+///
+/// 0 is an exact match, 1 a name containing a query of 3 or more
+/// characters, and 2 and up an edit distance within the typo budget.
+fn match_score(query: &str, name: &str) -> Option<usize> {
+    if name == query {
+        return Some(0);
+    }
+    if query.len() >= 3 && name.contains(query) {
+        return Some(1);
+    }
+    let budget = match query.len() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    let distance = edit_distance(query, name);
+    (distance <= budget).then_some(2 + distance)
+}
+
+/// Levenshtein distance between two ASCII strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != cb);
+            row[j + 1] = substitute.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_exact_legendary() {
+        let items = resolve_item_name("kaoson");
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.name, "Kaoson");
+        assert_eq!(item.internal, "VLA_SM.comp_05_legendary_KaoSon");
+        assert_eq!(item.identity.manufacturer.map(|m| m.code), Some("VLA"));
+        assert_eq!(item.identity.weapon_type.map(|w| w.code), Some("SM"));
+        assert_eq!(item.identity.rarity.map(|r| r.tier), Some(5));
+
+        let items = resolve_item_name("LINEBACKER");
+        assert_eq!(items[0].identity.manufacturer.map(|m| m.code), Some("TOR"));
+    }
+
+    #[test]
+    fn test_resolve_fuzzy() {
+        // Typo
+        let items = resolve_item_name("Linebaker");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Linebacker");
+
+        // Spacing and punctuation are ignored
+        assert_eq!(resolve_item_name("heartgun")[0].name, "Heart Gun");
+
+        assert!(resolve_item_name("zzzzzz").is_empty());
+        assert!(resolve_item_name("  ").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_ambiguous() {
+        let mut index = ItemNameIndex::new();
+        index.insert("Hellwalker", "DAD_SG.comp_05_legendary_hellwalker");
+        index.insert("Hellwalker", "DAD_SG.comp_05_legendary_hellwalker_alt");
+        index.insert("Hellfire", "MAL_SG.comp_05_legendary_hellfire");
+        assert_eq!(index.len(), 3);
+
+        let items = index.resolve("hellwalker");
+        let internals: Vec<&str> = items.iter().map(|i| i.internal.as_str()).collect();
+        assert_eq!(
+            internals,
+            [
+                "DAD_SG.comp_05_legendary_hellwalker",
+                "DAD_SG.comp_05_legendary_hellwalker_alt"
+            ]
+        );
+
+        // Without the index these names are unknown
+        assert!(resolve_item_name("Hellfire").is_empty());
+        assert_eq!(index.resolve("Hellfire").len(), 1);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+}
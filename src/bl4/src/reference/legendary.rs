@@ -9,6 +9,17 @@ pub struct LegendaryItem {
     pub manufacturer: &'static str,
 }
 
+impl LegendaryItem {
+    /// True if the `MANU_TYPE` prefix of `internal` matches the declared codes
+    ///
+    /// Both `JAK_PS.comp_05_legendary_SeventhSense` and `DAD_AR_Lumberjack`
+    /// forms are accepted.
+    pub fn is_consistent(&self) -> bool {
+        let mut codes = self.internal.split(['.', '_']);
+        codes.next() == Some(self.manufacturer) && codes.next() == Some(self.weapon_type)
+    }
+}
+
 /// Known legendary items
 pub const KNOWN_LEGENDARIES: &[LegendaryItem] = &[
     // Daedalus
@@ -189,4 +200,51 @@ mod tests {
         assert!(legendary_by_name("Seventh Sense").is_some());
         assert!(legendary_by_internal("JAK_PS.comp_05_legendary_SeventhSense").is_some());
     }
+
+    #[test]
+    fn test_legendary_prefix_matches_declared_codes() {
+        for item in KNOWN_LEGENDARIES {
+            assert!(
+                item.is_consistent(),
+                "{}: declared {}_{} does not match the internal prefix",
+                item.internal,
+                item.manufacturer,
+                item.weapon_type
+            );
+        }
+
+        // Heavy weapons and placeholder-named entries follow the same rule
+        for internal in [
+            "MAL_HW.comp_05_legendary_GammaVoid",
+            "TOR_HW.comp_05_legendary_ravenfire",
+            "BOR_SM.comp_05_legendary_p",
+            "TED_SG.comp_05_legendary_a",
+        ] {
+            assert!(legendary_by_internal(internal).unwrap().is_consistent());
+        }
+
+        let typo = LegendaryItem {
+            weapon_type: "SG",
+            ..legendary_by_name("Seventh Sense").unwrap().clone()
+        };
+        assert!(!typo.is_consistent());
+    }
+
+    #[test]
+    fn test_legendary_codes_in_reference_tables() {
+        for item in KNOWN_LEGENDARIES {
+            assert!(
+                crate::reference::manufacturer_by_code(item.manufacturer).is_some(),
+                "{}: unknown manufacturer {}",
+                item.internal,
+                item.manufacturer
+            );
+            assert!(
+                crate::reference::weapon_type_by_code(item.weapon_type).is_some(),
+                "{}: unknown weapon type {}",
+                item.internal,
+                item.weapon_type
+            );
+        }
+    }
 }
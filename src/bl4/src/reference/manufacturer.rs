@@ -100,4 +100,19 @@ mod tests {
         assert_eq!(manufacturer_by_name("Vladof").map(|m| m.code), Some("VLA"));
         assert!(manufacturer_by_name("Unknown").is_none());
     }
+
+    #[test]
+    fn test_manufacturer_weapon_types_resolve() {
+        assert_eq!(manufacturer_name_by_code("ORD"), Some("Order"));
+        for m in MANUFACTURERS {
+            for code in m.weapon_types {
+                assert!(
+                    crate::reference::weapon_type_by_code(code).is_some(),
+                    "{} lists unknown weapon type {}",
+                    m.code,
+                    code
+                );
+            }
+        }
+    }
 }
@@ -4,18 +4,25 @@
 //! weapon types, manufacturers, and gear types. This data is used for
 //! display and categorization purposes.
 
+mod color;
 mod element;
 mod gear;
+mod identity;
+mod item_name;
 mod legendary;
 mod manufacturer;
+mod part_name;
 mod rarity;
 mod stats;
 mod weapon;
 
 pub use element::*;
 pub use gear::*;
+pub use identity::*;
+pub use item_name::*;
 pub use legendary::*;
 pub use manufacturer::*;
+pub use part_name::*;
 pub use rarity::*;
 pub use stats::*;
 pub use weapon::*;
@@ -0,0 +1,71 @@
+//! Part name parsing against the reference tables
+
+use super::{manufacturer_by_code, weapon_type_by_code, Manufacturer, WeaponType};
+
+/// A part name split into its manufacturer, weapon type, and remainder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNameInfo<'a> {
+    pub manufacturer: &'static Manufacturer,
+    pub weapon_type: &'static WeaponType,
+    /// Everything after `MANU_TYPE_` (e.g. "Barrel_01")
+    pub remainder: &'a str,
+}
+
+/// Parse a part name in `MANU_TYPE_Name` format (e.g. "DAD_PS_Barrel_01")
+///
+/// Both codes must exist in the reference tables, and the remainder must be
+/// non-empty and made only of ASCII alphanumerics and underscores.
+pub fn parse_part_name(s: &str) -> Option<PartNameInfo<'_>> {
+    let mut parts = s.splitn(3, '_');
+    let manufacturer = manufacturer_by_code(parts.next()?)?;
+    let weapon_type = weapon_type_by_code(parts.next()?)?;
+    let remainder = parts.next()?;
+
+    if remainder.is_empty()
+        || !remainder
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    Some(PartNameInfo {
+        manufacturer,
+        weapon_type,
+        remainder,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_part_name() {
+        let info = parse_part_name("DAD_PS_Barrel_01").unwrap();
+        assert_eq!(info.manufacturer.code, "DAD");
+        assert_eq!(info.weapon_type.code, "PS");
+        assert_eq!(info.remainder, "Barrel_01");
+    }
+
+    #[test]
+    fn test_parse_part_name_heavy_weapons_and_order() {
+        for name in ["MAL_HW_Barrel_01", "TOR_HW_Grip_02", "VLA_HW_Mag_01"] {
+            let info = parse_part_name(name).unwrap();
+            assert_eq!(info.weapon_type.name, "Heavy Weapon");
+        }
+
+        let info = parse_part_name("ORD_AR_Scope_01").unwrap();
+        assert_eq!(info.manufacturer.name, "Order");
+    }
+
+    #[test]
+    fn test_parse_part_name_rejects_unknown() {
+        assert!(parse_part_name("XXX_PS_Barrel_01").is_none());
+        assert!(parse_part_name("DAD_ZZ_Barrel_01").is_none());
+        assert!(parse_part_name("DAD_PS").is_none());
+        assert!(parse_part_name("DAD_PS_").is_none());
+        assert!(parse_part_name("DAD_PS.part_barrel_01").is_none());
+        assert!(parse_part_name("DAD_PS_Barrel 01").is_none());
+    }
+}
@@ -1,5 +1,7 @@
 //! Rarity tier definitions
 
+use super::color::parse_hex_rgb;
+
 /// Rarity tier information
 #[derive(Debug, Clone, PartialEq)]
 pub struct RarityTier {
@@ -51,6 +53,16 @@ pub const RARITY_TIERS: &[RarityTier] = &[
     },
 ];
 
+impl RarityTier {
+    /// The display color as RGB components, parsed from `color`.
+    ///
+    /// Falls back to black for a malformed constant; the table tests
+    /// guarantee every entry parses.
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        parse_hex_rgb(self.color).unwrap_or((0, 0, 0))
+    }
+}
+
 /// Sum of all rarity weights (precomputed for probability calculations)
 const TOTAL_WEIGHT: f64 = 100.0 + 6.0 + 0.14 + 0.045 + 0.0003;
 
@@ -64,6 +76,17 @@ pub fn rarity_by_code(code: &str) -> Option<&'static RarityTier> {
     RARITY_TIERS.iter().find(|r| r.code == code)
 }
 
+/// Find the rarity from a `comp_0N` code anywhere in an internal string
+///
+/// Item internals embed the code mid-string
+/// (`JAK_AR.comp_05_legendary_rowan`), where [`rarity_by_code`] needs the
+/// bare code. The first occurrence that names a known tier wins.
+pub fn rarity_from_internal(internal: &str) -> Option<&'static RarityTier> {
+    internal
+        .match_indices("comp_0")
+        .find_map(|(start, _)| internal.get(start..start + 7).and_then(rarity_by_code))
+}
+
 /// Get the probability of a rarity tier dropping (weight / total_weight)
 pub fn rarity_probability(tier: u8) -> Option<f64> {
     rarity_by_tier(tier).map(|r| r.weight / TOTAL_WEIGHT)
@@ -80,6 +103,43 @@ mod tests {
         assert_eq!(rarity_by_code("comp_03").map(|r| r.name), Some("Rare"));
     }
 
+    #[test]
+    fn test_rarity_from_internal() {
+        use crate::reference::KNOWN_LEGENDARIES;
+
+        for internal in [
+            "DAD_AR.comp_05_legendary_OM",
+            "JAK_PS.comp_05_legendary_SeventhSense",
+        ] {
+            assert!(KNOWN_LEGENDARIES.iter().any(|l| l.internal == internal));
+            assert_eq!(rarity_from_internal(internal).map(|r| r.tier), Some(5));
+        }
+        for item in KNOWN_LEGENDARIES
+            .iter()
+            .filter(|l| l.internal.contains("comp_05"))
+        {
+            assert_eq!(
+                rarity_from_internal(item.internal).map(|r| r.name),
+                Some("Legendary"),
+                "{}",
+                item.internal
+            );
+        }
+
+        assert_eq!(
+            rarity_from_internal("VLA_SM.comp_03_rare").map(|r| r.name),
+            Some("Rare")
+        );
+        assert_eq!(rarity_from_internal("comp_02").map(|r| r.tier), Some(2));
+        // An unknown code doesn't hide a later valid one
+        assert_eq!(
+            rarity_from_internal("x.comp_09.comp_04_epic").map(|r| r.tier),
+            Some(4)
+        );
+        assert!(rarity_from_internal("DAD_PS.Zipper").is_none());
+        assert!(rarity_from_internal("comp_0").is_none());
+    }
+
     #[test]
     fn test_rarity_weights() {
         assert!((rarity_by_tier(1).unwrap().weight - 100.0).abs() < f64::EPSILON);
@@ -100,4 +160,17 @@ mod tests {
         let total: f64 = (1..=5).filter_map(rarity_probability).sum();
         assert!((total - 1.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_color_hex() {
+        for tier in RARITY_TIERS {
+            assert!(
+                parse_hex_rgb(tier.color).is_some(),
+                "{} has malformed color {}",
+                tier.name,
+                tier.color
+            );
+        }
+        assert_eq!(rarity_by_tier(5).unwrap().rgb(), (0xFF, 0xA5, 0x00));
+    }
 }
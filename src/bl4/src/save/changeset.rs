@@ -1,16 +1,74 @@
 //! Batch change tracking for save file modifications.
 
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use super::inventory::{backpack_slot_occupied, MAX_BACKPACK_SLOTS, MAX_BANK_SLOTS};
+use super::progression::{
+    character_xp_for_level, experience_entries, EXPERIENCE_TRACKS, MAX_CHARACTER_LEVEL,
+    SAFE_MAX_CASH, SAFE_MAX_ERIDIUM,
+};
+use super::{
+    parse_value, set_yaml_path, EquipSlot, Loadout, SaveError, SaveFile, SaveKind, SaveSchema,
+    StateFlags,
+};
+use crate::reference::{rarity_by_tier, RarityTier};
+use crate::serial::{ItemSerial, Token};
+
+/// Something in a [`ChangeSet`] that applies cleanly but likely isn't wanted,
+/// from [`ChangeSet::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeWarning {
+    /// A staged `state_flags` value lacks [`StateFlags::VALID`], so the game
+    /// will treat the slot as empty and the item is lost
+    MissingValidFlag { path: String, flags: StateFlags },
+}
+
+impl ChangeWarning {
+    /// The staged path the warning is about
+    pub fn path(&self) -> &str {
+        match self {
+            Self::MissingValidFlag { path, .. } => path,
+        }
+    }
+}
 
-use super::{parse_value, SaveError, SaveFile, StateFlags};
+impl fmt::Display for ChangeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingValidFlag { path, flags } => write!(
+                f,
+                "{}: {} ({}) lacks the VALID bit, so the game will treat the slot as empty",
+                path, flags.0, flags
+            ),
+        }
+    }
+}
 
 /// Represents a set of changes to apply to a save file
 ///
 /// This is useful for batching multiple changes together and for
 /// tracking modifications in a GUI before applying them.
-#[derive(Default, Debug, Clone)]
+///
+/// Serializes as a plain `path: value` map, so a work-in-progress set can
+/// be written to YAML or JSON and read back. [`ChangeSet::to_yaml`] gives
+/// the same shape with sorted keys. Preconditions added with
+/// [`ChangeSet::set_if`], and the permission [`ChangeSet::insert`] gives to
+/// create a key, are not serialized.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct ChangeSet {
     changes: HashMap<String, serde_yaml::Value>,
+    /// Values the save must hold at these paths for `apply` to go ahead
+    #[serde(skip)]
+    expected: HashMap<String, serde_yaml::Value>,
+    /// Paths whose final key `apply` may create
+    #[serde(skip)]
+    inserts: HashSet<String>,
+    /// Where the inventory helpers write slot paths
+    #[serde(skip)]
+    schema: SaveSchema,
 }
 
 impl ChangeSet {
@@ -18,14 +76,67 @@ impl ChangeSet {
     pub fn new() -> Self {
         ChangeSet {
             changes: HashMap::new(),
+            expected: HashMap::new(),
+            inserts: HashSet::new(),
+            schema: SaveSchema::default(),
+        }
+    }
+
+    /// Create an empty ChangeSet whose inventory helpers write under `schema`
+    pub fn with_schema(schema: SaveSchema) -> Self {
+        ChangeSet {
+            schema,
+            ..ChangeSet::new()
         }
     }
 
+    /// Paths the inventory helpers write to
+    pub fn schema(&self) -> &SaveSchema {
+        &self.schema
+    }
+
     /// Add a change to the set
+    ///
+    /// Replaces any value already staged for `path`. A precondition from
+    /// [`ChangeSet::set_if`] on `path` still guards the new value; call
+    /// [`ChangeSet::remove`] first to drop it.
     pub fn add(&mut self, path: String, value: serde_yaml::Value) {
         self.changes.insert(path, value);
     }
 
+    /// Add a change that only applies if the save currently holds `expected`
+    ///
+    /// [`ChangeSet::apply`] checks every such precondition before writing
+    /// anything and fails with [`SaveError::PreconditionFailed`] if the value
+    /// at `path` differs or is missing.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::ChangeSet;
+    ///
+    /// let mut changes = ChangeSet::new();
+    /// changes.set_if("state.currencies.cash".into(), 1000.into(), 5000.into());
+    /// assert!(changes.has_precondition("state.currencies.cash"));
+    /// ```
+    pub fn set_if(&mut self, path: String, expected: serde_yaml::Value, new: serde_yaml::Value) {
+        self.expected.insert(path.clone(), expected);
+        self.changes.insert(path, new);
+    }
+
+    /// Add a change whose final key is created if the save lacks it
+    ///
+    /// [`ChangeSet::apply`] writes it with [`SaveFile::insert`] instead of
+    /// [`SaveFile::set`], so the parent of `path` must still exist.
+    pub fn insert(&mut self, path: String, value: serde_yaml::Value) {
+        self.inserts.insert(path.clone());
+        self.changes.insert(path, value);
+    }
+
+    /// Check if a path has a precondition from [`ChangeSet::set_if`]
+    pub fn has_precondition(&self, path: &str) -> bool {
+        self.expected.contains_key(path)
+    }
+
     /// Add a change with a string value (auto-parsed)
     pub fn add_parsed(&mut self, path: String, value_str: &str) {
         let value = parse_value(value_str);
@@ -51,12 +162,16 @@ impl ChangeSet {
 
     /// Remove a change from the set
     pub fn remove(&mut self, path: &str) -> Option<serde_yaml::Value> {
+        self.expected.remove(path);
+        self.inserts.remove(path);
         self.changes.remove(path)
     }
 
     /// Clear all changes
     pub fn clear(&mut self) {
         self.changes.clear();
+        self.expected.clear();
+        self.inserts.clear();
     }
 
     /// Get number of changes
@@ -74,10 +189,165 @@ impl ChangeSet {
         self.changes.iter()
     }
 
+    /// Fold another ChangeSet into this one.
+    ///
+    /// Values and preconditions from `other` win when both sets touch the same
+    /// path. Returns the (sorted) paths where both sets had different values.
+    pub fn merge(&mut self, other: &ChangeSet) -> Vec<String> {
+        let conflicts = self.conflicts_with(other);
+        for (path, value) in &other.changes {
+            self.changes.insert(path.clone(), value.clone());
+        }
+        for (path, expected) in &other.expected {
+            self.expected.insert(path.clone(), expected.clone());
+        }
+        self.inserts.extend(other.inserts.iter().cloned());
+        conflicts
+    }
+
+    /// Fold another ChangeSet into this one, failing on any conflict.
+    ///
+    /// On error, `self` is left unmodified.
+    pub fn merge_strict(&mut self, other: &ChangeSet) -> Result<(), SaveError> {
+        let conflicts = self.conflicts_with(other);
+        if !conflicts.is_empty() {
+            return Err(SaveError::ChangeConflict(conflicts));
+        }
+        self.merge(other);
+        Ok(())
+    }
+
+    /// Paths set in both ChangeSets with different values (sorted)
+    fn conflicts_with(&self, other: &ChangeSet) -> Vec<String> {
+        let mut conflicts: Vec<String> = other
+            .changes
+            .iter()
+            .filter(|(path, value)| self.changes.get(*path).is_some_and(|v| v != *value))
+            .map(|(path, _)| path.clone())
+            .collect();
+        conflicts.sort();
+        conflicts
+    }
+
+    /// Serialize the changes as a YAML preset.
+    ///
+    /// Keys are the dotted/indexed save paths, sorted for stable output.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::ChangeSet;
+    ///
+    /// let mut changes = ChangeSet::new();
+    /// changes.set_cash(999999);
+    /// assert_eq!(changes.to_yaml(), "state.currencies.cash: 999999\n");
+    /// ```
+    pub fn to_yaml(&self) -> String {
+        let sorted: BTreeMap<&String, &serde_yaml::Value> = self.changes.iter().collect();
+        serde_yaml::to_string(&sorted).expect("string-keyed map always serializes")
+    }
+
+    /// Load a ChangeSet from a YAML preset produced by [`ChangeSet::to_yaml`]
+    pub fn from_yaml(s: &str) -> Result<ChangeSet, SaveError> {
+        let changes: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(s)?;
+        Ok(ChangeSet {
+            changes,
+            expected: HashMap::new(),
+            inserts: HashSet::new(),
+            schema: SaveSchema::default(),
+        })
+    }
+
+    /// Check staged values for edits that apply cleanly but break the save
+    ///
+    /// Flags every `*.state_flags` change without [`StateFlags::VALID`]: the
+    /// game reads such a slot as empty, so staging `0` or a label-only value
+    /// silently deletes the item. Warnings are sorted by path.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::{ChangeSet, StateFlags};
+    ///
+    /// let mut changes = ChangeSet::new();
+    /// changes.set_backpack_flags_unchecked(0, StateFlags::new(StateFlags::FAVORITE));
+    /// assert_eq!(changes.validate().len(), 1);
+    /// ```
+    pub fn validate(&self) -> Vec<ChangeWarning> {
+        let mut warnings: Vec<ChangeWarning> = self
+            .changes
+            .iter()
+            .filter(|(path, _)| path.ends_with(".state_flags"))
+            .filter_map(|(path, value)| {
+                let flags = StateFlags::from_raw(u32::try_from(value.as_u64()?).ok()?);
+                (!flags.is_valid()).then(|| ChangeWarning::MissingValidFlag {
+                    path: path.clone(),
+                    flags,
+                })
+            })
+            .collect();
+        warnings.sort_by(|a, b| a.path().cmp(b.path()));
+        warnings
+    }
+
     /// Apply all changes to a SaveFile
+    ///
+    /// Fails with [`SaveError::WrongSaveKind`] before writing anything if a
+    /// profile-only path (`domains.*`) targets a character save. Use
+    /// [`ChangeSet::apply_lenient`] to skip this check. Preconditions from
+    /// [`ChangeSet::set_if`] are checked either way.
+    ///
+    /// Either every change is applied or, on error, none is.
     pub fn apply(&self, save: &mut SaveFile) -> Result<(), SaveError> {
-        for (path, value) in &self.changes {
-            save.set(path, value.clone())?;
+        if save.kind() == SaveKind::Character {
+            if let Some(path) = self
+                .changes
+                .keys()
+                .find(|p| SaveKind::for_path(p) == SaveKind::Profile)
+            {
+                return Err(SaveError::WrongSaveKind {
+                    path: path.clone(),
+                    kind: SaveKind::Character,
+                });
+            }
+        }
+        self.apply_lenient(save)
+    }
+
+    /// Apply all changes to a SaveFile without checking the save kind
+    ///
+    /// Fails with [`SaveError::PreconditionFailed`] before writing anything if
+    /// a [`ChangeSet::set_if`] precondition does not hold.
+    ///
+    /// Changes are written to a copy of the save that replaces it only once
+    /// every path has been set, so an error from any one path (such as
+    /// [`SaveError::KeyNotFound`]) leaves `save` exactly as it was.
+    ///
+    /// Paths are written in sorted order, so a change to a whole value (e.g.
+    /// `state.experience`) lands before changes inside it
+    /// (`state.experience[0].points`) rather than overwriting them.
+    pub fn apply_lenient(&self, save: &mut SaveFile) -> Result<(), SaveError> {
+        self.check_preconditions(save)?;
+        let mut staged = save.clone();
+        let mut changes: Vec<(&String, &serde_yaml::Value)> = self.changes.iter().collect();
+        changes.sort_by(|a, b| a.0.cmp(b.0));
+        for (path, value) in changes {
+            if self.inserts.contains(path) {
+                staged.insert(path, value.clone())?;
+            } else {
+                staged.set(path, value.clone())?;
+            }
+        }
+        *save = staged;
+        Ok(())
+    }
+
+    /// First path (sorted) whose current value differs from its precondition
+    fn check_preconditions(&self, save: &SaveFile) -> Result<(), SaveError> {
+        let mut paths: Vec<&String> = self.expected.keys().collect();
+        paths.sort();
+        for path in paths {
+            if save.get(path).ok() != self.expected.get(path) {
+                return Err(SaveError::PreconditionFailed { path: path.clone() });
+            }
         }
         Ok(())
     }
@@ -92,6 +362,61 @@ impl ChangeSet {
         );
     }
 
+    /// Stage a copy of this character under a new name and identity
+    ///
+    /// Sets `state.char_name` and `state.char_guid`, the 32-digit hex id the
+    /// game uses to tell characters apart; a copy that keeps the original
+    /// GUID is treated as the same character. The GUID comes from the caller
+    /// (e.g. a v4 UUID without dashes) and is stored uppercase; it is created
+    /// if the save lacks one. Chain
+    /// [`ChangeSet::with_class`] to change the class as well.
+    ///
+    /// The game also keys characters on their slot file (`1.sav` to `5.sav`),
+    /// so write the clone to a free slot rather than over the original.
+    /// `save_game_header.guid` is left as is; nothing so far suggests the
+    /// game uses it to identify the character.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::ChangeSet;
+    ///
+    /// let mut changes = ChangeSet::new();
+    /// changes
+    ///     .clone_character("Alt", "6f1c0a2b9d3e4f5a8b7c6d5e4f3a2b1c")
+    ///     .unwrap()
+    ///     .with_class("Char_DarkSiren");
+    /// assert!(changes.has_change("state.char_guid"));
+    /// assert!(changes.has_change("state.class"));
+    /// assert!(changes.clone_character("Alt", "not-a-guid").is_err());
+    /// ```
+    pub fn clone_character(
+        &mut self,
+        new_name: &str,
+        char_guid: &str,
+    ) -> Result<&mut Self, SaveError> {
+        if char_guid.len() != 32 || !char_guid.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(SaveError::InvalidCharGuid(char_guid.to_string()));
+        }
+        self.set_character_name(new_name);
+        self.insert(
+            "state.char_guid".to_string(),
+            serde_yaml::Value::String(char_guid.to_ascii_uppercase()),
+        );
+        Ok(self)
+    }
+
+    /// Set the character class (e.g. "Char_Paladin", "Char_DarkSiren")
+    ///
+    /// Skill trees and class mods are not converted, so a class change is
+    /// best paired with [`ChangeSet::clone_character`] on a fresh character.
+    pub fn with_class(&mut self, class: &str) -> &mut Self {
+        self.add(
+            "state.class".to_string(),
+            serde_yaml::Value::String(class.to_string()),
+        );
+        self
+    }
+
     /// Set cash amount
     pub fn set_cash(&mut self, amount: u64) {
         self.add(
@@ -108,6 +433,22 @@ impl ChangeSet {
         );
     }
 
+    /// Set the stack count for an ammo type (see [`SaveFile::get_ammo`])
+    pub fn set_ammo(&mut self, kind: &str, amount: u64) {
+        self.add(
+            format!("state.ammo.{}", kind),
+            serde_yaml::Value::Number(amount.into()),
+        );
+    }
+
+    /// Set the total playtime in seconds (see [`SaveFile::playtime_seconds`])
+    pub fn set_playtime_seconds(&mut self, seconds: f64) {
+        self.add(
+            "state.total_playtime".to_string(),
+            serde_yaml::Value::Number(seconds.into()),
+        );
+    }
+
     /// Set character XP
     pub fn set_character_xp(&mut self, xp: u64) {
         self.add(
@@ -124,6 +465,146 @@ impl ChangeSet {
         );
     }
 
+    /// Stage a maxed-out character in one call
+    ///
+    /// Sets the character level (clamped to 1..=[`MAX_CHARACTER_LEVEL`]) and
+    /// its XP from [`character_xp_for_level`], then cash to [`SAFE_MAX_CASH`]
+    /// and eridium to [`SAFE_MAX_ERIDIUM`]. Both caps stay far below
+    /// `i32::MAX` so in-game pickups can't overflow them.
+    ///
+    /// Specialization XP is left alone: its curve is only known up to level 6
+    /// and no cap is known. Golden keys are not touched either; the character
+    /// save's `currencies.golden_key` names the SHiFT source rather than a
+    /// count, and key counts live in the profile.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::ChangeSet;
+    ///
+    /// let mut changes = ChangeSet::new();
+    /// changes.max_character(50);
+    /// assert!(changes.has_change("state.experience[0].points"));
+    /// ```
+    pub fn max_character(&mut self, level: u32) {
+        let level = level.clamp(1, MAX_CHARACTER_LEVEL);
+        self.add(
+            "state.experience[0].level".to_string(),
+            serde_yaml::Value::Number(level.into()),
+        );
+        self.set_character_xp(character_xp_for_level(level));
+        self.set_cash(SAFE_MAX_CASH);
+        self.set_eridium(SAFE_MAX_ERIDIUM);
+    }
+
+    /// Set an experience track's level and points, adding the track if the
+    /// save lacks it
+    ///
+    /// When `state.experience` already has an entry of `type_name`, its
+    /// `level` and `points` are staged in place. Otherwise the whole array is
+    /// staged with a `{type, level, points}` entry appended, folding in any
+    /// `state.experience[N]` changes already in this set; a save without
+    /// `state.experience` gets a new array. Only the tracks in
+    /// [`EXPERIENCE_TRACKS`] are appended; any other type fails with
+    /// [`SaveError::UnknownExperienceTrack`].
+    ///
+    /// Returns the track's index in the array.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::{ChangeSet, SaveFile};
+    ///
+    /// let yaml = "state:\n  experience:\n    - type: Character\n      level: 10\n      points: 5000\n";
+    /// let mut save = SaveFile::from_yaml(yaml.as_bytes()).unwrap();
+    ///
+    /// let mut changes = ChangeSet::new();
+    /// let index = changes
+    ///     .ensure_experience_track("Specialization", 3, 2599, &save)
+    ///     .unwrap();
+    /// changes.apply(&mut save).unwrap();
+    /// assert_eq!(index, 1);
+    /// assert_eq!(save.experience_entries()[1].points, Some(2599));
+    /// ```
+    pub fn ensure_experience_track(
+        &mut self,
+        type_name: &str,
+        level: u32,
+        points: u64,
+        save: &SaveFile,
+    ) -> Result<usize, SaveError> {
+        const EXPERIENCE_PATH: &str = "state.experience";
+
+        let staged_array = self.get_change(EXPERIENCE_PATH).cloned();
+        let mut doc = experience_doc(match &staged_array {
+            Some(array) => array.clone(),
+            None => match save.get(EXPERIENCE_PATH) {
+                Ok(array) => array.clone(),
+                Err(SaveError::KeyNotFound(_)) => serde_yaml::Value::Sequence(Vec::new()),
+                Err(e) => return Err(e),
+            },
+        });
+        let existing = experience_entries(&doc)
+            .into_iter()
+            .find(|e| e.type_name.as_deref() == Some(type_name))
+            .map(|e| e.index);
+
+        if let (Some(index), None) = (existing, &staged_array) {
+            let base = format!("{}[{}]", EXPERIENCE_PATH, index);
+            self.add(
+                format!("{}.level", base),
+                serde_yaml::Value::Number(level.into()),
+            );
+            self.add(
+                format!("{}.points", base),
+                serde_yaml::Value::Number(points.into()),
+            );
+            return Ok(index);
+        }
+        if existing.is_none() && !EXPERIENCE_TRACKS.contains(&type_name) {
+            return Err(SaveError::UnknownExperienceTrack(type_name.to_string()));
+        }
+
+        // Element paths would race the whole-array change in `apply`
+        let element_prefix = format!("{}[", EXPERIENCE_PATH);
+        let mut element_paths: Vec<String> = self
+            .changes
+            .keys()
+            .filter(|p| p.starts_with(&element_prefix))
+            .cloned()
+            .collect();
+        element_paths.sort();
+        for path in &element_paths {
+            set_yaml_path(&mut doc, path, self.changes[path].clone(), false)?;
+        }
+
+        let array = doc
+            .get_mut("state")
+            .and_then(|s| s.get_mut("experience"))
+            .and_then(|e| e.as_sequence_mut())
+            .ok_or_else(|| SaveError::KeyNotFound(EXPERIENCE_PATH.to_string()))?;
+        let index = match existing {
+            Some(index) => {
+                array[index]["level"] = level.into();
+                array[index]["points"] = points.into();
+                index
+            }
+            None => {
+                let mut entry = serde_yaml::Mapping::new();
+                entry.insert("type".into(), type_name.into());
+                entry.insert("level".into(), level.into());
+                entry.insert("points".into(), points.into());
+                array.push(entry.into());
+                array.len() - 1
+            }
+        };
+        let array = serde_yaml::Value::Sequence(array.clone());
+
+        for path in &element_paths {
+            self.remove(path);
+        }
+        self.insert(EXPERIENCE_PATH.to_string(), array);
+        Ok(index)
+    }
+
     // ─────────────────────────────────────────────────────────────────
     // Backpack Item Operations
     // ─────────────────────────────────────────────────────────────────
@@ -131,7 +612,7 @@ impl ChangeSet {
     /// Add an item to a backpack slot.
     ///
     /// # Arguments
-    /// * `slot` - Backpack slot number (0-22 typically)
+    /// * `slot` - Backpack slot number (below [`MAX_BACKPACK_SLOTS`])
     /// * `serial` - Item serial string (e.g., "@Ugr$ZCm/...")
     /// * `flags` - State flags for the item
     ///
@@ -140,10 +621,25 @@ impl ChangeSet {
     /// use bl4::{ChangeSet, StateFlags};
     ///
     /// let mut changes = ChangeSet::new();
-    /// changes.add_backpack_item(0, "@Ugr$ZCm/...", StateFlags::backpack());
+    /// changes
+    ///     .add_backpack_item(0, "@Ugr$ZCm/...", StateFlags::backpack())
+    ///     .unwrap();
+    /// assert!(changes.add_backpack_item(23, "@Ugr$ZCm/...", StateFlags::backpack()).is_err());
     /// ```
-    pub fn add_backpack_item(&mut self, slot: u8, serial: &str, flags: StateFlags) {
-        let base = format!("state.inventory.items.backpack.slot_{}", slot);
+    pub fn add_backpack_item(
+        &mut self,
+        slot: u8,
+        serial: &str,
+        flags: StateFlags,
+    ) -> Result<(), SaveError> {
+        check_backpack_slot(slot)?;
+        self.add_backpack_item_unchecked(slot, serial, flags);
+        Ok(())
+    }
+
+    /// Add an item to any backpack slot number, including ones the game ignores.
+    pub fn add_backpack_item_unchecked(&mut self, slot: u8, serial: &str, flags: StateFlags) {
+        let base = self.schema.backpack_slot(slot);
         self.add(
             format!("{}.serial", base),
             serde_yaml::Value::String(serial.to_string()),
@@ -158,54 +654,117 @@ impl ChangeSet {
         );
     }
 
+    /// Add an item to the first backpack slot that is free in `save` and
+    /// not already claimed by this changeset.
+    ///
+    /// Returns the chosen slot, or [`SaveError::BackpackFull`].
+    pub fn add_item_auto(
+        &mut self,
+        save: &SaveFile,
+        serial: &str,
+        flags: StateFlags,
+    ) -> Result<u8, SaveError> {
+        let slot = (0..MAX_BACKPACK_SLOTS)
+            .find(|&slot| {
+                !backpack_slot_occupied(&save.data, &self.schema, slot)
+                    && !self.backpack_slot_staged(slot)
+            })
+            .ok_or(SaveError::BackpackFull(MAX_BACKPACK_SLOTS))?;
+        self.add_backpack_item_unchecked(slot, serial, flags);
+        Ok(slot)
+    }
+
+    /// Check whether this changeset already puts an item in a backpack slot
+    fn backpack_slot_staged(&self, slot: u8) -> bool {
+        let path = format!("{}.serial", self.schema.backpack_slot(slot));
+        self.get_change(&path)
+            .and_then(|v| v.as_str())
+            .is_some_and(|s| !s.is_empty())
+    }
+
     /// Set state_flags on an existing backpack item.
-    pub fn set_backpack_flags(&mut self, slot: u8, flags: StateFlags) {
+    ///
+    /// Fails with [`SaveError::MissingValidFlag`] if `flags` lacks
+    /// [`StateFlags::VALID`], which would make the game drop the item.
+    pub fn set_backpack_flags(&mut self, slot: u8, flags: StateFlags) -> Result<(), SaveError> {
+        check_backpack_slot(slot)?;
+        check_valid_flags(flags)?;
+        self.set_backpack_flags_unchecked(slot, flags);
+        Ok(())
+    }
+
+    /// Set state_flags on any backpack slot number, including ones the game ignores.
+    pub fn set_backpack_flags_unchecked(&mut self, slot: u8, flags: StateFlags) {
         self.add(
-            format!("state.inventory.items.backpack.slot_{}.state_flags", slot),
+            format!("{}.state_flags", self.schema.backpack_slot(slot)),
             serde_yaml::Value::Number((flags.0 as i64).into()),
         );
     }
 
     /// Set or clear the favorite flag on a backpack item.
-    pub fn set_favorite(&mut self, slot: u8, value: bool) {
+    pub fn set_favorite(&mut self, slot: u8, value: bool) -> Result<(), SaveError> {
         let mut flags = StateFlags::backpack();
         flags.set_favorite(value);
-        self.set_backpack_flags(slot, flags);
+        self.set_backpack_flags(slot, flags)
     }
 
     /// Set or clear the junk flag on a backpack item.
-    pub fn set_junk(&mut self, slot: u8, value: bool) {
+    pub fn set_junk(&mut self, slot: u8, value: bool) -> Result<(), SaveError> {
         let mut flags = StateFlags::backpack();
         flags.set_junk(value);
-        self.set_backpack_flags(slot, flags);
+        self.set_backpack_flags(slot, flags)
     }
 
     /// Set or clear label 1 on a backpack item.
-    pub fn set_label1(&mut self, slot: u8, value: bool) {
+    pub fn set_label1(&mut self, slot: u8, value: bool) -> Result<(), SaveError> {
         let mut flags = StateFlags::backpack();
         flags.set_label1(value);
-        self.set_backpack_flags(slot, flags);
+        self.set_backpack_flags(slot, flags)
     }
 
     /// Set or clear label 2 on a backpack item.
-    pub fn set_label2(&mut self, slot: u8, value: bool) {
+    pub fn set_label2(&mut self, slot: u8, value: bool) -> Result<(), SaveError> {
         let mut flags = StateFlags::backpack();
         flags.set_label2(value);
-        self.set_backpack_flags(slot, flags);
+        self.set_backpack_flags(slot, flags)
     }
 
     /// Set or clear label 3 on a backpack item.
-    pub fn set_label3(&mut self, slot: u8, value: bool) {
+    pub fn set_label3(&mut self, slot: u8, value: bool) -> Result<(), SaveError> {
         let mut flags = StateFlags::backpack();
         flags.set_label3(value);
-        self.set_backpack_flags(slot, flags);
+        self.set_backpack_flags(slot, flags)
     }
 
     /// Set or clear label 4 on a backpack item.
-    pub fn set_label4(&mut self, slot: u8, value: bool) {
+    pub fn set_label4(&mut self, slot: u8, value: bool) -> Result<(), SaveError> {
         let mut flags = StateFlags::backpack();
         flags.set_label4(value);
-        self.set_backpack_flags(slot, flags);
+        self.set_backpack_flags(slot, flags)
+    }
+
+    /// Change a backpack item's rarity by swapping its `comp_0N` part.
+    ///
+    /// Reads the slot's current serial from `save`, replaces the composition
+    /// part with the generic part for `tier` (1-5, see [`crate::RARITY_TIERS`]),
+    /// and queues the re-encoded serial.
+    pub fn set_item_rarity(
+        &mut self,
+        slot: u8,
+        tier: u8,
+        save: &SaveFile,
+    ) -> Result<(), SaveError> {
+        check_backpack_slot(slot)?;
+        let rarity = rarity_by_tier(tier).ok_or(SaveError::InvalidRarityTier(tier))?;
+        let path = format!("{}.serial", self.schema.backpack_slot(slot));
+        let serial = save
+            .get(&path)?
+            .as_str()
+            .ok_or_else(|| SaveError::KeyNotFound(path.clone()))?;
+
+        let new_serial = swap_composition_part(serial, rarity)?;
+        self.add(path, serde_yaml::Value::String(new_serial));
+        Ok(())
     }
 
     // ─────────────────────────────────────────────────────────────────
@@ -217,11 +776,23 @@ impl ChangeSet {
     /// Note: Bank items are stored in profile.sav, not character saves.
     ///
     /// # Arguments
-    /// * `slot` - Bank slot number
+    /// * `slot` - Bank slot number (below [`MAX_BANK_SLOTS`])
     /// * `serial` - Item serial string
     /// * `flags` - State flags for the item
-    pub fn add_bank_item(&mut self, slot: u16, serial: &str, flags: StateFlags) {
-        let base = format!("domains.local.shared.inventory.items.bank.slot_{}", slot);
+    pub fn add_bank_item(
+        &mut self,
+        slot: u16,
+        serial: &str,
+        flags: StateFlags,
+    ) -> Result<(), SaveError> {
+        check_bank_slot(slot)?;
+        self.add_bank_item_unchecked(slot, serial, flags);
+        Ok(())
+    }
+
+    /// Add an item to any bank slot number, including out-of-range ones.
+    pub fn add_bank_item_unchecked(&mut self, slot: u16, serial: &str, flags: StateFlags) {
+        let base = self.schema.bank_slot(slot);
         self.add(
             format!("{}.serial", base),
             serde_yaml::Value::String(serial.to_string()),
@@ -233,12 +804,20 @@ impl ChangeSet {
     }
 
     /// Set state_flags on an existing bank item.
-    pub fn set_bank_flags(&mut self, slot: u16, flags: StateFlags) {
+    ///
+    /// Fails with [`SaveError::MissingValidFlag`] like
+    /// [`ChangeSet::set_backpack_flags`].
+    pub fn set_bank_flags(&mut self, slot: u16, flags: StateFlags) -> Result<(), SaveError> {
+        check_bank_slot(slot)?;
+        check_valid_flags(flags)?;
+        self.set_bank_flags_unchecked(slot, flags);
+        Ok(())
+    }
+
+    /// Set state_flags on any bank slot number, including out-of-range ones.
+    pub fn set_bank_flags_unchecked(&mut self, slot: u16, flags: StateFlags) {
         self.add(
-            format!(
-                "domains.local.shared.inventory.items.bank.slot_{}.state_flags",
-                slot
-            ),
+            format!("{}.state_flags", self.schema.bank_slot(slot)),
             serde_yaml::Value::Number((flags.0 as i64).into()),
         );
     }
@@ -257,29 +836,112 @@ impl ChangeSet {
     /// * `serial` - Item serial string
     pub fn equip_item(&mut self, slot: u8, serial: &str) {
         let yaml = format!("- serial: '{}'\n  flags: 1\n  state_flags: 1", serial);
-        let _ = self.add_raw(
-            format!("state.inventory.equipped_inventory.equipped.slot_{}", slot),
-            &yaml,
-        );
+        let _ = self.add_raw(self.schema.equipped_slot(slot), &yaml);
     }
 
     /// Clear an equipped slot (unequip item).
     pub fn unequip_slot(&mut self, slot: u8) {
-        let _ = self.add_raw(
-            format!("state.inventory.equipped_inventory.equipped.slot_{}", slot),
-            "[]",
-        );
+        let _ = self.add_raw(self.schema.equipped_slot(slot), "[]");
+    }
+
+    /// Write every equipment slot from a loadout, clearing empty slots.
+    ///
+    /// Slots missing from the save's `equipped` map are added, so a save that
+    /// has never used some slots still applies.
+    pub fn set_loadout(&mut self, loadout: &Loadout) {
+        for slot in EquipSlot::ALL {
+            self.insert(self.schema.equip_slot(slot), loadout.slot_value(slot));
+        }
+    }
+}
+
+/// Reject backpack slots the game never reads
+fn check_backpack_slot(slot: u8) -> Result<(), SaveError> {
+    if slot < MAX_BACKPACK_SLOTS {
+        return Ok(());
+    }
+    Err(SaveError::SlotOutOfRange {
+        container: "backpack",
+        slot: slot.into(),
+        limit: MAX_BACKPACK_SLOTS.into(),
+    })
+}
+
+/// Reject bank slots past [`MAX_BANK_SLOTS`]
+fn check_bank_slot(slot: u16) -> Result<(), SaveError> {
+    if slot < MAX_BANK_SLOTS {
+        return Ok(());
+    }
+    Err(SaveError::SlotOutOfRange {
+        container: "bank",
+        slot,
+        limit: MAX_BANK_SLOTS,
+    })
+}
+
+fn check_valid_flags(flags: StateFlags) -> Result<(), SaveError> {
+    if flags.is_valid() {
+        return Ok(());
     }
+    Err(SaveError::MissingValidFlag(flags.0))
+}
+
+/// Re-encode `serial` with its `comp_0N` part replaced by `rarity`'s generic part
+fn swap_composition_part(serial: &str, rarity: &RarityTier) -> Result<String, SaveError> {
+    let item = ItemSerial::decode(serial)?;
+    let old_index = item
+        .parts_with_names()
+        .into_iter()
+        .find_map(|(index, name, _)| {
+            let segment = name?.split('.').next_back()?;
+            segment.starts_with("comp_0").then_some(index)
+        })
+        .ok_or_else(|| SaveError::NoCompositionPart(serial.to_string()))?;
+
+    let category = item
+        .parts_category()
+        .ok_or_else(|| SaveError::NoCompositionPart(serial.to_string()))?;
+    let target = format!("{}_{}", rarity.code, rarity.name.to_lowercase());
+    let new_index = crate::manifest::part_index(category, &target).ok_or_else(|| {
+        SaveError::CompositionPartNotFound {
+            category,
+            code: rarity.code.to_string(),
+        }
+    })?;
+
+    let tokens = item
+        .tokens
+        .iter()
+        .map(|token| match token {
+            Token::Part { index, values } if *index == old_index => Token::Part {
+                index: new_index as u64,
+                values: values.clone(),
+            },
+            other => other.clone(),
+        })
+        .collect();
+    Ok(item.with_tokens(tokens).encode_from_tokens())
+}
+
+/// Wrap an experience array as `state.experience` so save-path helpers work on it
+fn experience_doc(array: serde_yaml::Value) -> serde_yaml::Value {
+    let mut state = serde_yaml::Mapping::new();
+    state.insert("experience".into(), array);
+    let mut doc = serde_yaml::Mapping::new();
+    doc.insert("state".into(), state.into());
+    doc.into()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::save::EquippedItem;
 
     fn test_save_yaml() -> &'static str {
         r#"
 state:
   char_name: TestChar
+  char_guid: 0123456789ABCDEF0123456789ABCDEF
   class: Char_TestClass
   player_difficulty: Normal
   currencies:
@@ -304,6 +966,143 @@ save_game_header:
 "#
     }
 
+    #[test]
+    fn test_clone_character() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changes = ChangeSet::new();
+        changes
+            .clone_character("Alt", "6f1c0a2b9d3e4f5a8b7c6d5e4f3a2b1c")
+            .unwrap()
+            .with_class("Char_DarkSiren");
+        changes.apply(&mut save).unwrap();
+
+        assert_eq!(save.get_character_name(), Some("Alt"));
+        assert_eq!(save.get_character_class(), Some("Char_DarkSiren"));
+        let guid = save.get("state.char_guid").unwrap();
+        assert_eq!(guid.as_str(), Some("6F1C0A2B9D3E4F5A8B7C6D5E4F3A2B1C"));
+    }
+
+    #[test]
+    fn test_clone_character_creates_missing_guid() {
+        let yaml = test_save_yaml().replace("  char_guid: 0123456789ABCDEF0123456789ABCDEF\n", "");
+        let mut save = SaveFile::from_yaml(yaml.as_bytes()).unwrap();
+        assert!(save.get("state.char_guid").is_err());
+
+        let mut changes = ChangeSet::new();
+        changes
+            .clone_character("Alt", "6F1C0A2B9D3E4F5A8B7C6D5E4F3A2B1C")
+            .unwrap();
+        changes.apply(&mut save).unwrap();
+
+        let guid = save.get("state.char_guid").unwrap();
+        assert_eq!(guid.as_str(), Some("6F1C0A2B9D3E4F5A8B7C6D5E4F3A2B1C"));
+    }
+
+    #[test]
+    fn test_clone_character_rejects_bad_guid() {
+        let mut changes = ChangeSet::new();
+        for guid in ["", "6F1C0A2B", "6F1C0A2B-9D3E-4F5A-8B7C-6D5E4F3A2B1C"] {
+            assert!(matches!(
+                changes.clone_character("Alt", guid),
+                Err(SaveError::InvalidCharGuid(_))
+            ));
+        }
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_ensure_experience_track_updates_existing() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changes = ChangeSet::new();
+        let index = changes
+            .ensure_experience_track("Specialization", 6, 12718, &save)
+            .unwrap();
+        assert_eq!(index, 1);
+        assert!(changes.has_change("state.experience[1].points"));
+        assert!(!changes.has_change("state.experience"));
+
+        changes.apply(&mut save).unwrap();
+        let entries = save.experience_entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].level, Some(6));
+        assert_eq!(entries[1].points, Some(12718));
+    }
+
+    #[test]
+    fn test_ensure_experience_track_appends_missing() {
+        let yaml =
+            "state:\n  experience:\n    - type: Character\n      level: 10\n      points: 5000\n";
+        let mut save = SaveFile::from_yaml(yaml.as_bytes()).unwrap();
+        let mut changes = ChangeSet::new();
+        changes.set_character_xp(6000);
+        let index = changes
+            .ensure_experience_track("Specialization", 3, 2599, &save)
+            .unwrap();
+        assert_eq!(index, 1);
+        // The staged element change is folded into the new array
+        assert!(!changes.has_change("state.experience[0].points"));
+
+        // A second call updates the staged array rather than the save
+        let index = changes
+            .ensure_experience_track("Character", 12, 7000, &save)
+            .unwrap();
+        assert_eq!(index, 0);
+
+        changes.apply(&mut save).unwrap();
+        let entries = save.experience_entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].points, Some(7000));
+        assert_eq!(entries[1].type_name.as_deref(), Some("Specialization"));
+        assert_eq!(entries[1].level, Some(3));
+        assert_eq!(entries[1].points, Some(2599));
+    }
+
+    #[test]
+    fn test_ensure_experience_track_then_element_change() {
+        let yaml =
+            "state:\n  experience:\n    - type: Character\n      level: 10\n      points: 5000\n";
+        let mut save = SaveFile::from_yaml(yaml.as_bytes()).unwrap();
+        let mut changes = ChangeSet::new();
+        changes
+            .ensure_experience_track("Specialization", 3, 2599, &save)
+            .unwrap();
+        // Staged after the whole array, so it must be applied on top of it
+        changes.set_character_xp(6000);
+
+        changes.apply(&mut save).unwrap();
+        let entries = save.experience_entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].points, Some(6000));
+        assert_eq!(entries[1].points, Some(2599));
+    }
+
+    #[test]
+    fn test_ensure_experience_track_creates_array() {
+        let mut save = SaveFile::from_yaml(b"state:\n  char_name: Test\n").unwrap();
+        let mut changes = ChangeSet::new();
+        let index = changes
+            .ensure_experience_track("Character", 5, 1000, &save)
+            .unwrap();
+        assert_eq!(index, 0);
+
+        changes.apply(&mut save).unwrap();
+        let entries = save.experience_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].type_name.as_deref(), Some("Character"));
+        assert_eq!(entries[0].level, Some(5));
+    }
+
+    #[test]
+    fn test_ensure_experience_track_rejects_unknown() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changes = ChangeSet::new();
+        assert!(matches!(
+            changes.ensure_experience_track("Vehicle", 1, 0, &save),
+            Err(SaveError::UnknownExperienceTrack(_))
+        ));
+        assert!(changes.is_empty());
+    }
+
     #[test]
     fn test_changeset_new() {
         let changeset = ChangeSet::new();
@@ -387,6 +1186,92 @@ save_game_header:
         assert_eq!(save.get_character_name(), Some("Modified"));
     }
 
+    #[test]
+    fn test_changeset_apply_rejects_profile_path_on_character() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changeset = ChangeSet::new();
+        changeset.set_cash(1);
+        changeset.set_bank_flags(0, StateFlags::bank()).unwrap();
+
+        let result = changeset.apply(&mut save);
+        assert!(matches!(
+            result,
+            Err(SaveError::WrongSaveKind {
+                kind: SaveKind::Character,
+                ..
+            })
+        ));
+        // Nothing was written
+        assert_eq!(save.get_cash(), Some(1000));
+
+        // Lenient apply gets as far as the missing bank tree
+        let result = changeset.apply_lenient(&mut save);
+        assert!(matches!(result, Err(SaveError::KeyNotFound(_))));
+    }
+
+    #[test]
+    fn test_changeset_apply_failure_leaves_save_untouched() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let before = save.to_yaml().unwrap();
+        let mut changeset = ChangeSet::new();
+        changeset.set_cash(5555);
+        changeset.set_eridium(6666);
+        changeset.add("state.missing.key".to_string(), 1.into());
+
+        let result = changeset.apply(&mut save);
+        assert!(matches!(result, Err(SaveError::KeyNotFound(_))));
+        assert_eq!(save.to_yaml().unwrap(), before);
+        assert!(!save.is_dirty());
+    }
+
+    #[test]
+    fn test_changeset_set_if_matching() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changeset = ChangeSet::new();
+        changeset.set_if("state.currencies.cash".into(), 1000.into(), 5000.into());
+        changeset.set_eridium(6666);
+
+        assert!(changeset.has_precondition("state.currencies.cash"));
+        assert!(!changeset.has_precondition("state.currencies.eridium"));
+        changeset.apply(&mut save).unwrap();
+        assert_eq!(save.get_cash(), Some(5000));
+        assert_eq!(save.get_eridium(), Some(6666));
+
+        // The save has moved on, so applying again is stale
+        assert!(matches!(
+            changeset.apply(&mut save),
+            Err(SaveError::PreconditionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_changeset_set_if_mismatch() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changeset = ChangeSet::new();
+        changeset.set_eridium(6666);
+        changeset.set_if("state.currencies.cash".into(), 999.into(), 5000.into());
+
+        let result = changeset.apply(&mut save);
+        assert!(
+            matches!(result, Err(SaveError::PreconditionFailed { path }) if path == "state.currencies.cash")
+        );
+        // Nothing was written, including the unconditional change
+        assert_eq!(save.get_cash(), Some(1000));
+        assert_eq!(save.get_eridium(), Some(50));
+
+        // A missing path never matches
+        let mut changeset = ChangeSet::new();
+        changeset.set_if("state.nope".into(), serde_yaml::Value::Null, 1.into());
+        assert!(matches!(
+            changeset.apply_lenient(&mut save),
+            Err(SaveError::PreconditionFailed { .. })
+        ));
+
+        // Removing the change drops its precondition
+        changeset.remove("state.nope");
+        assert!(!changeset.has_precondition("state.nope"));
+    }
+
     #[test]
     fn test_changeset_convenience_methods() {
         let mut changeset = ChangeSet::new();
@@ -396,13 +1281,30 @@ save_game_header:
         changeset.set_eridium(22222);
         changeset.set_character_xp(33333);
         changeset.set_specialization_xp(44444);
+        changeset.set_ammo("repairkit", 5);
 
-        assert_eq!(changeset.len(), 5);
+        assert_eq!(changeset.len(), 6);
         assert!(changeset.has_change("state.char_name"));
         assert!(changeset.has_change("state.currencies.cash"));
         assert!(changeset.has_change("state.currencies.eridium"));
         assert!(changeset.has_change("state.experience[0].points"));
         assert!(changeset.has_change("state.experience[1].points"));
+        assert!(changeset.has_change("state.ammo.repairkit"));
+    }
+
+    #[test]
+    fn test_changeset_max_character() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut changeset = ChangeSet::new();
+        changeset.max_character(80);
+        assert_eq!(changeset.len(), 4);
+        changeset.apply(&mut save).unwrap();
+
+        assert_eq!(save.get_character_level(), Some((50, 3_430_207)));
+        assert_eq!(save.get_cash(), Some(SAFE_MAX_CASH));
+        assert_eq!(save.get_eridium(), Some(SAFE_MAX_ERIDIUM));
+        // Specialization is untouched
+        assert_eq!(save.get_specialization_level(), Some((5, 2500)));
     }
 
     #[test]
@@ -435,10 +1337,134 @@ save_game_header:
         assert_eq!(save.get_specialization_level(), Some((5, 66666)));
     }
 
+    #[test]
+    fn test_changeset_merge_no_conflicts() {
+        let mut a = ChangeSet::new();
+        a.set_cash(100);
+        let mut b = ChangeSet::new();
+        b.set_eridium(200);
+        b.set_cash(100);
+
+        let conflicts = a.merge(&b);
+        assert!(conflicts.is_empty());
+        assert_eq!(a.len(), 2);
+    }
+
+    #[test]
+    fn test_changeset_merge_other_wins() {
+        let mut a = ChangeSet::new();
+        a.set_cash(100);
+        a.set_eridium(5);
+        let mut b = ChangeSet::new();
+        b.set_cash(999);
+        b.set_eridium(6);
+
+        let conflicts = a.merge(&b);
+        assert_eq!(
+            conflicts,
+            vec![
+                "state.currencies.cash".to_string(),
+                "state.currencies.eridium".to_string()
+            ]
+        );
+        assert_eq!(
+            a.get_change("state.currencies.cash").unwrap().as_u64(),
+            Some(999)
+        );
+    }
+
+    #[test]
+    fn test_changeset_merge_strict() {
+        let mut a = ChangeSet::new();
+        a.set_cash(100);
+        let mut b = ChangeSet::new();
+        b.set_cash(999);
+
+        let result = a.merge_strict(&b);
+        assert!(matches!(result, Err(SaveError::ChangeConflict(ref p)) if p.len() == 1));
+        // Unchanged on failure
+        assert_eq!(
+            a.get_change("state.currencies.cash").unwrap().as_u64(),
+            Some(100)
+        );
+
+        let mut c = ChangeSet::new();
+        c.set_eridium(1);
+        a.merge_strict(&c).unwrap();
+        assert_eq!(a.len(), 2);
+    }
+
+    #[test]
+    fn test_changeset_yaml_roundtrip() {
+        let mut changeset = ChangeSet::new();
+        changeset.set_cash(123);
+        changeset.set_character_name("Preset");
+        changeset.set_specialization_xp(42);
+
+        let yaml = changeset.to_yaml();
+        assert!(yaml.contains("state.currencies.cash: 123"));
+
+        let loaded = ChangeSet::from_yaml(&yaml).unwrap();
+        assert_eq!(loaded.len(), 3);
+        for (path, value) in changeset.iter() {
+            assert_eq!(loaded.get_change(path), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_changeset_serde_roundtrip() {
+        let mut changeset = ChangeSet::new();
+        changeset.set_cash(123);
+        changeset.set_character_name("Preset");
+        changeset
+            .add_raw("state.list".to_string(), "[1, two, {three: 3}]")
+            .unwrap();
+
+        let json = serde_json::to_string(&changeset).unwrap();
+        let from_json: ChangeSet = serde_json::from_str(&json).unwrap();
+        let yaml = serde_yaml::to_string(&changeset).unwrap();
+        let from_yaml: ChangeSet = serde_yaml::from_str(&yaml).unwrap();
+
+        for loaded in [&from_json, &from_yaml] {
+            assert_eq!(loaded.len(), changeset.len());
+            for (path, value) in changeset.iter() {
+                assert_eq!(loaded.get_change(path), Some(value));
+            }
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["state.currencies.cash"], 123);
+    }
+
+    #[test]
+    fn test_changeset_from_yaml_apply() {
+        let preset = r#"
+state.currencies.cash: 424242
+state.currencies.eridium: 777
+state.char_name: FromPreset
+state.experience[0].points: 31337
+"#;
+        let changeset = ChangeSet::from_yaml(preset).unwrap();
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        changeset.apply(&mut save).unwrap();
+
+        assert_eq!(save.get_cash(), Some(424242));
+        assert_eq!(save.get_eridium(), Some(777));
+        assert_eq!(save.get_character_name(), Some("FromPreset"));
+        assert_eq!(save.get_character_level(), Some((10, 31337)));
+    }
+
+    #[test]
+    fn test_changeset_from_yaml_invalid() {
+        assert!(ChangeSet::from_yaml("- not\n- a map").is_err());
+    }
+
     #[test]
     fn test_changeset_add_backpack_item() {
         let mut changeset = ChangeSet::new();
-        changeset.add_backpack_item(5, "@TestSerial", StateFlags::backpack().with_favorite());
+        changeset
+            .add_backpack_item(5, "@TestSerial", StateFlags::backpack().with_favorite())
+            .unwrap();
 
         assert!(changeset.has_change("state.inventory.items.backpack.slot_5.serial"));
         assert!(changeset.has_change("state.inventory.items.backpack.slot_5.flags"));
@@ -453,7 +1479,9 @@ save_game_header:
     #[test]
     fn test_changeset_set_backpack_flags() {
         let mut changeset = ChangeSet::new();
-        changeset.set_backpack_flags(3, StateFlags::backpack().with_junk());
+        changeset
+            .set_backpack_flags(3, StateFlags::backpack().with_junk())
+            .unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_3.state_flags")
@@ -461,10 +1489,58 @@ save_game_header:
         assert_eq!(change.as_i64(), Some(517)); // 513 + 4 (backpack + junk)
     }
 
+    #[test]
+    fn test_changeset_flags_without_valid_bit_rejected() {
+        let mut changeset = ChangeSet::new();
+        let label_only = StateFlags::new(StateFlags::FAVORITE);
+        assert!(matches!(
+            changeset.set_backpack_flags(3, label_only),
+            Err(SaveError::MissingValidFlag(2))
+        ));
+        assert!(matches!(
+            changeset.set_bank_flags(3, StateFlags::new(0)),
+            Err(SaveError::MissingValidFlag(0))
+        ));
+        assert!(changeset.is_empty());
+    }
+
+    #[test]
+    fn test_changeset_validate() {
+        let mut changeset = ChangeSet::new();
+        changeset.set_backpack_flags_unchecked(5, StateFlags::new(StateFlags::JUNK));
+        changeset.set_bank_flags_unchecked(1, StateFlags::new(0));
+        changeset
+            .set_backpack_flags(0, StateFlags::backpack())
+            .unwrap();
+        changeset.add_parsed(
+            "state.inventory.items.backpack.slot_2.state_flags".to_string(),
+            "0",
+        );
+        changeset.set_cash(0);
+
+        let warnings = changeset.validate();
+        let paths: Vec<&str> = warnings.iter().map(|w| w.path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "domains.local.shared.inventory.items.bank.slot_1.state_flags",
+                "state.inventory.items.backpack.slot_2.state_flags",
+                "state.inventory.items.backpack.slot_5.state_flags",
+            ]
+        );
+        assert_eq!(
+            warnings[2],
+            ChangeWarning::MissingValidFlag {
+                path: "state.inventory.items.backpack.slot_5.state_flags".to_string(),
+                flags: StateFlags::new(StateFlags::JUNK),
+            }
+        );
+    }
+
     #[test]
     fn test_changeset_set_favorite() {
         let mut changeset = ChangeSet::new();
-        changeset.set_favorite(0, true);
+        changeset.set_favorite(0, true).unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_0.state_flags")
@@ -475,7 +1551,7 @@ save_game_header:
     #[test]
     fn test_changeset_set_junk() {
         let mut changeset = ChangeSet::new();
-        changeset.set_junk(1, true);
+        changeset.set_junk(1, true).unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_1.state_flags")
@@ -486,7 +1562,9 @@ save_game_header:
     #[test]
     fn test_changeset_add_bank_item() {
         let mut changeset = ChangeSet::new();
-        changeset.add_bank_item(10, "@BankSerial", StateFlags::bank());
+        changeset
+            .add_bank_item(10, "@BankSerial", StateFlags::bank())
+            .unwrap();
 
         assert!(changeset.has_change("domains.local.shared.inventory.items.bank.slot_10.serial"));
         assert!(
@@ -512,10 +1590,271 @@ save_game_header:
         assert!(changeset.has_change("state.inventory.equipped_inventory.equipped.slot_4"));
     }
 
+    #[test]
+    fn test_changeset_set_loadout_roundtrip() {
+        let yaml = r#"
+state:
+  inventory:
+    equipped_inventory:
+      equipped:
+        slot_0: []
+        slot_1: []
+        slot_2: []
+        slot_3: []
+        slot_4:
+          - serial: "@OldShield"
+            flags: 1
+            state_flags: 1
+        slot_5: []
+        slot_6: []
+        slot_7: []
+        slot_8: []
+"#;
+        let mut save = SaveFile::from_yaml(yaml.as_bytes()).unwrap();
+
+        let mut loadout = save.loadout();
+        loadout.set(EquipSlot::Weapon2, Some(EquippedItem::new("@Gun")));
+        loadout.set(EquipSlot::Shield, None);
+
+        let mut changeset = ChangeSet::new();
+        changeset.set_loadout(&loadout);
+        assert_eq!(changeset.len(), EquipSlot::ALL.len());
+        changeset.apply(&mut save).unwrap();
+
+        assert_eq!(save.loadout(), loadout);
+        assert!(save
+            .get("state.inventory.equipped_inventory.equipped.slot_4")
+            .unwrap()
+            .as_sequence()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_changeset_set_loadout_partial_equipped() {
+        let yaml = r#"
+state:
+  inventory:
+    equipped_inventory:
+      equipped:
+        slot_0: []
+        slot_4: []
+"#;
+        let mut save = SaveFile::from_yaml(yaml.as_bytes()).unwrap();
+
+        let mut loadout = Loadout::default();
+        loadout.set(EquipSlot::ClassMod, Some(EquippedItem::new("@Mod")));
+
+        let mut changeset = ChangeSet::new();
+        changeset.set_loadout(&loadout);
+        changeset.apply(&mut save).unwrap();
+
+        assert_eq!(save.loadout(), loadout);
+        let equipped = save.keys("state.inventory.equipped_inventory.equipped");
+        assert_eq!(equipped.len(), 9);
+
+        // Only the final key is created; a missing equipped map still fails
+        let mut bare = SaveFile::from_yaml(b"state:\n  inventory: {}\n").unwrap();
+        assert!(matches!(
+            changeset.apply(&mut bare),
+            Err(SaveError::KeyNotFound(_))
+        ));
+    }
+
+    fn backpack_save(serial: &str) -> SaveFile {
+        let yaml = format!(
+            "state:\n  inventory:\n    items:\n      backpack:\n        slot_0:\n          \
+             serial: '{}'\n          flags: 0\n          state_flags: 513\n",
+            serial
+        );
+        SaveFile::from_yaml(yaml.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_changeset_backpack_slot_bounds() {
+        let mut changeset = ChangeSet::new();
+        let last = MAX_BACKPACK_SLOTS - 1;
+        assert!(changeset
+            .add_backpack_item(last, "@Last", StateFlags::backpack())
+            .is_ok());
+        assert!(changeset.set_favorite(last, true).is_ok());
+
+        let result =
+            changeset.add_backpack_item(MAX_BACKPACK_SLOTS, "@Over", StateFlags::backpack());
+        assert!(matches!(
+            result,
+            Err(SaveError::SlotOutOfRange {
+                container: "backpack",
+                slot: 23,
+                limit: 23,
+            })
+        ));
+        assert!(changeset.set_junk(200, true).is_err());
+        assert!(!changeset.has_change("state.inventory.items.backpack.slot_23.serial"));
+
+        changeset.add_backpack_item_unchecked(MAX_BACKPACK_SLOTS, "@Over", StateFlags::backpack());
+        assert!(changeset.has_change("state.inventory.items.backpack.slot_23.serial"));
+    }
+
+    #[test]
+    fn test_changeset_bank_slot_bounds() {
+        let mut changeset = ChangeSet::new();
+        assert!(changeset
+            .add_bank_item(MAX_BANK_SLOTS - 1, "@Last", StateFlags::bank())
+            .is_ok());
+        assert!(matches!(
+            changeset.set_bank_flags(MAX_BANK_SLOTS, StateFlags::bank()),
+            Err(SaveError::SlotOutOfRange {
+                container: "bank",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_changeset_add_item_auto() {
+        let save = backpack_save("@Existing");
+        let mut changeset = ChangeSet::new();
+
+        let first = changeset
+            .add_item_auto(&save, "@New1", StateFlags::backpack())
+            .unwrap();
+        let second = changeset
+            .add_item_auto(&save, "@New2", StateFlags::backpack())
+            .unwrap();
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(
+            changeset
+                .get_change("state.inventory.items.backpack.slot_2.serial")
+                .and_then(|v| v.as_str()),
+            Some("@New2")
+        );
+    }
+
+    #[test]
+    fn test_changeset_add_item_auto_full() {
+        let save = backpack_save("@Existing");
+        let mut changeset = ChangeSet::new();
+        for slot in 1..MAX_BACKPACK_SLOTS {
+            changeset
+                .add_backpack_item(slot, "@Filler", StateFlags::backpack())
+                .unwrap();
+        }
+
+        let result = changeset.add_item_auto(&save, "@Overflow", StateFlags::backpack());
+        assert!(matches!(result, Err(SaveError::BackpackFull(23))));
+    }
+
+    #[test]
+    fn test_changeset_custom_schema() {
+        let yaml = r#"
+state:
+  inv:
+    backpack:
+      slot_0:
+        serial: "@Existing"
+        flags: 0
+        state_flags: 1
+      slot_1:
+        serial: ""
+        flags: 0
+        state_flags: 0
+"#;
+        let schema = SaveSchema {
+            backpack: "state.inv.backpack".to_string(),
+            ..SaveSchema::default()
+        };
+        let mut save = SaveFile::from_yaml(yaml.as_bytes())
+            .unwrap()
+            .with_schema(schema.clone());
+        assert_eq!(save.backpack_items().count(), 1);
+
+        let mut changeset = ChangeSet::with_schema(schema);
+        let slot = changeset
+            .add_item_auto(&save, "@New", StateFlags::backpack())
+            .unwrap();
+        assert_eq!(slot, 1);
+        assert!(changeset.has_change("state.inv.backpack.slot_1.serial"));
+
+        changeset.apply(&mut save).unwrap();
+        let serials: Vec<String> = save.backpack_items().map(|i| i.serial).collect();
+        assert_eq!(serials, ["@Existing", "@New"]);
+    }
+
+    #[test]
+    fn test_changeset_set_item_rarity() {
+        // Rebuild a known serial with an explicit Rare composition part
+        let base = ItemSerial::decode("@Ugr$ZCm/&tH!t{KgK/Shxu>k").unwrap();
+        let category = base.parts_category().unwrap();
+        let rare = crate::manifest::part_index(category, "comp_03_rare").unwrap();
+        let comp_indices: Vec<u64> = base
+            .parts_with_names()
+            .into_iter()
+            .filter(|(_, name, _)| name.is_some_and(|n| n.contains("comp_0")))
+            .map(|(index, _, _)| index)
+            .collect();
+        let mut tokens: Vec<Token> = base
+            .tokens
+            .iter()
+            .filter(|t| !matches!(t, Token::Part { index, .. } if comp_indices.contains(index)))
+            .cloned()
+            .collect();
+        tokens.push(Token::Part {
+            index: rare as u64,
+            values: vec![],
+        });
+        let serial = base.with_tokens(tokens).encode_from_tokens();
+        let mut save = backpack_save(&serial);
+
+        let mut changeset = ChangeSet::new();
+        changeset.set_item_rarity(0, 5, &save).unwrap();
+        changeset.apply(&mut save).unwrap();
+
+        let new_serial = save
+            .get("state.inventory.items.backpack.slot_0.serial")
+            .unwrap()
+            .as_str()
+            .unwrap();
+        let item = ItemSerial::decode(new_serial).unwrap();
+        let names: Vec<&str> = item
+            .parts_with_names()
+            .into_iter()
+            .filter_map(|(_, name, _)| name)
+            .collect();
+        assert!(names.iter().any(|n| n.ends_with("comp_05_legendary")));
+        assert!(!names.iter().any(|n| n.ends_with("comp_03_rare")));
+    }
+
+    #[test]
+    fn test_changeset_set_item_rarity_errors() {
+        let save = backpack_save("@Ugr$ZCm/&tH!t{KgK/Shxu>k");
+        let mut changeset = ChangeSet::new();
+
+        assert!(matches!(
+            changeset.set_item_rarity(0, 0, &save),
+            Err(SaveError::InvalidRarityTier(0))
+        ));
+        assert!(matches!(
+            changeset.set_item_rarity(0, 6, &save),
+            Err(SaveError::InvalidRarityTier(6))
+        ));
+        assert!(matches!(
+            changeset.set_item_rarity(3, 5, &save),
+            Err(SaveError::KeyNotFound(_))
+        ));
+        assert!(changeset.is_empty());
+
+        let bad = backpack_save("not-a-serial");
+        assert!(matches!(
+            changeset.set_item_rarity(0, 5, &bad),
+            Err(SaveError::Serial(_))
+        ));
+    }
+
     #[test]
     fn test_changeset_set_label1() {
         let mut changeset = ChangeSet::new();
-        changeset.set_label1(2, true);
+        changeset.set_label1(2, true).unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_2.state_flags")
@@ -526,7 +1865,7 @@ save_game_header:
     #[test]
     fn test_changeset_set_label2() {
         let mut changeset = ChangeSet::new();
-        changeset.set_label2(3, true);
+        changeset.set_label2(3, true).unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_3.state_flags")
@@ -537,7 +1876,7 @@ save_game_header:
     #[test]
     fn test_changeset_set_label3() {
         let mut changeset = ChangeSet::new();
-        changeset.set_label3(4, true);
+        changeset.set_label3(4, true).unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_4.state_flags")
@@ -548,7 +1887,7 @@ save_game_header:
     #[test]
     fn test_changeset_set_label4() {
         let mut changeset = ChangeSet::new();
-        changeset.set_label4(5, true);
+        changeset.set_label4(5, true).unwrap();
 
         let change = changeset
             .get_change("state.inventory.items.backpack.slot_5.state_flags")
@@ -559,7 +1898,9 @@ save_game_header:
     #[test]
     fn test_changeset_set_bank_flags() {
         let mut changeset = ChangeSet::new();
-        changeset.set_bank_flags(42, StateFlags::bank().with_favorite());
+        changeset
+            .set_bank_flags(42, StateFlags::bank().with_favorite())
+            .unwrap();
 
         let change = changeset
             .get_change("domains.local.shared.inventory.items.bank.slot_42.state_flags")
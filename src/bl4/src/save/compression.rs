@@ -0,0 +1,127 @@
+//! Compressed save YAML detection and encoding.
+//!
+//! Some tools keep decrypted saves gzip- or zstd-compressed. The format is
+//! recognized from its magic number, so readers don't need to be told.
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::io::{Cursor, Read, Write};
+
+use super::SaveError;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression applied to decrypted save YAML
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Plain YAML
+    #[default]
+    None,
+    /// gzip at the default level
+    Gzip,
+    /// zstd at the default level (requires the `zstd` feature to read or write)
+    Zstd,
+}
+
+impl Compression {
+    /// Identify the compression from the first bytes of a stream
+    pub fn detect(magic: &[u8]) -> Compression {
+        if magic.starts_with(&GZIP_MAGIC) {
+            Compression::Gzip
+        } else if magic.starts_with(&ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// Wrap `reader` in whichever decoder its magic number calls for
+pub(super) fn decompressing_reader<'a, R: Read + 'a>(
+    mut reader: R,
+) -> Result<Box<dyn Read + 'a>, SaveError> {
+    let mut magic = Vec::with_capacity(ZSTD_MAGIC.len());
+    reader
+        .by_ref()
+        .take(ZSTD_MAGIC.len() as u64)
+        .read_to_end(&mut magic)?;
+    let compression = Compression::detect(&magic);
+    let reader = Cursor::new(magic).chain(reader);
+
+    match compression {
+        Compression::None => Ok(Box::new(reader)),
+        Compression::Gzip => Ok(Box::new(GzDecoder::new(reader))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(zstd::Decoder::new(reader)?)),
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => Err(SaveError::UnsupportedCompression(compression)),
+    }
+}
+
+/// Compress everything `write` produces into `writer`
+pub(super) fn write_compressed<W, F>(
+    writer: W,
+    compression: Compression,
+    write: F,
+) -> Result<(), SaveError>
+where
+    W: Write,
+    F: FnOnce(&mut dyn Write) -> Result<(), SaveError>,
+{
+    match compression {
+        Compression::None => {
+            let mut writer = writer;
+            write(&mut writer)
+        }
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+            write(&mut encoder)?;
+            encoder.finish()?;
+            Ok(())
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            let mut encoder = zstd::Encoder::new(writer, 0)?;
+            write(&mut encoder)?;
+            encoder.finish()?;
+            Ok(())
+        }
+        #[cfg(not(feature = "zstd"))]
+        Compression::Zstd => Err(SaveError::UnsupportedCompression(compression)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+        assert_eq!(Compression::detect(&ZSTD_MAGIC), Compression::Zstd);
+        assert_eq!(Compression::detect(b"state:"), Compression::None);
+        assert_eq!(Compression::detect(&[0x1f]), Compression::None);
+        assert_eq!(Compression::detect(&[]), Compression::None);
+    }
+
+    #[test]
+    fn test_plain_reader_keeps_sniffed_bytes() {
+        let mut out = String::new();
+        decompressing_reader(&b"ab"[..])
+            .unwrap()
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(out, "ab");
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_zstd_without_feature() {
+        let err = decompressing_reader(&ZSTD_MAGIC[..]).err().unwrap();
+        assert!(matches!(
+            err,
+            SaveError::UnsupportedCompression(Compression::Zstd)
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+//! Leaf-level comparison of two save files.
+
+use std::fmt;
+
+use serde_yaml::Value;
+
+/// A single value that differs between two saves
+///
+/// `old` is `None` for paths only present in the new save, and `new` is `None`
+/// for paths that were removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathChange {
+    /// Path in [`super::SaveFile::get`] syntax (e.g. `state.experience[0].level`)
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl fmt::Display for PathChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                write!(f, "~ {}: {} -> {}", self.path, inline(old), inline(new))
+            }
+            (None, Some(new)) => write!(f, "+ {}: {}", self.path, inline(new)),
+            (Some(old), None) => write!(f, "- {}: {}", self.path, inline(old)),
+            (None, None) => write!(f, "  {}", self.path),
+        }
+    }
+}
+
+/// Render a value on one line for diff output
+fn inline(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Mapping(_) | Value::Sequence(_) => {
+            serde_json::to_string(value).unwrap_or_else(|_| "<unprintable>".to_string())
+        }
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+fn child_path(parent: &str, key: &Value) -> String {
+    let key = match key {
+        Value::String(s) => s.clone(),
+        other => inline(other),
+    };
+    if parent.is_empty() {
+        key
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// Compare `old` and `new`, collecting every differing leaf under `path`
+pub(crate) fn diff_values(path: &str, old: &Value, new: &Value, out: &mut Vec<PathChange>) {
+    match (old, new) {
+        (Value::Mapping(a), Value::Mapping(b)) => {
+            for (key, a_val) in a {
+                let child = child_path(path, key);
+                match b.get(key) {
+                    Some(b_val) => diff_values(&child, a_val, b_val, out),
+                    None => out.push(PathChange {
+                        path: child,
+                        old: Some(a_val.clone()),
+                        new: None,
+                    }),
+                }
+            }
+            for (key, b_val) in b.iter().filter(|(k, _)| !a.contains_key(*k)) {
+                out.push(PathChange {
+                    path: child_path(path, key),
+                    old: None,
+                    new: Some(b_val.clone()),
+                });
+            }
+        }
+        (Value::Sequence(a), Value::Sequence(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let child = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(a_val), Some(b_val)) => diff_values(&child, a_val, b_val, out),
+                    (a_val, b_val) => out.push(PathChange {
+                        path: child,
+                        old: a_val.cloned(),
+                        new: b_val.cloned(),
+                    }),
+                }
+            }
+        }
+        _ if old != new => out.push(PathChange {
+            path: path.to_string(),
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(a: &str, b: &str) -> Vec<PathChange> {
+        let a: Value = serde_yaml::from_str(a).unwrap();
+        let b: Value = serde_yaml::from_str(b).unwrap();
+        let mut out = Vec::new();
+        diff_values("", &a, &b, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        assert!(diff("a: 1\nb: [1, 2]\n", "a: 1\nb: [1, 2]\n").is_empty());
+    }
+
+    #[test]
+    fn test_diff_changed_added_removed() {
+        let changes = diff(
+            "state:\n  cash: 10\n  old: x\n  xp: [1, 2]\n",
+            "state:\n  cash: 20\n  new: y\n  xp: [1, 3, 4]\n",
+        );
+        let lines: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "~ state.cash: 10 -> 20",
+                "- state.old: x",
+                "~ state.xp[1]: 2 -> 3",
+                "+ state.xp[2]: 4",
+                "+ state.new: y",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_type_change() {
+        let changes = diff("a: 1\n", "a: {b: 2}\n");
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_string(), "~ a: 1 -> {\"b\":2}");
+    }
+}
@@ -25,6 +25,15 @@ fn filled_foddata(value: u8) -> Result<String, SaveError> {
     Ok(BASE64_STANDARD.encode(compressed))
 }
 
+/// Save path of the `foddatas` sequence [`reveal_map`] and [`clear_map`] edit
+pub fn foddatas_path(data: &serde_yaml::Value) -> &'static str {
+    if data.get("gbx_discovery_pc").is_some() {
+        "gbx_discovery_pc.foddatas"
+    } else {
+        "foddatas"
+    }
+}
+
 /// Replace FOD data for matching zones with a given fill value.
 ///
 /// If `zone` is Some, only affects that zone. Otherwise affects all zones.
@@ -0,0 +1,335 @@
+//! Inventory enumeration for save files.
+//!
+//! Items live in YAML maps keyed `slot_N`. Slots are yielded in numeric
+//! order (so `slot_10` comes after `slot_9`) and empty slots are skipped.
+
+use std::collections::BTreeMap;
+
+use super::{SaveSchema, StateFlags};
+use crate::serial::ItemSerial;
+
+/// Number of backpack slots the game uses (`slot_0` through `slot_22`)
+pub const MAX_BACKPACK_SLOTS: u8 = 23;
+
+/// Upper bound on bank slot numbers, to catch typos and corrupt values
+pub const MAX_BANK_SLOTS: u16 = 400;
+
+/// An item stored in the profile bank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BankItem {
+    pub slot: u16,
+    pub serial: String,
+    pub state_flags: StateFlags,
+}
+
+/// An item stored in a character's backpack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackpackItem {
+    pub slot: u8,
+    pub serial: String,
+    pub state_flags: StateFlags,
+}
+
+/// Item counts for a character's backpack
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InventorySummary {
+    /// Occupied backpack slots
+    pub backpack_items: usize,
+    pub favorites: usize,
+    pub junk: usize,
+    /// Items per weapon or gear type name; serials that don't decode, or
+    /// decode to no known type, are counted under [`UNKNOWN_ITEM_TYPE`]
+    pub by_type: BTreeMap<String, usize>,
+    /// Free slots among the first [`MAX_BACKPACK_SLOTS`]
+    pub empty_slots: usize,
+}
+
+/// [`InventorySummary::by_type`] bucket for items whose type can't be determined
+pub const UNKNOWN_ITEM_TYPE: &str = "unknown";
+
+/// Walk a sequence of map keys from the root
+pub(crate) fn lookup<'a>(
+    data: &'a serde_yaml::Value,
+    segments: &[&str],
+) -> Option<&'a serde_yaml::Value> {
+    segments
+        .iter()
+        .try_fold(data, |current, key| current.get(*key))
+}
+
+/// Walk a dotted path of map keys (no `[N]` indices) from the root
+pub(crate) fn lookup_dotted<'a>(
+    data: &'a serde_yaml::Value,
+    path: &str,
+) -> Option<&'a serde_yaml::Value> {
+    path.split('.')
+        .try_fold(data, |current, key| current.get(key))
+}
+
+/// Collect `slot_N` entries from a slot map, sorted by slot number
+pub(crate) fn sorted_slots(container: &serde_yaml::Value) -> Vec<(u32, &serde_yaml::Value)> {
+    let Some(map) = container.as_mapping() else {
+        return Vec::new();
+    };
+
+    let mut slots: Vec<(u32, &serde_yaml::Value)> = map
+        .iter()
+        .filter_map(|(key, value)| {
+            let slot = key.as_str()?.strip_prefix("slot_")?.parse().ok()?;
+            Some((slot, value))
+        })
+        .collect();
+    slots.sort_by_key(|(slot, _)| *slot);
+    slots
+}
+
+/// Read a slot's serial and state_flags, skipping empty slots
+pub(crate) fn slot_serial(item: &serde_yaml::Value) -> Option<(String, StateFlags)> {
+    let serial = item.get("serial")?.as_str()?;
+    if serial.is_empty() {
+        return None;
+    }
+    let flags = item
+        .get("state_flags")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+    Some((serial.to_string(), StateFlags::from_raw(flags)))
+}
+
+/// Check whether a backpack slot holds an item (an empty serial counts as free)
+pub(crate) fn backpack_slot_occupied(
+    data: &serde_yaml::Value,
+    schema: &SaveSchema,
+    slot: u8,
+) -> bool {
+    lookup_dotted(data, &schema.backpack)
+        .and_then(|backpack| backpack.get(format!("slot_{}", slot)))
+        .and_then(slot_serial)
+        .is_some()
+}
+
+/// Find the lowest-numbered free backpack slot
+pub(crate) fn first_empty_backpack_slot(
+    data: &serde_yaml::Value,
+    schema: &SaveSchema,
+) -> Option<u8> {
+    (0..MAX_BACKPACK_SLOTS).find(|&slot| !backpack_slot_occupied(data, schema, slot))
+}
+
+/// Enumerate bank items from profile save data
+pub(crate) fn bank_items(data: &serde_yaml::Value, schema: &SaveSchema) -> Vec<BankItem> {
+    let Some(bank) = lookup_dotted(data, &schema.bank) else {
+        return Vec::new();
+    };
+
+    sorted_slots(bank)
+        .into_iter()
+        .filter_map(|(slot, item)| {
+            let slot = u16::try_from(slot).ok()?;
+            let (serial, state_flags) = slot_serial(item)?;
+            Some(BankItem {
+                slot,
+                serial,
+                state_flags,
+            })
+        })
+        .collect()
+}
+
+/// Enumerate backpack items from character save data
+pub(crate) fn backpack_items(data: &serde_yaml::Value, schema: &SaveSchema) -> Vec<BackpackItem> {
+    let Some(backpack) = lookup_dotted(data, &schema.backpack) else {
+        return Vec::new();
+    };
+
+    sorted_slots(backpack)
+        .into_iter()
+        .filter_map(|(slot, item)| {
+            let slot = u8::try_from(slot).ok()?;
+            let (serial, state_flags) = slot_serial(item)?;
+            Some(BackpackItem {
+                slot,
+                serial,
+                state_flags,
+            })
+        })
+        .collect()
+}
+
+/// Weapon type (for weapons) or category name (for gear) of a serial
+fn item_type(serial: &str) -> &'static str {
+    let Ok(item) = ItemSerial::decode(serial) else {
+        return UNKNOWN_ITEM_TYPE;
+    };
+    item.weapon_info()
+        .map(|(_, weapon_type)| weapon_type)
+        .or_else(|| item.category_name())
+        .unwrap_or(UNKNOWN_ITEM_TYPE)
+}
+
+/// Count backpack items by label and type
+pub(crate) fn inventory_summary(data: &serde_yaml::Value, schema: &SaveSchema) -> InventorySummary {
+    let mut summary = InventorySummary {
+        empty_slots: (0..MAX_BACKPACK_SLOTS)
+            .filter(|&slot| !backpack_slot_occupied(data, schema, slot))
+            .count(),
+        ..InventorySummary::default()
+    };
+
+    for item in backpack_items(data, schema) {
+        summary.backpack_items += 1;
+        summary.favorites += usize::from(item.state_flags.is_favorite());
+        summary.junk += usize::from(item.state_flags.is_junk());
+        *summary
+            .by_type
+            .entry(item_type(&item.serial).to_string())
+            .or_default() += 1;
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile_yaml() -> &'static str {
+        r#"
+domains:
+  local:
+    shared:
+      inventory:
+        items:
+          bank:
+            slot_10:
+              serial: "@Ten"
+              state_flags: 3
+            slot_2:
+              serial: "@Two"
+              state_flags: 1
+            slot_5:
+              serial: ""
+              state_flags: 0
+            slot_9:
+              serial: "@Nine"
+            not_a_slot: 1
+"#
+    }
+
+    #[test]
+    fn test_bank_items_sorted_and_skips_empty() {
+        let data: serde_yaml::Value = serde_yaml::from_str(profile_yaml()).unwrap();
+        let items = bank_items(&data, &SaveSchema::default());
+
+        let slots: Vec<u16> = items.iter().map(|i| i.slot).collect();
+        assert_eq!(slots, vec![2, 9, 10]);
+        assert_eq!(items[0].serial, "@Two");
+        assert_eq!(items[1].state_flags, StateFlags::from_raw(0));
+        assert!(items[2].state_flags.is_favorite());
+    }
+
+    #[test]
+    fn test_bank_items_missing_bank() {
+        let data: serde_yaml::Value = serde_yaml::from_str("state: {}").unwrap();
+        assert!(bank_items(&data, &SaveSchema::default()).is_empty());
+    }
+
+    #[test]
+    fn test_first_empty_backpack_slot() {
+        let yaml = r#"
+state:
+  inventory:
+    items:
+      backpack:
+        slot_0:
+          serial: "@Zero"
+        slot_1:
+          serial: ""
+        slot_2:
+          serial: "@Two"
+"#;
+        let data: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        assert!(backpack_slot_occupied(&data, &SaveSchema::default(), 0));
+        assert!(!backpack_slot_occupied(&data, &SaveSchema::default(), 1));
+        assert_eq!(
+            first_empty_backpack_slot(&data, &SaveSchema::default()),
+            Some(1)
+        );
+
+        let empty: serde_yaml::Value = serde_yaml::from_str("state: {}").unwrap();
+        assert_eq!(
+            first_empty_backpack_slot(&empty, &SaveSchema::default()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_first_empty_backpack_slot_full() {
+        let slots: String = (0..MAX_BACKPACK_SLOTS)
+            .map(|slot| format!("        slot_{}:\n          serial: \"@Item\"\n", slot))
+            .collect();
+        let yaml = format!(
+            "state:\n  inventory:\n    items:\n      backpack:\n{}",
+            slots
+        );
+        let data: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(
+            first_empty_backpack_slot(&data, &SaveSchema::default()),
+            None
+        );
+    }
+
+    fn backpack_yaml() -> &'static str {
+        r#"
+state:
+  inventory:
+    items:
+      backpack:
+        slot_12:
+          serial: "@Ugr$ZCm/&tH!t{KgK/Shxu>k"
+          state_flags: 513
+        slot_3:
+          serial: "@Ugr$ZCm/&tH!t{KgK/Shxu>k"
+          state_flags: 515
+        slot_4:
+          serial: ""
+        slot_7:
+          serial: "not a serial"
+          state_flags: 517
+"#
+    }
+
+    #[test]
+    fn test_backpack_items_sorted_and_skips_empty() {
+        let data: serde_yaml::Value = serde_yaml::from_str(backpack_yaml()).unwrap();
+        let slots: Vec<u8> = backpack_items(&data, &SaveSchema::default())
+            .iter()
+            .map(|i| i.slot)
+            .collect();
+        assert_eq!(slots, vec![3, 7, 12]);
+    }
+
+    #[test]
+    fn test_inventory_summary() {
+        let data: serde_yaml::Value = serde_yaml::from_str(backpack_yaml()).unwrap();
+        let summary = inventory_summary(&data, &SaveSchema::default());
+
+        assert_eq!(summary.backpack_items, 3);
+        assert_eq!(summary.favorites, 1);
+        assert_eq!(summary.junk, 1);
+        assert_eq!(summary.empty_slots, MAX_BACKPACK_SLOTS as usize - 3);
+        assert_eq!(
+            summary.by_type,
+            BTreeMap::from([
+                ("Vladof SMG".to_string(), 2),
+                (UNKNOWN_ITEM_TYPE.to_string(), 1),
+            ])
+        );
+        assert_eq!(item_type("not a serial"), UNKNOWN_ITEM_TYPE);
+
+        let empty: serde_yaml::Value = serde_yaml::from_str("state: {}").unwrap();
+        assert_eq!(
+            inventory_summary(&empty, &SaveSchema::default()).empty_slots,
+            MAX_BACKPACK_SLOTS as usize
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Equipped-item loadout for character saves.
+//!
+//! `state.inventory.equipped_inventory.equipped` maps `slot_N` to a
+//! one-element array holding the equipped item (or an empty array).
+
+use super::inventory::{lookup_dotted, slot_serial, sorted_slots};
+use super::{SaveSchema, StateFlags};
+
+/// An equipment slot in `equipped_inventory`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EquipSlot {
+    Weapon1,
+    Weapon2,
+    Weapon3,
+    Weapon4,
+    Shield,
+    Grenade,
+    Gear1,
+    Gear2,
+    ClassMod,
+}
+
+impl EquipSlot {
+    /// All slots in `slot_N` order
+    pub const ALL: [EquipSlot; 9] = [
+        EquipSlot::Weapon1,
+        EquipSlot::Weapon2,
+        EquipSlot::Weapon3,
+        EquipSlot::Weapon4,
+        EquipSlot::Shield,
+        EquipSlot::Grenade,
+        EquipSlot::Gear1,
+        EquipSlot::Gear2,
+        EquipSlot::ClassMod,
+    ];
+
+    /// The `N` in `slot_N`
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Look up a slot by its `slot_N` index
+    pub fn from_index(index: u8) -> Option<EquipSlot> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// YAML path to this slot's item array under the default [`SaveSchema`]
+    pub fn path(self) -> String {
+        SaveSchema::default().equip_slot(self)
+    }
+}
+
+/// An item occupying an equipment slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquippedItem {
+    pub serial: String,
+    pub flags: u32,
+    pub state_flags: StateFlags,
+}
+
+impl EquippedItem {
+    /// Create an equipped item with the default flags the game writes
+    pub fn new(serial: &str) -> Self {
+        EquippedItem {
+            serial: serial.to_string(),
+            flags: 1,
+            state_flags: StateFlags::equipped(),
+        }
+    }
+
+    fn from_slot_value(value: &serde_yaml::Value) -> Option<Self> {
+        let item = value.as_sequence()?.first()?;
+        let (serial, state_flags) = slot_serial(item)?;
+        let flags = item.get("flags").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        Some(EquippedItem {
+            serial,
+            flags,
+            state_flags,
+        })
+    }
+
+    fn to_yaml(&self) -> serde_yaml::Value {
+        let mut map = serde_yaml::Mapping::new();
+        map.insert("serial".into(), self.serial.clone().into());
+        map.insert("flags".into(), u64::from(self.flags).into());
+        map.insert("state_flags".into(), u64::from(self.state_flags.0).into());
+        serde_yaml::Value::Mapping(map)
+    }
+}
+
+/// Everything a character has equipped, by slot
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Loadout {
+    pub weapons: [Option<EquippedItem>; 4],
+    pub shield: Option<EquippedItem>,
+    pub grenade: Option<EquippedItem>,
+    pub gear: [Option<EquippedItem>; 2],
+    pub class_mod: Option<EquippedItem>,
+}
+
+impl Loadout {
+    /// Get the item in a slot
+    pub fn get(&self, slot: EquipSlot) -> Option<&EquippedItem> {
+        match slot {
+            EquipSlot::Weapon1 => self.weapons[0].as_ref(),
+            EquipSlot::Weapon2 => self.weapons[1].as_ref(),
+            EquipSlot::Weapon3 => self.weapons[2].as_ref(),
+            EquipSlot::Weapon4 => self.weapons[3].as_ref(),
+            EquipSlot::Shield => self.shield.as_ref(),
+            EquipSlot::Grenade => self.grenade.as_ref(),
+            EquipSlot::Gear1 => self.gear[0].as_ref(),
+            EquipSlot::Gear2 => self.gear[1].as_ref(),
+            EquipSlot::ClassMod => self.class_mod.as_ref(),
+        }
+    }
+
+    /// Put an item in a slot (or clear it with `None`)
+    pub fn set(&mut self, slot: EquipSlot, item: Option<EquippedItem>) {
+        *self.slot_mut(slot) = item;
+    }
+
+    fn slot_mut(&mut self, slot: EquipSlot) -> &mut Option<EquippedItem> {
+        match slot {
+            EquipSlot::Weapon1 => &mut self.weapons[0],
+            EquipSlot::Weapon2 => &mut self.weapons[1],
+            EquipSlot::Weapon3 => &mut self.weapons[2],
+            EquipSlot::Weapon4 => &mut self.weapons[3],
+            EquipSlot::Shield => &mut self.shield,
+            EquipSlot::Grenade => &mut self.grenade,
+            EquipSlot::Gear1 => &mut self.gear[0],
+            EquipSlot::Gear2 => &mut self.gear[1],
+            EquipSlot::ClassMod => &mut self.class_mod,
+        }
+    }
+
+    /// Iterate over occupied slots in `slot_N` order
+    pub fn iter(&self) -> impl Iterator<Item = (EquipSlot, &EquippedItem)> {
+        EquipSlot::ALL
+            .into_iter()
+            .filter_map(move |slot| self.get(slot).map(|item| (slot, item)))
+    }
+
+    /// The one-element (or empty) array the game stores for a slot
+    pub(crate) fn slot_value(&self, slot: EquipSlot) -> serde_yaml::Value {
+        let items = self
+            .get(slot)
+            .map(EquippedItem::to_yaml)
+            .into_iter()
+            .collect();
+        serde_yaml::Value::Sequence(items)
+    }
+}
+
+/// Read the equipped slots from character save data
+pub(crate) fn loadout(data: &serde_yaml::Value, schema: &SaveSchema) -> Loadout {
+    let mut loadout = Loadout::default();
+    let Some(equipped) = lookup_dotted(data, &schema.equipped) else {
+        return loadout;
+    };
+
+    for (index, value) in sorted_slots(equipped) {
+        let Some(slot) = u8::try_from(index).ok().and_then(EquipSlot::from_index) else {
+            continue;
+        };
+        loadout.set(slot, EquippedItem::from_slot_value(value));
+    }
+    loadout
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character_yaml() -> &'static str {
+        r#"
+state:
+  inventory:
+    equipped_inventory:
+      equipped:
+        slot_0:
+          - serial: "@Gun"
+            flags: 1
+            state_flags: 517
+        slot_4:
+          - serial: "@Shield"
+            flags: 1
+            state_flags: 1
+        slot_5: []
+        slot_8:
+          - serial: "@ClassMod"
+            flags: 1
+            state_flags: 1
+        slot_42:
+          - serial: "@Unknown"
+"#
+    }
+
+    #[test]
+    fn test_loadout_reads_slots() {
+        let data: serde_yaml::Value = serde_yaml::from_str(character_yaml()).unwrap();
+        let loadout = loadout(&data, &SaveSchema::default());
+
+        assert_eq!(loadout.weapons[0].as_ref().unwrap().serial, "@Gun");
+        assert_eq!(
+            loadout.weapons[0].as_ref().unwrap().state_flags,
+            StateFlags::from_raw(517)
+        );
+        assert!(loadout.weapons[1].is_none());
+        assert_eq!(loadout.shield.as_ref().unwrap().serial, "@Shield");
+        assert!(loadout.grenade.is_none());
+        assert_eq!(
+            loadout.get(EquipSlot::ClassMod).unwrap().serial,
+            "@ClassMod"
+        );
+
+        let slots: Vec<EquipSlot> = loadout.iter().map(|(slot, _)| slot).collect();
+        assert_eq!(
+            slots,
+            vec![EquipSlot::Weapon1, EquipSlot::Shield, EquipSlot::ClassMod]
+        );
+    }
+
+    #[test]
+    fn test_loadout_missing_section() {
+        let data: serde_yaml::Value = serde_yaml::from_str("state: {}").unwrap();
+        assert_eq!(loadout(&data, &SaveSchema::default()), Loadout::default());
+    }
+
+    #[test]
+    fn test_equip_slot_index_roundtrip() {
+        for slot in EquipSlot::ALL {
+            assert_eq!(EquipSlot::from_index(slot.index()), Some(slot));
+        }
+        assert_eq!(EquipSlot::from_index(9), None);
+        assert_eq!(
+            EquipSlot::Shield.path(),
+            "state.inventory.equipped_inventory.equipped.slot_4"
+        );
+    }
+
+    #[test]
+    fn test_slot_value_shape() {
+        let mut loadout = Loadout::default();
+        loadout.set(EquipSlot::Grenade, Some(EquippedItem::new("@Nade")));
+
+        let value = loadout.slot_value(EquipSlot::Grenade);
+        let items = value.as_sequence().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["serial"].as_str(), Some("@Nade"));
+        assert_eq!(items[0]["flags"].as_u64(), Some(1));
+
+        let empty = loadout.slot_value(EquipSlot::Shield);
+        assert!(empty.as_sequence().unwrap().is_empty());
+    }
+}
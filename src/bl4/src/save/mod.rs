@@ -3,13 +3,32 @@
 //! This module provides high-level APIs for working with Borderlands 4 save files.
 
 mod changeset;
+mod compression;
+mod diff;
 mod fod;
+mod inventory;
+mod loadout;
+mod progression;
+mod schema;
 mod state_flags;
 
-pub use changeset::ChangeSet;
+pub use changeset::{ChangeSet, ChangeWarning};
+pub use compression::Compression;
+pub use diff::PathChange;
+pub use inventory::{
+    BackpackItem, BankItem, InventorySummary, MAX_BACKPACK_SLOTS, MAX_BANK_SLOTS, UNKNOWN_ITEM_TYPE,
+};
+pub use loadout::{EquipSlot, EquippedItem, Loadout};
+pub use progression::{
+    character_xp_for_level, ExperienceEntry, EXPERIENCE_TRACKS, MAX_CHARACTER_LEVEL, SAFE_MAX_CASH,
+    SAFE_MAX_ERIDIUM,
+};
+pub use schema::SaveSchema;
 pub use state_flags::StateFlags;
 
+use std::collections::{BTreeSet, HashSet};
 use std::fmt;
+use std::io::{Read, Write};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -28,18 +47,138 @@ pub enum SaveError {
 
     #[error("FOD compression failed: {0}")]
     FodCompress(String),
+
+    #[error("Conflicting changes at: {}", .0.join(", "))]
+    ChangeConflict(Vec<String>),
+
+    #[error("Path {path} does not apply to a {kind:?} save")]
+    WrongSaveKind { path: String, kind: SaveKind },
+
+    #[error("Invalid item serial: {0}")]
+    Serial(#[from] crate::serial::SerialError),
+
+    #[error("Invalid rarity tier {0} (expected 1-5)")]
+    InvalidRarityTier(u8),
+
+    #[error("Item has no comp_0N rarity part: {0}")]
+    NoCompositionPart(String),
+
+    #[error("No {code} part in category {category}")]
+    CompositionPartNotFound { category: i64, code: String },
+
+    #[error("Unknown experience track {0:?} (expected one of: Character, Specialization)")]
+    UnknownExperienceTrack(String),
+
+    #[error("state_flags {0:#x} lack the VALID bit; the game would treat the slot as empty")]
+    MissingValidFlag(u32),
+
+    #[error("Backpack is full (all {0} slots occupied)")]
+    BackpackFull(u8),
+
+    #[error("{container} slot {slot} out of range (must be below {limit})")]
+    SlotOutOfRange {
+        container: &'static str,
+        slot: u16,
+        limit: u16,
+    },
+
+    #[error("Not a BL4 save: no top-level `state` or `domains` key")]
+    NotABl4Save,
+
+    #[error("Precondition failed at {path}: current value does not match expected")]
+    PreconditionFailed { path: String },
+
+    #[error("Invalid JSON pointer: {0}")]
+    InvalidPointer(String),
+
+    #[error("Invalid character GUID {0:?} (expected 32 hex digits)")]
+    InvalidCharGuid(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0:?} save compression needs the `zstd` feature")]
+    UnsupportedCompression(Compression),
+}
+
+/// Which kind of save a file holds, based on its top-level keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveKind {
+    /// Character save (`N.sav`) with a top-level `state` tree
+    Character,
+    /// Profile save (`profile.sav`) with a top-level `domains` tree (bank, cosmetics)
+    Profile,
+    /// Neither tree present
+    Unknown,
+}
+
+impl SaveKind {
+    /// Guess which kind of save a path belongs to from its root key
+    pub fn for_path(path: &str) -> SaveKind {
+        match path.split(['.', '[']).next() {
+            Some("state") => SaveKind::Character,
+            Some("domains") => SaveKind::Profile,
+            _ => SaveKind::Unknown,
+        }
+    }
 }
 
 /// Represents a loaded save file with query/modify capabilities
+#[derive(Clone)]
 pub struct SaveFile {
     data: serde_yaml::Value,
+    /// Paths written since load or the last [`SaveFile::mark_clean`]
+    dirty: BTreeSet<String>,
+    /// Dirty paths whose final key was added by [`SaveFile::insert`]
+    inserted: HashSet<String>,
+    schema: SaveSchema,
 }
 
 impl SaveFile {
     /// Parse a save file from decrypted YAML data
+    ///
+    /// Fails with [`SaveError::NotABl4Save`] unless the document has a
+    /// `state` (character) or `domains` (profile) root.
     pub fn from_yaml(yaml_data: &[u8]) -> Result<Self, SaveError> {
+        Self::from_yaml_unchecked(yaml_data)?.checked()
+    }
+
+    /// Parse any YAML document as a save, skipping the structural check
+    ///
+    /// For partial saves and test fixtures; accessors on an unrecognized
+    /// document just return `None`.
+    pub fn from_yaml_unchecked(yaml_data: &[u8]) -> Result<Self, SaveError> {
         let data = serde_yaml::from_slice(yaml_data)?;
-        Ok(SaveFile { data })
+        Ok(SaveFile {
+            data,
+            dirty: BTreeSet::new(),
+            inserted: HashSet::new(),
+            schema: SaveSchema::default(),
+        })
+    }
+
+    /// Parse a save file from a reader yielding decrypted YAML
+    ///
+    /// gzip- and zstd-compressed YAML is recognized by its magic number and
+    /// decompressed on the fly; zstd needs the `zstd` feature. Applies the
+    /// same structural check as [`SaveFile::from_yaml`].
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, SaveError> {
+        let reader = compression::decompressing_reader(reader)?;
+        let data = serde_yaml::from_reader(reader)?;
+        SaveFile {
+            data,
+            dirty: BTreeSet::new(),
+            inserted: HashSet::new(),
+            schema: SaveSchema::default(),
+        }
+        .checked()
+    }
+
+    fn checked(self) -> Result<Self, SaveError> {
+        match self.kind() {
+            SaveKind::Unknown => Err(SaveError::NotABl4Save),
+            _ => Ok(self),
+        }
     }
 
     /// Serialize the save file back to YAML
@@ -48,17 +187,247 @@ impl SaveFile {
         Ok(yaml_string.into_bytes())
     }
 
+    /// Serialize the save file as YAML into a writer (same output as [`SaveFile::to_yaml`])
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), SaveError> {
+        serde_yaml::to_writer(writer, &self.data)?;
+        Ok(())
+    }
+
+    /// Serialize the save file as YAML compressed with `compression`
+    ///
+    /// [`SaveFile::from_reader`] reads the result back without being told
+    /// the format.
+    pub fn to_writer_compressed<W: Write>(
+        &self,
+        writer: W,
+        compression: Compression,
+    ) -> Result<(), SaveError> {
+        compression::write_compressed(writer, compression, |w| self.to_writer(w))
+    }
+
+    /// Serialize the save file as YAML with every mapping's keys sorted
+    ///
+    /// Two saves holding the same data produce byte-identical output whatever
+    /// order their keys were in, so save/load cycles and version control only
+    /// show real changes.
+    pub fn to_yaml_canonical(&self) -> String {
+        serde_yaml::to_string(&canonical_value(&self.data)).expect("YAML value always serializes")
+    }
+
+    /// Read inventory containers from the paths in `schema` instead of the default ones
+    pub fn with_schema(mut self, schema: SaveSchema) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// Paths the inventory accessors read from
+    pub fn schema(&self) -> &SaveSchema {
+        &self.schema
+    }
+
+    /// Detect whether this is a character or profile save
+    pub fn kind(&self) -> SaveKind {
+        if self.data.get("state").is_some() {
+            SaveKind::Character
+        } else if self.data.get("domains").is_some() {
+            SaveKind::Profile
+        } else {
+            SaveKind::Unknown
+        }
+    }
+
     /// Query a value at a YAML path (e.g. "state.currencies.cash" or "state.experience\[0\].level")
     pub fn get(&self, path: &str) -> Result<&serde_yaml::Value, SaveError> {
         query_yaml_path(&self.data, path)
     }
 
+    /// Read an unsigned integer at a YAML path
+    ///
+    /// Like the other typed accessors, returns None both when the path is
+    /// missing and when the value there has another type.
+    pub fn get_u64(&self, path: &str) -> Option<u64> {
+        self.get(path).ok()?.as_u64()
+    }
+
+    /// Read a signed integer at a YAML path
+    pub fn get_i64(&self, path: &str) -> Option<i64> {
+        self.get(path).ok()?.as_i64()
+    }
+
+    /// Read a number at a YAML path; integers convert to `f64`
+    pub fn get_f64(&self, path: &str) -> Option<f64> {
+        self.get(path).ok()?.as_f64()
+    }
+
+    /// Read a string at a YAML path
+    pub fn get_str(&self, path: &str) -> Option<&str> {
+        self.get(path).ok()?.as_str()
+    }
+
+    /// Read a boolean at a YAML path
+    pub fn get_bool(&self, path: &str) -> Option<bool> {
+        self.get(path).ok()?.as_bool()
+    }
+
+    /// Query a value by RFC 6901 JSON Pointer (e.g. `/state/experience/0/level`)
+    ///
+    /// The pointer is translated to the dotted syntax of [`SaveFile::get`],
+    /// which stays the primary API. All-digit segments are array indices on
+    /// the key before them, and `~1`/`~0` unescape to `/`/`~`. Pointers the
+    /// dotted syntax can't express fail with [`SaveError::InvalidPointer`]:
+    /// the empty (root) pointer, empty keys, keys containing `.`, `[` or `]`,
+    /// and indices that don't directly follow a key.
+    pub fn get_ptr(&self, ptr: &str) -> Result<&serde_yaml::Value, SaveError> {
+        self.get(&pointer_to_path(ptr)?)
+    }
+
+    /// Set a value by RFC 6901 JSON Pointer (see [`SaveFile::get_ptr`])
+    pub fn set_ptr(&mut self, ptr: &str, value: serde_yaml::Value) -> Result<(), SaveError> {
+        self.set(&pointer_to_path(ptr)?, value)
+    }
+
+    /// List the child keys under a YAML path (empty path = root)
+    ///
+    /// Mappings yield their key names; sequences yield `[0]`..`[n-1]`, ready to
+    /// append to the path. Scalars and missing paths have no children.
+    pub fn keys(&self, prefix: &str) -> Vec<String> {
+        let node = if prefix.is_empty() {
+            &self.data
+        } else {
+            match query_yaml_path(&self.data, prefix) {
+                Ok(node) => node,
+                Err(_) => return Vec::new(),
+            }
+        };
+
+        match node {
+            serde_yaml::Value::Mapping(map) => map.keys().map(yaml_key_name).collect(),
+            serde_yaml::Value::Sequence(seq) => {
+                (0..seq.len()).map(|i| format!("[{}]", i)).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// List every leaf value that differs between this save and `other`
+    ///
+    /// Paths use the same syntax as [`SaveFile::get`], in document order.
+    pub fn diff(&self, other: &SaveFile) -> Vec<PathChange> {
+        let mut changes = Vec::new();
+        diff::diff_values("", &self.data, &other.data, &mut changes);
+        changes
+    }
+
     /// Set a value at a YAML path
     pub fn set(&mut self, path: &str, value: serde_yaml::Value) -> Result<(), SaveError> {
-        set_yaml_path(&mut self.data, path, value)
+        set_yaml_path(&mut self.data, path, value, false)?;
+        self.dirty.insert(path.to_string());
+        Ok(())
+    }
+
+    /// Set a value at a YAML path, adding the final key if it is missing
+    ///
+    /// Like [`SaveFile::set`], but a plain key at the end of the path (e.g.
+    /// `slot_7`) is added to its parent mapping when absent. Everything
+    /// before it must already exist.
+    pub fn insert(&mut self, path: &str, value: serde_yaml::Value) -> Result<(), SaveError> {
+        set_yaml_path(&mut self.data, path, value, true)?;
+        self.dirty.insert(path.to_string());
+        self.inserted.insert(path.to_string());
+        Ok(())
+    }
+
+    /// Paths written since the save was loaded or last marked clean, sorted
+    ///
+    /// Every successful [`SaveFile::set`] (and so every setter and applied
+    /// [`ChangeSet`]) records its path, and the map fill methods record the
+    /// `foddatas` sequence. A path inside another listed path is folded into
+    /// it. Paths are recorded on write, so setting a value to what it already
+    /// was still lists it; use [`SaveFile::diff`] to compare contents.
+    pub fn dirty_paths(&self) -> Vec<String> {
+        let mut kept: HashSet<&str> = HashSet::new();
+        let mut paths: Vec<String> = Vec::new();
+        for path in &self.dirty {
+            // An ancestor sorts before its descendants, though not always
+            // right before them (`a.b1` < `a.b10` < `a.b1[0]`), so check
+            // every prefix that ends at a path boundary
+            let covered = path
+                .match_indices(['.', '['])
+                .any(|(i, _)| kept.contains(&path[..i]));
+            if !covered {
+                kept.insert(path);
+                paths.push(path.clone());
+            }
+        }
+        paths
+    }
+
+    /// Whether anything was written since load or the last [`SaveFile::mark_clean`]
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Forget the recorded paths, e.g. after writing the save to disk
+    pub fn mark_clean(&mut self) {
+        self.dirty.clear();
+        self.inserted.clear();
+    }
+
+    /// The current value at every [`SaveFile::dirty_paths`] entry, as a patch
+    ///
+    /// A `.sav` is a single compressed, encrypted YAML document, so the file
+    /// itself is always rewritten whole. This patch is what an editor can
+    /// keep instead: [`ChangeSet::to_yaml`] writes it as a small preset, and
+    /// applying it to a fresh load of the original save reproduces the
+    /// edits. Paths written with [`SaveFile::insert`] are staged with
+    /// [`ChangeSet::insert`], so keys the original lacks are created again.
+    ///
+    /// # Example
+    /// ```
+    /// use bl4::SaveFile;
+    ///
+    /// let yaml = b"state:\n  currencies:\n    cash: 10\n    eridium: 5\n";
+    /// let mut save = SaveFile::from_yaml(yaml).unwrap();
+    /// save.set_cash(500).unwrap();
+    /// assert_eq!(save.dirty_paths(), vec!["state.currencies.cash"]);
+    ///
+    /// let mut original = SaveFile::from_yaml(yaml).unwrap();
+    /// save.dirty_changes().apply(&mut original).unwrap();
+    /// assert_eq!(original.get_cash(), Some(500));
+    /// ```
+    pub fn dirty_changes(&self) -> ChangeSet {
+        let mut changes = ChangeSet::new();
+        for path in self.dirty_paths() {
+            if let Ok(value) = self.get(&path) {
+                if self.inserted.contains(&path) {
+                    changes.insert(path, value.clone());
+                } else {
+                    changes.add(path, value.clone());
+                }
+            }
+        }
+        changes
+    }
+
+    /// Compute the checksum BL4 stores for a YAML payload (Adler-32 of the bytes)
+    ///
+    /// Use this on the output of [`SaveFile::to_yaml`] when writing a save back.
+    pub fn compute_checksum(yaml_data: &[u8]) -> u32 {
+        crate::crypto::compute_checksum(yaml_data)
+    }
+
+    /// Verify a decrypted, still-compressed save blob (see [`crate::crypto::decrypt_sav_raw`])
+    ///
+    /// The checksum covers the uncompressed YAML, and is stored with the YAML
+    /// length in the 8 bytes after the zlib stream.
+    pub fn verify_checksum(bytes: &[u8]) -> bool {
+        crate::crypto::verify_checksum(bytes)
     }
 
     /// Parse a string value into the appropriate YAML type (number, bool, or string)
+    ///
+    /// Quoted values, `@`-prefixed serials, and leading-zero tokens are kept as
+    /// strings. See the module-level `parse_value` rules for details.
     pub fn parse_value(value_str: &str) -> serde_yaml::Value {
         parse_value(value_str)
     }
@@ -150,6 +519,53 @@ impl SaveFile {
         )
     }
 
+    /// Get the stack count for an ammo type (e.g. "pistol", "sniper", "repairkit")
+    ///
+    /// Backpack and bank slots hold one item each and have no quantity field;
+    /// ammo and repair kits are the only stacked counts in a character save.
+    pub fn get_ammo(&self, kind: &str) -> Option<u64> {
+        self.data
+            .get("state")
+            .and_then(|s| s.get("ammo"))
+            .and_then(|a| a.get(kind))
+            .and_then(|v| v.as_u64())
+    }
+
+    /// Set the stack count for an ammo type
+    pub fn set_ammo(&mut self, kind: &str, amount: u64) -> Result<(), SaveError> {
+        self.set(
+            &format!("state.ammo.{}", kind),
+            serde_yaml::Value::Number(amount.into()),
+        )
+    }
+
+    /// Total time played on this character, in seconds (`state.total_playtime`)
+    ///
+    /// Stored as a float with sub-second precision. No last-played or
+    /// last-saved timestamp has been found in the YAML (neither under
+    /// `state` nor `save_game_header`); the `.sav` file's modification
+    /// time is the only record of when it was written.
+    pub fn playtime_seconds(&self) -> Option<f64> {
+        self.data
+            .get("state")
+            .and_then(|s| s.get("total_playtime"))
+            .and_then(|v| v.as_f64())
+    }
+
+    /// [`SaveFile::playtime_seconds`] as a `Duration`, if it is a valid one
+    pub fn playtime(&self) -> Option<std::time::Duration> {
+        self.playtime_seconds()
+            .and_then(|secs| std::time::Duration::try_from_secs_f64(secs).ok())
+    }
+
+    /// Set the total playtime in seconds
+    pub fn set_playtime_seconds(&mut self, seconds: f64) -> Result<(), SaveError> {
+        self.set(
+            "state.total_playtime",
+            serde_yaml::Value::Number(seconds.into()),
+        )
+    }
+
     /// Get character level and XP
     pub fn get_character_level(&self) -> Option<(u64, u64)> {
         self.data
@@ -194,12 +610,48 @@ impl SaveFile {
         )
     }
 
+    /// Every experience track in `state.experience`, in array order
+    ///
+    /// Unlike [`SaveFile::get_character_level`] and
+    /// [`SaveFile::get_specialization_level`], this doesn't assume which
+    /// track sits at which index, and entries missing a field are still listed.
+    pub fn experience_entries(&self) -> Vec<ExperienceEntry> {
+        progression::experience_entries(&self.data)
+    }
+
+    /// Enumerate bank items (profile.sav) in slot order, skipping empty slots
+    pub fn bank_items(&self) -> impl Iterator<Item = BankItem> {
+        inventory::bank_items(&self.data, &self.schema).into_iter()
+    }
+
+    /// Enumerate backpack items (character saves) in slot order, skipping empty slots
+    pub fn backpack_items(&self) -> impl Iterator<Item = BackpackItem> {
+        inventory::backpack_items(&self.data, &self.schema).into_iter()
+    }
+
+    /// Count backpack items by label and weapon/gear type (character saves)
+    pub fn inventory_summary(&self) -> InventorySummary {
+        inventory::inventory_summary(&self.data, &self.schema)
+    }
+
+    /// Lowest-numbered backpack slot without an item (character saves)
+    pub fn first_empty_backpack_slot(&self) -> Option<u8> {
+        inventory::first_empty_backpack_slot(&self.data, &self.schema)
+    }
+
+    /// Read the equipped items (character saves), keyed by slot
+    pub fn loadout(&self) -> Loadout {
+        loadout::loadout(&self.data, &self.schema)
+    }
+
     /// Reveal the entire map (all zones, or a specific zone).
     ///
     /// Replaces FOD data with fully-revealed grids (all 0xFF).
     /// Returns the number of zones modified.
     pub fn reveal_map(&mut self, zone: Option<&str>) -> Result<usize, SaveError> {
-        fod::reveal_map(&mut self.data, zone)
+        let count = fod::reveal_map(&mut self.data, zone)?;
+        self.mark_fod_dirty(count);
+        Ok(count)
     }
 
     /// Clear the entire map (all zones, or a specific zone).
@@ -207,7 +659,16 @@ impl SaveFile {
     /// Replaces FOD data with fully-fogged grids (all 0x00).
     /// Returns the number of zones modified.
     pub fn clear_map(&mut self, zone: Option<&str>) -> Result<usize, SaveError> {
-        fod::clear_map(&mut self.data, zone)
+        let count = fod::clear_map(&mut self.data, zone)?;
+        self.mark_fod_dirty(count);
+        Ok(count)
+    }
+
+    fn mark_fod_dirty(&mut self, zones_changed: usize) {
+        if zones_changed > 0 {
+            let path = fod::foddatas_path(&self.data);
+            self.dirty.insert(path.to_string());
+        }
     }
 }
 
@@ -259,10 +720,84 @@ fn query_yaml_path<'a>(
     Ok(current)
 }
 
+/// Translate a JSON Pointer (`/state/experience/0/level`) to a dotted path
+/// (`state.experience[0].level`)
+fn pointer_to_path(ptr: &str) -> Result<String, SaveError> {
+    let invalid = || SaveError::InvalidPointer(ptr.to_string());
+    let rest = ptr.strip_prefix('/').ok_or_else(invalid)?;
+    let mut path = String::new();
+
+    for raw in rest.split('/') {
+        if raw.split('~').skip(1).any(|s| !s.starts_with(['0', '1'])) {
+            return Err(invalid());
+        }
+        let segment = raw.replace("~1", "/").replace("~0", "~");
+
+        let is_index = !segment.is_empty()
+            && segment.bytes().all(|b| b.is_ascii_digit())
+            && (segment == "0" || !segment.starts_with('0'));
+        if is_index {
+            if path.is_empty() || path.ends_with(']') {
+                return Err(invalid());
+            }
+            path.push_str(&format!("[{}]", segment));
+        } else {
+            if segment.is_empty() || segment.contains(['.', '[', ']']) {
+                return Err(invalid());
+            }
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(&segment);
+        }
+    }
+
+    Ok(path)
+}
+
+fn yaml_key_name(key: &serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Deep copy of a value with mapping keys sorted by their path name
+fn canonical_value(value: &serde_yaml::Value) -> serde_yaml::Value {
+    match value {
+        serde_yaml::Value::Mapping(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_cached_key(|(k, _)| yaml_key_name(k));
+            serde_yaml::Value::Mapping(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), canonical_value(v)))
+                    .collect(),
+            )
+        }
+        serde_yaml::Value::Sequence(seq) => {
+            serde_yaml::Value::Sequence(seq.iter().map(canonical_value).collect())
+        }
+        serde_yaml::Value::Tagged(tagged) => {
+            serde_yaml::Value::Tagged(Box::new(serde_yaml::value::TaggedValue {
+                tag: tagged.tag.clone(),
+                value: canonical_value(&tagged.value),
+            }))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Write `new_value` at `path`; with `create`, a missing final key is added
 fn set_yaml_path(
     value: &mut serde_yaml::Value,
     path: &str,
     new_value: serde_yaml::Value,
+    create: bool,
 ) -> Result<(), SaveError> {
     let parts: Vec<&str> = path.split('.').collect();
     let mut current = value;
@@ -293,10 +828,17 @@ fn set_yaml_path(
                     .ok_or(SaveError::IndexOutOfBounds(index))?;
             }
         } else if is_last {
-            *current
-                .get_mut(part)
-                .ok_or_else(|| SaveError::KeyNotFound(part.to_string()))? = new_value;
-            return Ok(());
+            if let Some(slot) = current.get_mut(part) {
+                *slot = new_value;
+                return Ok(());
+            }
+            match current.as_mapping_mut() {
+                Some(map) if create => {
+                    map.insert(serde_yaml::Value::String(part.to_string()), new_value);
+                    return Ok(());
+                }
+                _ => return Err(SaveError::KeyNotFound(part.to_string())),
+            }
         } else {
             current = current
                 .get_mut(part)
@@ -307,19 +849,25 @@ fn set_yaml_path(
     Ok(())
 }
 
+/// Parse a user-supplied string into a YAML value.
+///
+/// Rules, applied in order:
+/// - Values wrapped in matching `"` or `'` quotes are always strings (quotes stripped)
+/// - `@`-prefixed tokens (item serials) are always strings
+/// - Tokens with a leading zero (e.g. `0123`, `-007`) stay strings so IDs aren't mangled
+/// - `true`/`false` (case-insensitive) become booleans
+/// - Integers become numbers (i64, falling back to u64 for large values)
+/// - Decimal/exponent floats become numbers (`inf`/`nan` are left as strings)
+/// - Everything else is a string
 pub(crate) fn parse_value(value_str: &str) -> serde_yaml::Value {
-    // Try to parse as number first
-    if let Ok(num) = value_str.parse::<i64>() {
-        return serde_yaml::Value::Number(num.into());
-    }
-    if let Ok(num) = value_str.parse::<u64>() {
-        return serde_yaml::Value::Number(num.into());
+    if let Some(inner) = strip_quotes(value_str) {
+        return serde_yaml::Value::String(inner.to_string());
     }
-    if let Ok(num) = value_str.parse::<f64>() {
-        return serde_yaml::Value::Number(serde_yaml::Number::from(num));
+
+    if value_str.starts_with('@') || has_leading_zero(value_str) {
+        return serde_yaml::Value::String(value_str.to_string());
     }
 
-    // Try boolean
     if value_str.eq_ignore_ascii_case("true") {
         return serde_yaml::Value::Bool(true);
     }
@@ -327,10 +875,50 @@ pub(crate) fn parse_value(value_str: &str) -> serde_yaml::Value {
         return serde_yaml::Value::Bool(false);
     }
 
-    // Default to string
+    if let Ok(num) = value_str.parse::<i64>() {
+        return serde_yaml::Value::Number(num.into());
+    }
+    if let Ok(num) = value_str.parse::<u64>() {
+        return serde_yaml::Value::Number(num.into());
+    }
+    if is_float_literal(value_str) {
+        if let Ok(num) = value_str.parse::<f64>() {
+            return serde_yaml::Value::Number(serde_yaml::Number::from(num));
+        }
+    }
+
     serde_yaml::Value::String(value_str.to_string())
 }
 
+/// Return the inner text if the value is wrapped in matching single or double quotes
+fn strip_quotes(value_str: &str) -> Option<&str> {
+    let bytes = value_str.as_bytes();
+    if bytes.len() < 2 {
+        return None;
+    }
+    let (first, last) = (bytes[0], bytes[bytes.len() - 1]);
+    if first == last && (first == b'"' || first == b'\'') {
+        Some(&value_str[1..value_str.len() - 1])
+    } else {
+        None
+    }
+}
+
+/// Check for a numeric-looking token with a leading zero, like `0123` or `-042`
+fn has_leading_zero(value_str: &str) -> bool {
+    let digits = value_str.strip_prefix(['-', '+']).unwrap_or(value_str);
+    let bytes = digits.as_bytes();
+    bytes.len() > 1 && bytes[0] == b'0' && bytes[1].is_ascii_digit()
+}
+
+/// Check that a token only contains characters of a plain decimal float literal
+fn is_float_literal(value_str: &str) -> bool {
+    value_str.bytes().any(|b| b.is_ascii_digit())
+        && value_str
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,6 +934,10 @@ state:
     cash: 1000
     eridium: 50
     golden_key: shift
+  ammo:
+    pistol: 148
+    repairkit: 10
+  total_playtime: 4050.5
   experience:
     - type: Character
       level: 10
@@ -364,6 +956,47 @@ save_game_header:
 "#
     }
 
+    #[test]
+    fn test_save_kind() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert_eq!(save.kind(), SaveKind::Character);
+
+        let profile = SaveFile::from_yaml(b"domains:\n  local:\n    shared: {}\n").unwrap();
+        assert_eq!(profile.kind(), SaveKind::Profile);
+
+        let other = SaveFile::from_yaml_unchecked(b"foo: 1\n").unwrap();
+        assert_eq!(other.kind(), SaveKind::Unknown);
+    }
+
+    #[test]
+    fn test_from_yaml_rejects_non_save() {
+        for yaml in [&b"foo: 1\n"[..], b"- state\n- domains\n", b"state"] {
+            assert!(matches!(
+                SaveFile::from_yaml(yaml),
+                Err(SaveError::NotABl4Save)
+            ));
+        }
+        assert!(matches!(
+            SaveFile::from_reader(&b"settings: {}\n"[..]),
+            Err(SaveError::NotABl4Save)
+        ));
+        assert!(matches!(
+            SaveFile::from_yaml(b"state: [unclosed"),
+            Err(SaveError::YamlParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_kind_for_path() {
+        assert_eq!(SaveKind::for_path("state.currencies.cash"), SaveKind::Character);
+        assert_eq!(SaveKind::for_path("state[0]"), SaveKind::Character);
+        assert_eq!(
+            SaveKind::for_path("domains.local.shared.inventory"),
+            SaveKind::Profile
+        );
+        assert_eq!(SaveKind::for_path("save_game_header.guid"), SaveKind::Unknown);
+    }
+
     #[test]
     fn test_save_file_from_yaml() {
         let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
@@ -377,6 +1010,34 @@ save_game_header:
         assert_eq!(name.as_str(), Some("TestChar"));
     }
 
+    #[test]
+    fn test_typed_getters() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert_eq!(save.get_u64("state.currencies.cash"), Some(1000));
+        assert_eq!(save.get_i64("state.experience[0].level"), Some(10));
+        assert_eq!(save.get_f64("state.total_playtime"), Some(4050.5));
+        assert_eq!(save.get_f64("state.currencies.cash"), Some(1000.0));
+        assert_eq!(save.get_str("state.char_name"), Some("TestChar"));
+
+        let save = SaveFile::from_yaml(b"state:\n  debt: -5\n  hardcore: true\n").unwrap();
+        assert_eq!(save.get_i64("state.debt"), Some(-5));
+        assert_eq!(save.get_bool("state.hardcore"), Some(true));
+    }
+
+    #[test]
+    fn test_typed_getters_mismatch() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert_eq!(save.get_u64("state.char_name"), None);
+        assert_eq!(save.get_u64("state.total_playtime"), None);
+        assert_eq!(save.get_str("state.currencies.cash"), None);
+        assert_eq!(save.get_bool("state.currencies"), None);
+        assert_eq!(save.get_f64("state.currencies.golden_key"), None);
+
+        // Missing paths are None too, not an error
+        assert_eq!(save.get_u64("state.currencies.missing"), None);
+        assert_eq!(save.get_i64("state.experience[9].level"), None);
+    }
+
     #[test]
     fn test_query_nested_path() {
         let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
@@ -394,6 +1055,62 @@ save_game_header:
         assert_eq!(spec_level.as_u64(), Some(5));
     }
 
+    #[test]
+    fn test_keys() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+
+        assert_eq!(save.keys(""), vec!["state", "save_game_header"]);
+        assert_eq!(
+            save.keys("state.currencies"),
+            vec!["cash", "eridium", "golden_key"]
+        );
+        assert_eq!(save.keys("state.experience"), vec!["[0]", "[1]"]);
+        assert_eq!(
+            save.keys("state.experience[1]"),
+            vec!["type", "level", "points"]
+        );
+        assert!(save.keys("state.char_name").is_empty());
+        assert!(save.keys("state.nonexistent").is_empty());
+    }
+
+    #[test]
+    fn test_to_yaml_canonical() {
+        let a_yaml = b"b: 1\na:\n  y: [2, {d: 1, c: 2}]\n  x: !tag {f: 1, e: 2}\n";
+        let b_yaml = b"a:\n  x: !tag {e: 2, f: 1}\n  y: [2, {c: 2, d: 1}]\nb: 1\n";
+        let a = SaveFile::from_yaml_unchecked(a_yaml).unwrap();
+        let b = SaveFile::from_yaml_unchecked(b_yaml).unwrap();
+
+        let canonical = a.to_yaml_canonical();
+        assert_eq!(canonical, b.to_yaml_canonical());
+        assert!(canonical.starts_with("a:\n  x: !tag"));
+        assert!(canonical.find("e: 2") < canonical.find("f: 1"));
+        assert!(canonical.find("c: 2") < canonical.find("d: 1"));
+
+        let reloaded = SaveFile::from_yaml_unchecked(canonical.as_bytes()).unwrap();
+        assert_eq!(reloaded.to_yaml_canonical(), canonical);
+        assert!(reloaded.diff(&a).is_empty());
+    }
+
+    #[test]
+    fn test_diff_after_changeset() {
+        let original = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut edited = original.clone();
+
+        let mut changes = ChangeSet::new();
+        changes.add_parsed("state.currencies.cash".to_string(), "5000");
+        changes.add_parsed("state.experience[0].level".to_string(), "50");
+        changes.apply(&mut edited).unwrap();
+
+        let diff = original.diff(&edited);
+        let paths: Vec<&str> = diff.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["state.currencies.cash", "state.experience[0].level"]
+        );
+        assert_eq!(diff[0].to_string(), "~ state.currencies.cash: 1000 -> 5000");
+        assert!(original.diff(&original).is_empty());
+    }
+
     #[test]
     fn test_query_invalid_path() {
         let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
@@ -442,6 +1159,79 @@ save_game_header:
         assert_eq!(points.as_u64(), Some(99999));
     }
 
+    #[test]
+    fn test_insert_creates_final_key() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert!(matches!(
+            save.set("state.currencies.vault_card", 5.into()),
+            Err(SaveError::KeyNotFound(_))
+        ));
+
+        save.insert("state.currencies.vault_card", 5.into())
+            .unwrap();
+        assert_eq!(save.get_u64("state.currencies.vault_card"), Some(5));
+        // Existing keys are overwritten like set
+        save.insert("state.currencies.cash", 7.into()).unwrap();
+        assert_eq!(save.get_cash(), Some(7));
+
+        // Only the last key; a missing parent is still an error
+        assert!(matches!(
+            save.insert("state.missing.key", 1.into()),
+            Err(SaveError::KeyNotFound(_))
+        ));
+        assert!(save.insert("state.char_name.inner", 1.into()).is_err());
+    }
+
+    #[test]
+    fn test_pointer_matches_dotted_path() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        for (ptr, path) in [
+            ("/state/currencies/cash", "state.currencies.cash"),
+            ("/state/experience/1/level", "state.experience[1].level"),
+            (
+                "/state/inventory/items/backpack/slot_0/serial",
+                "state.inventory.items.backpack.slot_0.serial",
+            ),
+        ] {
+            assert_eq!(pointer_to_path(ptr).unwrap(), path);
+            assert_eq!(save.get_ptr(ptr).unwrap(), save.get(path).unwrap());
+        }
+
+        save.set_ptr(
+            "/state/experience/0/points",
+            serde_yaml::Value::Number(99999.into()),
+        )
+        .unwrap();
+        let points = save.get("state.experience[0].points").unwrap();
+        assert_eq!(points.as_u64(), Some(99999));
+
+        save.set_ptr("/state/char_name", "Ptr".into()).unwrap();
+        assert_eq!(save.get_character_name(), Some("Ptr"));
+    }
+
+    #[test]
+    fn test_pointer_errors() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert_eq!(pointer_to_path("/a~1b/c~0d").unwrap(), "a/b.c~d");
+        assert_eq!(pointer_to_path("/a/01").unwrap(), "a.01");
+
+        for ptr in ["", "state", "/", "/a//b", "/0", "/a/0/1", "/a.b", "/a~2"] {
+            assert!(
+                matches!(save.get_ptr(ptr), Err(SaveError::InvalidPointer(_))),
+                "{:?} should be rejected",
+                ptr
+            );
+        }
+        assert!(matches!(
+            save.get_ptr("/state/experience/9/level"),
+            Err(SaveError::IndexOutOfBounds(9))
+        ));
+        assert!(matches!(
+            save.get_ptr("/state/nope"),
+            Err(SaveError::KeyNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_parse_value_integer() {
         let val = SaveFile::parse_value("123");
@@ -478,6 +1268,83 @@ eridium: 6666
         assert_eq!(save.get_eridium(), Some(6666));
     }
 
+    #[test]
+    fn test_dirty_paths() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert!(!save.is_dirty());
+
+        save.set_cash(2000).unwrap();
+        save.set("state.experience[0].level", 20.into()).unwrap();
+        assert!(save.set("state.missing.key", 1.into()).is_err());
+        assert_eq!(
+            save.dirty_paths(),
+            vec!["state.currencies.cash", "state.experience[0].level"]
+        );
+
+        // An ancestor absorbs its descendants, but not siblings sharing a prefix
+        save.set_raw("state.currencies", "cash: 5\neridium: 6\n")
+            .unwrap();
+        save.set("state.experience", serde_yaml::Value::Sequence(vec![]))
+            .unwrap();
+        save.set("state.char_name", "A".into()).unwrap();
+        save.set("state.class", "B".into()).unwrap();
+        assert_eq!(
+            save.dirty_paths(),
+            vec![
+                "state.char_name",
+                "state.class",
+                "state.currencies",
+                "state.experience"
+            ]
+        );
+
+        save.mark_clean();
+        assert!(!save.is_dirty());
+        assert!(save.dirty_paths().is_empty());
+
+        // `x.slot_10` sorts between `x.slot_1` and its descendant
+        save.dirty = ["x.slot_1", "x.slot_10", "x.slot_1[0]"]
+            .map(String::from)
+            .into();
+        assert_eq!(save.dirty_paths(), vec!["x.slot_1", "x.slot_10"]);
+    }
+
+    #[test]
+    fn test_dirty_changes_replay() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        save.set_character_name("Renamed").unwrap();
+        save.set("state.experience[1].points", 9999.into()).unwrap();
+
+        let patch = save.dirty_changes();
+        assert_eq!(patch.len(), 2);
+
+        let mut original = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        ChangeSet::from_yaml(&patch.to_yaml())
+            .unwrap()
+            .apply(&mut original)
+            .unwrap();
+        assert!(original.diff(&save).is_empty());
+    }
+
+    #[test]
+    fn test_dirty_changes_replay_inserted_key() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        save.insert("state.currencies.vault_card", 3.into())
+            .unwrap();
+
+        let mut original = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        save.dirty_changes().apply(&mut original).unwrap();
+        assert_eq!(original.get_u64("state.currencies.vault_card"), Some(3));
+    }
+
+    #[test]
+    fn test_map_fill_marks_foddatas_dirty() {
+        let yaml = b"state: {}\ngbx_discovery_pc:\n  foddatas:\n    - levelname: Zone1\n      foddata: ''\n";
+        let mut save = SaveFile::from_yaml(yaml).unwrap();
+        assert!(save.reveal_map(Some("zone1")).is_ok());
+        assert_eq!(save.dirty_paths(), vec!["gbx_discovery_pc.foddatas"]);
+    }
+
     #[test]
     fn test_convenience_methods() {
         let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
@@ -487,6 +1354,9 @@ eridium: 6666
         assert_eq!(save.get_difficulty(), Some("Normal"));
         assert_eq!(save.get_cash(), Some(1000));
         assert_eq!(save.get_eridium(), Some(50));
+        assert_eq!(save.get_ammo("pistol"), Some(148));
+        assert_eq!(save.get_ammo("repairkit"), Some(10));
+        assert_eq!(save.get_ammo("sniper"), None);
         assert_eq!(save.get_character_level(), Some((10, 5000)));
         assert_eq!(save.get_specialization_level(), Some((5, 2500)));
     }
@@ -504,6 +1374,9 @@ eridium: 6666
         save.set_eridium(88888).unwrap();
         assert_eq!(save.get_eridium(), Some(88888));
 
+        save.set_ammo("pistol", 40).unwrap();
+        assert_eq!(save.get_ammo("pistol"), Some(40));
+
         save.set_character_xp(99999).unwrap();
         assert_eq!(save.get_character_level(), Some((10, 99999)));
 
@@ -511,6 +1384,28 @@ eridium: 6666
         assert_eq!(save.get_specialization_level(), Some((5, 11111)));
     }
 
+    #[test]
+    fn test_playtime() {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        assert_eq!(save.playtime_seconds(), Some(4050.5));
+        assert_eq!(
+            save.playtime(),
+            Some(std::time::Duration::from_millis(4_050_500))
+        );
+
+        save.set_playtime_seconds(60.0).unwrap();
+        assert_eq!(save.playtime_seconds(), Some(60.0));
+
+        let mut changes = ChangeSet::new();
+        changes.set_playtime_seconds(-1.0);
+        changes.apply(&mut save).unwrap();
+        assert_eq!(save.playtime_seconds(), Some(-1.0));
+        assert_eq!(save.playtime(), None);
+
+        let empty = SaveFile::from_yaml(b"state: {}\n").unwrap();
+        assert_eq!(empty.playtime_seconds(), None);
+    }
+
     #[test]
     fn test_to_yaml_roundtrip() {
         let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
@@ -521,6 +1416,53 @@ eridium: 6666
         assert_eq!(save2.get_cash(), Some(1000));
     }
 
+    #[test]
+    fn test_reader_writer_roundtrip() {
+        let mut save = SaveFile::from_reader(test_save_yaml().as_bytes()).unwrap();
+        save.set_cash(2000).unwrap();
+
+        let mut out = Vec::new();
+        save.to_writer(&mut out).unwrap();
+        assert_eq!(out, save.to_yaml().unwrap());
+
+        let save2 = SaveFile::from_reader(std::io::Cursor::new(out)).unwrap();
+        assert_eq!(save2.get_cash(), Some(2000));
+    }
+
+    fn compressed_roundtrip(compression: Compression) {
+        let mut save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        save.set_cash(3000).unwrap();
+
+        let mut out = Vec::new();
+        save.to_writer_compressed(&mut out, compression).unwrap();
+        assert_ne!(out, save.to_yaml().unwrap());
+        assert_eq!(Compression::detect(&out), compression);
+
+        let save2 = SaveFile::from_reader(out.as_slice()).unwrap();
+        assert_eq!(save2.get_character_name(), Some("TestChar"));
+        assert_eq!(save2.get_cash(), Some(3000));
+    }
+
+    #[test]
+    fn test_gzip_roundtrip() {
+        compressed_roundtrip(Compression::Gzip);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_roundtrip() {
+        compressed_roundtrip(Compression::Zstd);
+    }
+
+    #[test]
+    fn test_uncompressed_writer_matches_to_writer() {
+        let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
+        let mut out = Vec::new();
+        save.to_writer_compressed(&mut out, Compression::None)
+            .unwrap();
+        assert_eq!(out, save.to_yaml().unwrap());
+    }
+
     #[test]
     fn test_parse_value_float() {
         let val = SaveFile::parse_value("3.14159");
@@ -540,6 +1482,37 @@ eridium: 6666
         assert_eq!(val.as_u64(), Some(9999999999999));
     }
 
+    #[test]
+    fn test_parse_value_matrix() {
+        use serde_yaml::Value;
+
+        let cases: &[(&str, Value)] = &[
+            ("true", Value::Bool(true)),
+            ("False", Value::Bool(false)),
+            ("0", Value::Number(0.into())),
+            ("42", Value::Number(42.into())),
+            ("-42", Value::Number((-42).into())),
+            ("18446744073709551615", Value::Number(u64::MAX.into())),
+            ("1.5", Value::Number(1.5.into())),
+            ("0.25", Value::Number(0.25.into())),
+            ("-2.5e3", Value::Number((-2500.0).into())),
+            ("0123", Value::String("0123".into())),
+            ("-007", Value::String("-007".into())),
+            ("@Ugr$ZCm/123", Value::String("@Ugr$ZCm/123".into())),
+            ("\"true\"", Value::String("true".into())),
+            ("'42'", Value::String("42".into())),
+            ("\"\"", Value::String(String::new())),
+            ("inf", Value::String("inf".into())),
+            ("NaN", Value::String("NaN".into())),
+            ("\"", Value::String("\"".into())),
+            ("hello world", Value::String("hello world".into())),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(&SaveFile::parse_value(input), expected, "input: {}", input);
+        }
+    }
+
     #[test]
     fn test_save_file_debug() {
         let save = SaveFile::from_yaml(test_save_yaml().as_bytes()).unwrap();
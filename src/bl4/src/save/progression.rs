@@ -0,0 +1,153 @@
+//! Character progression limits: the XP curve and currency caps.
+//!
+//! The XP thresholds here are the ones observed in real saves (see the
+//! "Common Edits" section of docs/04-save-files.md); levels between them are
+//! interpolated.
+
+use super::inventory::lookup;
+
+/// Highest character level the game allows
+pub const MAX_CHARACTER_LEVEL: u32 = 50;
+
+/// Cash staged by [`super::ChangeSet::max_character`]
+///
+/// Well under `i32::MAX`, so picking up more cash in game can't overflow a
+/// 32-bit counter. This is the value the docs and presets have always used.
+pub const SAFE_MAX_CASH: u64 = 999_999_999;
+
+/// Eridium staged by [`super::ChangeSet::max_character`]
+///
+/// Same reasoning as [`SAFE_MAX_CASH`], kept to the documented example value.
+pub const SAFE_MAX_ERIDIUM: u64 = 9_999;
+
+/// Character XP thresholds observed in saves, ascending by level
+const KNOWN_CHARACTER_XP: &[(u32, u64)] = &[(1, 0), (2, 1_100), (30, 821_362), (50, 3_430_207)];
+
+/// Character XP needed to reach `level` (clamped to 1..=[`MAX_CHARACTER_LEVEL`])
+///
+/// Levels 1, 2, 30 and 50 are exact. Others follow a power curve through the
+/// nearest known thresholds on either side, rounded up so the result lands
+/// on or just past the real threshold rather than a level short.
+pub fn character_xp_for_level(level: u32) -> u64 {
+    let level = level.clamp(1, MAX_CHARACTER_LEVEL);
+    let upper = KNOWN_CHARACTER_XP
+        .iter()
+        .position(|&(known, _)| known >= level)
+        .expect("table ends at MAX_CHARACTER_LEVEL");
+    let (hi_level, hi_xp) = KNOWN_CHARACTER_XP[upper];
+    if hi_level == level {
+        return hi_xp;
+    }
+
+    // Level 1 is exact, so `upper` is at least 2 here and `lo_xp` is nonzero
+    let (lo_level, lo_xp) = KNOWN_CHARACTER_XP[upper - 1];
+    let exponent =
+        (hi_xp as f64 / lo_xp as f64).ln() / (f64::from(hi_level) / f64::from(lo_level)).ln();
+    let xp = lo_xp as f64 * (f64::from(level) / f64::from(lo_level)).powf(exponent);
+    xp.ceil() as u64
+}
+
+/// Experience track types the game writes to `state.experience`
+///
+/// Every character save seen so far holds exactly these two, in this order.
+/// The game ignores entries with any other `type`, so
+/// [`super::ChangeSet::ensure_experience_track`] only appends these.
+pub const EXPERIENCE_TRACKS: &[&str] = &["Character", "Specialization"];
+
+/// One element of a character save's `state.experience` array
+///
+/// Index 0 has always been the character track and index 1 the
+/// specialization track, but each entry names its own `type`, so other
+/// tracks are listed too. Fields an entry lacks (or holds with the wrong
+/// type) are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExperienceEntry {
+    /// Position in the array, as used in `state.experience[N]` paths
+    pub index: usize,
+    /// The entry's `type` (e.g. "Character", "Specialization")
+    pub type_name: Option<String>,
+    pub level: Option<u64>,
+    pub points: Option<u64>,
+}
+
+/// Every entry of `state.experience`, in array order
+pub(super) fn experience_entries(data: &serde_yaml::Value) -> Vec<ExperienceEntry> {
+    let Some(entries) = lookup(data, &["state", "experience"]).and_then(|e| e.as_sequence()) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| ExperienceEntry {
+            index,
+            type_name: entry.get("type").and_then(|t| t.as_str()).map(String::from),
+            level: entry.get("level").and_then(|l| l.as_u64()),
+            points: entry.get("points").and_then(|p| p.as_u64()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experience_entries() {
+        let data: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+state:
+  experience:
+    - type: Character
+      level: 10
+      points: 5000
+    - type: Specialization
+      points: 2500
+    - level: 2
+      points: nope
+"#,
+        )
+        .unwrap();
+
+        let entries = experience_entries(&data);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0],
+            ExperienceEntry {
+                index: 0,
+                type_name: Some("Character".to_string()),
+                level: Some(10),
+                points: Some(5000),
+            }
+        );
+        assert_eq!(entries[1].level, None);
+        assert_eq!(entries[1].points, Some(2500));
+        assert_eq!(entries[2].index, 2);
+        assert_eq!(entries[2].type_name, None);
+        assert_eq!(entries[2].points, None);
+    }
+
+    #[test]
+    fn test_experience_entries_missing_array() {
+        let data: serde_yaml::Value = serde_yaml::from_str("state: {}").unwrap();
+        assert!(experience_entries(&data).is_empty());
+    }
+
+    #[test]
+    fn test_character_xp_known_levels() {
+        for &(level, xp) in KNOWN_CHARACTER_XP {
+            assert_eq!(character_xp_for_level(level), xp);
+        }
+    }
+
+    #[test]
+    fn test_character_xp_curve() {
+        let xp: Vec<u64> = (1..=MAX_CHARACTER_LEVEL)
+            .map(character_xp_for_level)
+            .collect();
+        assert!(xp.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(character_xp_for_level(0), 0);
+        assert_eq!(character_xp_for_level(99), 3_430_207);
+    }
+}
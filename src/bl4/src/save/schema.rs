@@ -0,0 +1,87 @@
+//! Where the inventory containers live in the save YAML.
+//!
+//! Inventory helpers on [`SaveFile`](super::SaveFile) and
+//! [`ChangeSet`](super::ChangeSet) build every slot path from a
+//! [`SaveSchema`], so a save that nests these maps differently only needs
+//! a different schema, not different helpers.
+//!
+//! # Example
+//! ```
+//! use bl4::{ChangeSet, SaveSchema, StateFlags};
+//!
+//! let schema = SaveSchema {
+//!     backpack: "state.inv.backpack".to_string(),
+//!     ..SaveSchema::default()
+//! };
+//! let mut changes = ChangeSet::with_schema(schema);
+//! changes.set_backpack_flags(3, StateFlags::backpack()).unwrap();
+//! assert!(changes.has_change("state.inv.backpack.slot_3.state_flags"));
+//! ```
+
+use super::EquipSlot;
+
+/// Dotted paths to the slot maps, without the trailing `slot_N`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSchema {
+    /// Backpack slot map in character saves
+    pub backpack: String,
+    /// Bank slot map in profile saves
+    pub bank: String,
+    /// Equipped slot map in character saves
+    pub equipped: String,
+}
+
+impl Default for SaveSchema {
+    /// The layout of current PC saves
+    fn default() -> Self {
+        SaveSchema {
+            backpack: "state.inventory.items.backpack".to_string(),
+            bank: "domains.local.shared.inventory.items.bank".to_string(),
+            equipped: "state.inventory.equipped_inventory.equipped".to_string(),
+        }
+    }
+}
+
+impl SaveSchema {
+    /// Path to a backpack slot
+    pub fn backpack_slot(&self, slot: u8) -> String {
+        format!("{}.slot_{}", self.backpack, slot)
+    }
+
+    /// Path to a bank slot
+    pub fn bank_slot(&self, slot: u16) -> String {
+        format!("{}.slot_{}", self.bank, slot)
+    }
+
+    /// Path to an equipped slot's item array
+    pub fn equipped_slot(&self, slot: u8) -> String {
+        format!("{}.slot_{}", self.equipped, slot)
+    }
+
+    /// [`SaveSchema::equipped_slot`] for a named slot
+    pub fn equip_slot(&self, slot: EquipSlot) -> String {
+        self.equipped_slot(slot.index())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_paths() {
+        let schema = SaveSchema::default();
+        assert_eq!(
+            schema.backpack_slot(5),
+            "state.inventory.items.backpack.slot_5"
+        );
+        assert_eq!(
+            schema.bank_slot(120),
+            "domains.local.shared.inventory.items.bank.slot_120"
+        );
+        assert_eq!(
+            schema.equip_slot(EquipSlot::Shield),
+            EquipSlot::Shield.path()
+        );
+    }
+}
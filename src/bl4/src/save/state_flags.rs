@@ -19,47 +19,80 @@
 //! assert!(flags.is_in_backpack());
 //! ```
 
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
 /// State flags bitmask helper for inventory items.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct StateFlags(pub u32);
 
 impl StateFlags {
     // Bit values matching Borderlands 4's state_flags field (verified in-game)
-    const VALID: u32 = 1; // bit 0 - item exists/valid
-    const FAVORITE: u32 = 2; // bit 1 - favorite
-    const JUNK: u32 = 4; // bit 2 - junk marker
-    const LABEL1: u32 = 16; // bit 4 - label 1
-    const LABEL2: u32 = 32; // bit 5 - label 2
-    const LABEL3: u32 = 64; // bit 6 - label 3
-    const LABEL4: u32 = 128; // bit 7 - label 4
-    const IN_BACKPACK: u32 = 512; // bit 9 - in backpack (not equipped)
-
-    // All label bits are mutually exclusive (only one can be set at a time)
-    const ALL_LABELS: u32 =
+
+    /// Bit 0: item exists/valid
+    pub const VALID: u32 = 1;
+    /// Bit 1: favorite label
+    pub const FAVORITE: u32 = 2;
+    /// Bit 2: junk label
+    pub const JUNK: u32 = 4;
+    /// Bit 4: label 1
+    pub const LABEL1: u32 = 16;
+    /// Bit 5: label 2
+    pub const LABEL2: u32 = 32;
+    /// Bit 6: label 3
+    pub const LABEL3: u32 = 64;
+    /// Bit 7: label 4
+    pub const LABEL4: u32 = 128;
+    /// Bit 9: in backpack (not equipped)
+    pub const IN_BACKPACK: u32 = 512;
+
+    /// All label bits. The `with_*`/`set_*` methods keep at most one of
+    /// them set; the bitwise operators don't.
+    pub const ALL_LABELS: u32 =
         Self::FAVORITE | Self::JUNK | Self::LABEL1 | Self::LABEL2 | Self::LABEL3 | Self::LABEL4;
 
+    /// Every bit with a named constant above
+    const KNOWN_BITS: u32 = Self::VALID | Self::ALL_LABELS | Self::IN_BACKPACK;
+
+    /// Names for [`Display`](fmt::Display), in bit order
+    const NAMES: [(u32, &'static str); 8] = [
+        (Self::VALID, "valid"),
+        (Self::FAVORITE, "favorite"),
+        (Self::JUNK, "junk"),
+        (Self::LABEL1, "label1"),
+        (Self::LABEL2, "label2"),
+        (Self::LABEL3, "label3"),
+        (Self::LABEL4, "label4"),
+        (Self::IN_BACKPACK, "backpack"),
+    ];
+
+    /// Create flags from raw bits, usable in `const` contexts.
+    pub const fn new(bits: u32) -> Self {
+        Self(bits)
+    }
+
     /// Create flags for a backpack item (valid + in_backpack).
-    pub fn backpack() -> Self {
+    pub const fn backpack() -> Self {
         Self(Self::VALID | Self::IN_BACKPACK)
     }
 
     /// Create flags for an equipped item (valid only, no backpack bit).
-    pub fn equipped() -> Self {
+    pub const fn equipped() -> Self {
         Self(Self::VALID)
     }
 
     /// Create flags for a bank item (valid only).
-    pub fn bank() -> Self {
+    pub const fn bank() -> Self {
         Self(Self::VALID)
     }
 
     /// Create flags from a raw u32 value.
-    pub fn from_raw(bits: u32) -> Self {
+    pub const fn from_raw(bits: u32) -> Self {
         Self(bits)
     }
 
     /// Get the raw u32 value.
-    pub fn to_raw(self) -> u32 {
+    pub const fn to_raw(self) -> u32 {
         self.0
     }
 
@@ -110,6 +143,12 @@ impl StateFlags {
 
     // Query methods
 
+    /// Check if the VALID bit is set. Without it the game treats the slot
+    /// as empty and drops the item.
+    pub fn is_valid(&self) -> bool {
+        self.0 & Self::VALID != 0
+    }
+
     /// Check if the favorite flag is set.
     pub fn is_favorite(&self) -> bool {
         self.0 & Self::FAVORITE != 0
@@ -150,6 +189,14 @@ impl StateFlags {
         !self.is_in_backpack()
     }
 
+    /// Bits not covered by any named constant (bit 3, bit 8, bits 10+).
+    ///
+    /// Their meaning isn't known yet. Every method here leaves them as
+    /// they were, so they survive a read-modify-write round trip.
+    pub fn unknown_bits(&self) -> u32 {
+        self.0 & !Self::KNOWN_BITS
+    }
+
     // Mutation methods
     // Note: Labels are mutually exclusive - setting one clears others
 
@@ -225,6 +272,58 @@ impl StateFlags {
     }
 }
 
+/// Raw bitwise OR; doesn't clear other labels (use `with_*` for that).
+impl BitOr for StateFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for StateFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for StateFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+/// Flag names joined with `+`, e.g. `valid+favorite+backpack`, with any
+/// [`unknown_bits`](StateFlags::unknown_bits) appended as `+unknown(0x08)`.
+/// No bits at all prints `none`.
+impl fmt::Display for StateFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let mut sep = |f: &mut fmt::Formatter<'_>| {
+            let s = if first { "" } else { "+" };
+            first = false;
+            f.write_str(s)
+        };
+        for (bit, name) in Self::NAMES {
+            if self.0 & bit != 0 {
+                sep(f)?;
+                f.write_str(name)?;
+            }
+        }
+        let unknown = self.unknown_bits();
+        if unknown != 0 {
+            sep(f)?;
+            write!(f, "unknown({:#04x})", unknown)?;
+        }
+        if self.0 == 0 {
+            f.write_str("none")?;
+        }
+        Ok(())
+    }
+}
+
 impl From<u32> for StateFlags {
     fn from(v: u32) -> Self {
         Self(v)
@@ -249,6 +348,14 @@ mod tests {
         assert!(!flags.is_equipped());
     }
 
+    #[test]
+    fn test_state_flags_is_valid() {
+        assert!(StateFlags::backpack().is_valid());
+        assert!(StateFlags::bank().is_valid());
+        assert!(!StateFlags::new(0).is_valid());
+        assert!(!StateFlags::new(StateFlags::FAVORITE | StateFlags::IN_BACKPACK).is_valid());
+    }
+
     #[test]
     fn test_state_flags_equipped() {
         let flags = StateFlags::equipped();
@@ -435,4 +542,51 @@ mod tests {
         let flags = StateFlags::backpack();
         assert_eq!(flags.to_raw(), 513);
     }
+
+    #[test]
+    fn test_state_flags_bit_operators() {
+        const FAVORITE_BACKPACK: StateFlags =
+            StateFlags::new(StateFlags::VALID | StateFlags::IN_BACKPACK | StateFlags::FAVORITE);
+
+        let flags = StateFlags::backpack() | StateFlags(StateFlags::FAVORITE);
+        assert_eq!(flags, FAVORITE_BACKPACK);
+        assert_eq!(flags.to_raw(), 515);
+
+        // Raw operators don't enforce label exclusivity
+        let mut both = flags;
+        both |= StateFlags::new(StateFlags::JUNK);
+        assert!(both.is_favorite() && both.is_junk());
+        assert_eq!(
+            both & StateFlags::new(StateFlags::ALL_LABELS),
+            StateFlags::new(StateFlags::FAVORITE | StateFlags::JUNK)
+        );
+        assert_eq!(both.with_junk(), StateFlags::backpack().with_junk());
+    }
+
+    #[test]
+    fn test_state_flags_unknown_bits() {
+        assert_eq!(StateFlags::backpack().with_favorite().unknown_bits(), 0);
+
+        let flags = StateFlags::new(StateFlags::VALID | 8 | 256 | 1024);
+        assert_eq!(flags.unknown_bits(), 8 | 256 | 1024);
+
+        // Label and backpack edits leave unknown bits alone
+        let edited = flags.with_junk().to_backpack().with_no_label();
+        assert_eq!(edited.unknown_bits(), flags.unknown_bits());
+    }
+
+    #[test]
+    fn test_state_flags_display() {
+        assert_eq!(StateFlags::backpack().to_string(), "valid+backpack");
+        assert_eq!(
+            StateFlags::equipped().with_label2().to_string(),
+            "valid+label2"
+        );
+        assert_eq!(StateFlags::new(0).to_string(), "none");
+        assert_eq!(
+            StateFlags::new(StateFlags::VALID | 8).to_string(),
+            "valid+unknown(0x08)"
+        );
+        assert_eq!(StateFlags::new(0x400).to_string(), "unknown(0x400)");
+    }
 }
@@ -0,0 +1,287 @@
+//! Building new serials from a category and a list of parts
+
+use super::{encode_serial, SerialError, Token};
+use crate::manifest::PartsDatabase;
+use crate::reference::rarity_by_tier;
+
+/// Highest category that fits the 384 divisor without crossing into the
+/// 8192 regime (see [`crate::parts::varbit_divisor`])
+const MAX_SMALL_DIVISOR_CATEGORY: i64 = 340;
+
+/// Assemble a serial from a category, parts and a rarity
+///
+/// Serials are written in the VarBit-first (equipment) layout, whose first
+/// token carries the parts category directly, so
+/// [`ItemSerial::parts_category`](super::ItemSerial::parts_category)
+/// recovers it for any item type. Every part is checked against the
+/// supplied database before anything is encoded, and must belong to that
+/// category: a part token carries only an index, which the decoder reads in
+/// the item's own category.
+///
+/// The rarity is carried twice: as the category's generic `comp_0N` part,
+/// which is added automatically, and in the first token's rarity bits.
+/// Uncommon has no known rarity bits, so a decoded Uncommon item reports
+/// [`Rarity::Common`](super::Rarity::Common) in `rarity` while its
+/// `comp_02` part still names the right tier.
+///
+/// # Example
+/// ```
+/// use bl4::{ItemSerial, ItemSerialBuilder, PartsDatabase};
+///
+/// let db = PartsDatabase::from_tsv(
+///     "category\tindex\tname\tslot\n\
+///      279\t1\tbor_shield.comp_05_legendary\trarity\n\
+///      279\t7\tbor_shield.part_core_a\tcore\n",
+/// );
+/// let serial = ItemSerialBuilder::new(&db)
+///     .category(279)
+///     .add_part(279, 7)
+///     .rarity(5)
+///     .build()
+///     .unwrap();
+///
+/// let item = ItemSerial::decode(&serial).unwrap();
+/// assert_eq!(item.parts_category(), Some(279));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ItemSerialBuilder<'a> {
+    db: &'a PartsDatabase,
+    category: Option<i64>,
+    parts: Vec<(i64, u64)>,
+    rarity: Option<u8>,
+    level: u8,
+}
+
+impl<'a> ItemSerialBuilder<'a> {
+    /// Start an empty serial whose parts are validated against `db`
+    pub fn new(db: &'a PartsDatabase) -> Self {
+        Self {
+            db,
+            category: None,
+            parts: Vec::new(),
+            rarity: None,
+            level: crate::save::MAX_CHARACTER_LEVEL as u8,
+        }
+    }
+
+    /// Set the item's parts category (required)
+    pub fn category(mut self, category: i64) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Add the part at `index` in `category`
+    ///
+    /// Only the index is written to the serial, so `category` must match the
+    /// one passed to [`ItemSerialBuilder::category`]; [`ItemSerialBuilder::build`]
+    /// fails with [`SerialError::ForeignPart`] otherwise. Adding a part twice
+    /// writes it once.
+    pub fn add_part(mut self, category: i64, index: u64) -> Self {
+        if !self.parts.contains(&(category, index)) {
+            self.parts.push((category, index));
+        }
+        self
+    }
+
+    /// Set the rarity tier (1-5, see [`crate::RARITY_TIERS`])
+    pub fn rarity(mut self, tier: u8) -> Self {
+        self.rarity = Some(tier);
+        self
+    }
+
+    /// Set the item level (1-50, default 50)
+    pub fn level(mut self, level: u8) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Validate the parts and encode the serial
+    pub fn build(&self) -> Result<String, SerialError> {
+        Ok(encode_serial(&self.tokens()?))
+    }
+
+    fn tokens(&self) -> Result<Vec<Token>, SerialError> {
+        let category = self.category.ok_or(SerialError::MissingCategory)?;
+        let level_code = crate::parts::code_from_level(self.level)
+            .ok_or(SerialError::InvalidLevel(self.level))?;
+
+        for &(part_category, index) in &self.parts {
+            if self.db.get(part_category, index as i64).is_none() {
+                return Err(SerialError::UnknownPart {
+                    category: part_category,
+                    index,
+                });
+            }
+            if part_category != category {
+                return Err(SerialError::ForeignPart {
+                    category,
+                    part_category,
+                    index,
+                });
+            }
+        }
+
+        let mut parts = self.parts.clone();
+        let mut rarity_bits = 0;
+        if let Some(tier) = self.rarity {
+            let (index, bits) = self.composition_part(category, tier)?;
+            if !parts.contains(&(category, index)) {
+                parts.insert(0, (category, index));
+            }
+            rarity_bits = bits;
+        }
+
+        let divisor = if category <= MAX_SMALL_DIVISOR_CATEGORY {
+            384
+        } else {
+            8192
+        };
+        let mut tokens = vec![
+            Token::VarBit(category as u64 * divisor + (rarity_bits << 6)),
+            Token::Separator,
+            // The decoder adds 1 before reading the level code
+            Token::VarBit(level_code - 1),
+            Token::Separator,
+            Token::String(String::new()),
+            Token::Separator,
+        ];
+        tokens.extend(parts.into_iter().map(|(_, index)| Token::Part {
+            index,
+            values: Vec::new(),
+        }));
+        tokens.push(Token::Separator);
+        Ok(tokens)
+    }
+
+    /// Index of the category's generic part for `tier`, and its rarity bits
+    fn composition_part(&self, category: i64, tier: u8) -> Result<(u64, u64), SerialError> {
+        let rarity = rarity_by_tier(tier).ok_or(SerialError::InvalidRarityTier(tier))?;
+        let target = format!("{}_{}", rarity.code, rarity.name.to_lowercase());
+        let part = self
+            .db
+            .category(category)
+            .find(|p| p.name.split('.').next_back() == Some(target.as_str()))
+            .ok_or(SerialError::NoCompositionPart {
+                category,
+                code: rarity.code,
+            })?;
+
+        // Inverse of Rarity::from_equipment_varbit
+        let bits = match tier {
+            3 => 2,
+            4 => 1,
+            5 => 3,
+            _ => 0,
+        };
+        Ok((part.index as u64, bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::{ItemSerial, Rarity};
+
+    fn test_db() -> PartsDatabase {
+        PartsDatabase::from_tsv(
+            "category\tindex\tname\tslot\n\
+             279\t1\tbor_shield.comp_05_legendary\trarity\n\
+             279\t2\tbor_shield.comp_04_epic\trarity\n\
+             279\t7\tbor_shield.part_core_a\tcore\n\
+             279\t9\tbor_shield.part_augment_b\taugment\n\
+             1\t40\tshared.part_firmware_x\tfirmware\n\
+             400\t3\tbor_gadget.part_body\tbody\n",
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let db = test_db();
+        let serial = ItemSerialBuilder::new(&db)
+            .category(279)
+            .add_part(279, 7)
+            .add_part(279, 9)
+            .add_part(279, 7)
+            .rarity(5)
+            .level(30)
+            .build()
+            .unwrap();
+
+        let item = ItemSerial::decode(&serial).unwrap();
+        assert_eq!(item.parts_category(), Some(279));
+        assert_eq!(item.rarity, Some(Rarity::Legendary));
+        assert_eq!(item.level, Some(30));
+        let indices: Vec<u64> = item.parts().into_iter().map(|(i, _)| i).collect();
+        assert_eq!(indices, vec![1, 7, 9]);
+    }
+
+    #[test]
+    fn test_large_category_uses_wide_divisor() {
+        let db = test_db();
+        let serial = ItemSerialBuilder::new(&db)
+            .category(400)
+            .add_part(400, 3)
+            .build()
+            .unwrap();
+
+        let item = ItemSerial::decode(&serial).unwrap();
+        assert_eq!(item.parts_category(), Some(400));
+        assert_eq!(item.rarity, Some(Rarity::Common));
+    }
+
+    #[test]
+    fn test_explicit_composition_part_not_duplicated() {
+        let db = test_db();
+        let serial = ItemSerialBuilder::new(&db)
+            .category(279)
+            .add_part(279, 2)
+            .rarity(4)
+            .build()
+            .unwrap();
+
+        let item = ItemSerial::decode(&serial).unwrap();
+        assert_eq!(item.parts().len(), 1);
+        assert_eq!(item.rarity, Some(Rarity::Epic));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let db = test_db();
+        let builder = ItemSerialBuilder::new(&db);
+
+        assert!(matches!(
+            builder.clone().add_part(279, 7).build(),
+            Err(SerialError::MissingCategory)
+        ));
+        assert!(matches!(
+            builder.clone().category(279).add_part(279, 8).build(),
+            Err(SerialError::UnknownPart {
+                category: 279,
+                index: 8
+            })
+        ));
+        assert!(matches!(
+            builder.clone().category(279).add_part(1, 40).build(),
+            Err(SerialError::ForeignPart {
+                category: 279,
+                part_category: 1,
+                index: 40
+            })
+        ));
+        assert!(matches!(
+            builder.clone().category(279).rarity(6).build(),
+            Err(SerialError::InvalidRarityTier(6))
+        ));
+        assert!(matches!(
+            builder.clone().category(279).rarity(3).build(),
+            Err(SerialError::NoCompositionPart {
+                category: 279,
+                code: "comp_03"
+            })
+        ));
+        assert!(matches!(
+            builder.clone().category(279).level(0).build(),
+            Err(SerialError::InvalidLevel(0))
+        ));
+    }
+}
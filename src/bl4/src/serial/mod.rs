@@ -10,12 +10,14 @@
 
 mod base85;
 mod bitstream;
+mod builder;
 mod rarity;
 mod validate;
 
 use base85::{decode_base85, encode_base85, mirror_byte};
 use bitstream::{BitReader, BitWriter};
 
+pub use builder::ItemSerialBuilder;
 pub use rarity::RarityEstimate;
 pub use validate::{Legality, ValidationCheck, ValidationResult};
 
@@ -169,6 +171,27 @@ pub enum SerialError {
     #[error("Serial too short: expected at least {expected} bytes, got {actual}")]
     TooShort { expected: usize, actual: usize },
 
+    #[error("No parts category set")]
+    MissingCategory,
+
+    #[error("Part {index} not found in category {category}")]
+    UnknownPart { category: i64, index: u64 },
+
+    #[error("Part {index} is in category {part_category}, not the item's category {category}")]
+    ForeignPart {
+        category: i64,
+        part_category: i64,
+        index: u64,
+    },
+
+    #[error("Invalid rarity tier {0} (expected 1-5)")]
+    InvalidRarityTier(u8),
+
+    #[error("Category {category} has no {code} composition part")]
+    NoCompositionPart { category: i64, code: &'static str },
+
+    #[error("Invalid level {0} (expected 1-50)")]
+    InvalidLevel(u8),
 }
 
 /// Serial encoding format, determined from the binary token stream.
@@ -259,6 +282,23 @@ pub struct ItemSerial {
     pub rarity: Option<Rarity>,
 }
 
+/// Encode tokens into a complete `@U` serial string
+///
+/// Shared by [`ItemSerial::encode_from_tokens`] and [`ItemSerialBuilder`].
+fn encode_serial(tokens: &[Token]) -> String {
+    // Encode tokens to bytes
+    let bytes = encode_tokens(tokens);
+
+    // Mirror all bits (reverse of decode)
+    let mirrored: Vec<u8> = bytes.iter().map(|&b| mirror_byte(b)).collect();
+
+    // Encode to Base85
+    let encoded = encode_base85(&mirrored);
+
+    // Build final serial with prefix (@U; the rest is encoded in the base85 payload)
+    format!("@U{}", encoded)
+}
+
 /// Encode tokens back to bitstream bytes
 fn encode_tokens(tokens: &[Token]) -> Vec<u8> {
     let mut writer = BitWriter::new();
@@ -871,17 +911,7 @@ impl ItemSerial {
     /// This attempts to encode tokens back to bytes, but may not preserve
     /// all original data like item type encoding.
     pub fn encode_from_tokens(&self) -> String {
-        // Encode tokens to bytes
-        let bytes = encode_tokens(&self.tokens);
-
-        // Mirror all bits (reverse of decode)
-        let mirrored: Vec<u8> = bytes.iter().map(|&b| mirror_byte(b)).collect();
-
-        // Encode to Base85
-        let encoded = encode_base85(&mirrored);
-
-        // Build final serial with prefix (@U; the rest is encoded in the base85 payload)
-        format!("@U{}", encoded)
+        encode_serial(&self.tokens)
     }
 
     /// Create a new ItemSerial with modified tokens
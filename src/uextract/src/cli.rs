@@ -104,6 +104,38 @@ pub enum Commands {
         /// Texture format: bc7 or bc1
         #[arg(short = 'F', long, default_value = "bc7")]
         format: String,
+        /// Also write the mip chain (sizes that fit in the .ubulk) as JSON
+        #[arg(long)]
+        metadata: Option<PathBuf>,
+    },
+    /// Decode every matching texture asset to PNG
+    ExtractTextures {
+        /// Path to Paks directory
+        input: PathBuf,
+        /// Output directory (PNGs mirror the asset paths)
+        #[arg(long)]
+        out: PathBuf,
+        /// Mip level to extract (0 = highest resolution)
+        #[arg(short, long, default_value = "0")]
+        mip: usize,
+        /// Select specific paths (glob patterns, can specify multiple)
+        #[arg(short, long)]
+        select: Vec<String>,
+        /// Filter paths containing this string (can specify multiple, OR logic)
+        #[arg(short, long)]
+        filter: Vec<String>,
+        /// Case-insensitive filter (can specify multiple, OR logic)
+        #[arg(short = 'i', long)]
+        ifilter: Vec<String>,
+        /// Exclude paths matching pattern (can specify multiple)
+        #[arg(short, long)]
+        exclude: Vec<String>,
+        /// AES encryption key if pak is encrypted
+        #[arg(long)]
+        aes_key: Option<String>,
+        /// Also write each texture's mip chain metadata to a .json beside its PNG
+        #[arg(long)]
+        metadata: bool,
     },
     /// Dump ScriptObjects from global.utoc to JSON (for class resolution)
     ScriptObjects {
@@ -116,21 +148,83 @@ pub enum Commands {
         #[arg(long)]
         aes_key: Option<String>,
     },
-    /// Find assets by class type (requires scriptobjects.json)
+    /// Find assets by class type and/or export name (class search requires scriptobjects.json)
+    #[command(alias = "find-assets")]
     FindByClass {
         /// Path to Paks directory
         input: PathBuf,
         /// Class name to search for (e.g. "InventoryPartDef")
-        class_name: String,
+        #[arg(required_unless_present = "name_glob")]
+        class_name: Option<String>,
+        /// Only match exports whose object name matches this glob (repeatable, AND with class)
+        #[arg(long, value_name = "PATTERN")]
+        name_glob: Vec<String>,
         /// Path to scriptobjects.json
         #[arg(long, default_value = "scriptobjects.json")]
         scriptobjects: PathBuf,
         /// AES encryption key if pak is encrypted
         #[arg(long)]
         aes_key: Option<String>,
-        /// Output matching paths to file
+        /// Output matching paths and export names to file
         #[arg(short, long)]
         output: Option<PathBuf>,
+        /// Collapse byte-identical assets, reporting one path and a count
+        #[arg(long)]
+        unique: bool,
+        /// Also write each matching asset's raw bytes under this directory
+        #[arg(long, value_name = "DIR")]
+        extract_to: Option<PathBuf>,
+        /// Log scanned assets to this file and skip those already in it
+        #[arg(long, value_name = "FILE")]
+        checkpoint: Option<PathBuf>,
+        /// Remove this prefix from printed paths (e.g. "/Game/")
+        #[arg(long, value_name = "PREFIX")]
+        strip_prefix: Option<String>,
+        /// Print paths without their file extension
+        #[arg(long)]
+        no_extension: bool,
+    },
+    /// Dump a single asset's exports and properties to JSON
+    DumpAsset {
+        /// Path to Paks directory
+        input: PathBuf,
+        /// Asset path inside the IoStore (full path or trailing portion)
+        asset_path: String,
+        /// Output JSON file (default: stdout)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Path to .usmap file for property schema
+        #[arg(long)]
+        usmap: Option<PathBuf>,
+        /// Path to scriptobjects.json for class resolution
+        #[arg(long)]
+        scriptobjects: Option<PathBuf>,
+        /// AES encryption key if pak is encrypted
+        #[arg(long)]
+        aes_key: Option<String>,
+    },
+    /// List all chunk paths with sizes and container compression/encryption
+    ListFiles {
+        /// Path to Paks directory
+        input: PathBuf,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Select specific paths (glob patterns, can specify multiple)
+        #[arg(short, long)]
+        select: Vec<String>,
+        /// Filter paths containing this string (can specify multiple, OR logic)
+        #[arg(short, long)]
+        filter: Vec<String>,
+        /// Case-insensitive filter (can specify multiple, OR logic)
+        #[arg(short = 'i', long)]
+        ifilter: Vec<String>,
+        /// Exclude paths matching pattern (can specify multiple)
+        #[arg(short, long)]
+        exclude: Vec<String>,
+        /// AES encryption key if pak is encrypted
+        #[arg(long)]
+        aes_key: Option<String>,
     },
     /// List all unique class hashes found in pak files (debug)
     ListClasses {
@@ -145,6 +239,12 @@ pub enum Commands {
         /// Max number of sample assets to show per class
         #[arg(long, default_value = "3")]
         samples: usize,
+        /// Log scanned assets to this file and skip those already in it
+        #[arg(long, value_name = "FILE")]
+        checkpoint: Option<PathBuf>,
+        /// Also write the census as CSV (hash,class_name,count,sample_paths)
+        #[arg(long, value_name = "FILE")]
+        csv: Option<PathBuf>,
     },
 }
 
@@ -0,0 +1,266 @@
+//! Append-only progress log for resumable IoStore scans
+//!
+//! The first line names the scan the log belongs to. Every chunk finished
+//! after that appends one `{"container": ..., "path": ..., "result": ...}`
+//! line, written with a single call so a crash can at worst cut off the line
+//! in flight. On restart the log is replayed: logged chunks are skipped and
+//! their results reused. Chunks that couldn't be scanned are never logged,
+//! so a rerun tries them again.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    scan: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Line<T> {
+    container: String,
+    path: String,
+    result: T,
+}
+
+/// Container name and asset path of a logged chunk
+///
+/// The same path can be shipped in several containers (patch paks override
+/// base ones), so the path alone doesn't identify a chunk.
+type Key = (String, String);
+
+/// Results of chunks finished by earlier runs, plus the log new ones go to
+pub(super) struct Checkpoint<T> {
+    done: HashMap<Key, T>,
+    log: Option<Mutex<File>>,
+}
+
+impl<T: Serialize + DeserializeOwned + Clone> Checkpoint<T> {
+    /// Replay the log at `path` and open it for appending
+    ///
+    /// `scan` identifies the command, its input and its criteria; a log
+    /// written for a different scan is rejected rather than mixed in. With no path,
+    /// nothing is replayed or recorded.
+    pub(super) fn open(path: Option<&Path>, scan: &str) -> Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self {
+                done: HashMap::new(),
+                log: None,
+            });
+        };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)
+            .with_context(|| format!("Failed to open checkpoint {:?}", path))?;
+
+        let done = if file.metadata()?.len() == 0 {
+            let header = serde_json::to_string(&Header {
+                scan: scan.to_string(),
+            })?;
+            writeln!(file, "{}", header)?;
+            HashMap::new()
+        } else {
+            let done = replay(&mut file, scan)
+                .with_context(|| format!("Failed to replay checkpoint {:?}", path))?;
+            end_with_newline(&mut file)?;
+            done
+        };
+
+        Ok(Self {
+            done,
+            log: Some(Mutex::new(file)),
+        })
+    }
+
+    /// Number of chunks replayed from the log
+    pub(super) fn replayed(&self) -> usize {
+        self.done.len()
+    }
+
+    /// Result logged for `path` in `container` by an earlier run
+    pub(super) fn get(&self, container: &str, path: &str) -> Option<&T> {
+        self.done.get(&(container.to_string(), path.to_string()))
+    }
+
+    /// Reuse the logged result for `path` in `container`, or compute and log it
+    ///
+    /// `scan` returns `None` when the chunk couldn't be read or parsed; that
+    /// isn't logged, so the chunk is tried again on the next run. A failed
+    /// write is reported but doesn't stop the scan either.
+    pub(super) fn resume_or(
+        &self,
+        container: &str,
+        path: &str,
+        scan: impl FnOnce() -> Option<T>,
+    ) -> Option<T> {
+        if let Some(result) = self.get(container, path) {
+            return Some(result.clone());
+        }
+        let result = scan()?;
+        if let Err(e) = self.record(container, path, &result) {
+            eprintln!("Warning: failed to write checkpoint for {}: {:#}", path, e);
+        }
+        Some(result)
+    }
+
+    fn record(&self, container: &str, path: &str, result: &T) -> Result<()> {
+        let Some(log) = &self.log else {
+            return Ok(());
+        };
+        let mut line = serde_json::to_string(&Line {
+            container: container.to_string(),
+            path: path.to_string(),
+            result,
+        })?;
+        line.push('\n');
+        let mut file = log.lock().unwrap();
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Read a log's header and entries, dropping lines cut off by a crash
+fn replay<T: DeserializeOwned>(file: &mut File, scan: &str) -> Result<HashMap<Key, T>> {
+    file.seek(SeekFrom::Start(0))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let header: Header = match lines.next() {
+        Some(line) => serde_json::from_str(&line?).context("Missing checkpoint header")?,
+        None => anyhow::bail!("Missing checkpoint header"),
+    };
+    anyhow::ensure!(
+        header.scan == scan,
+        "Checkpoint was written for a different scan ({}); delete it to start over",
+        header.scan
+    );
+
+    let mut done = HashMap::new();
+    for line in lines {
+        // Lines cut off by a crash don't parse; those chunks are rescanned
+        if let Ok(entry) = serde_json::from_str::<Line<T>>(&line?) {
+            done.insert((entry.container, entry.path), entry.result);
+        }
+    }
+    Ok(done)
+}
+
+/// Terminate a cut-off last line so the next append starts on its own line
+fn end_with_newline(file: &mut File) -> Result<()> {
+    file.seek(SeekFrom::End(-1))?;
+    let mut last = [0u8];
+    file.read_exact(&mut last)?;
+    if last[0] != b'\n' {
+        file.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_replays_completed_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scan.log");
+
+        let first: Checkpoint<Vec<String>> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(first.replayed(), 0);
+        first.resume_or("pakchunk0", "/Game/A.uasset", || {
+            Some(vec!["1A".to_string()])
+        });
+        first.resume_or("pakchunk0", "/Game/B.uasset", || Some(Vec::new()));
+        drop(first);
+
+        let second: Checkpoint<Vec<String>> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(second.replayed(), 2);
+        let a = second.resume_or("pakchunk0", "/Game/A.uasset", || {
+            panic!("A was already scanned")
+        });
+        assert_eq!(a, Some(vec!["1A".to_string()]));
+        second.resume_or("pakchunk0", "/Game/C.uasset", || Some(Vec::new()));
+        drop(second);
+
+        let third: Checkpoint<Vec<String>> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(third.replayed(), 3);
+    }
+
+    #[test]
+    fn test_checkpoint_keys_by_container() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scan.log");
+
+        let first: Checkpoint<u32> = Checkpoint::open(Some(&path), "scan").unwrap();
+        first.resume_or("pakchunk0", "/Game/A.uasset", || Some(1));
+        drop(first);
+
+        let second: Checkpoint<u32> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(second.get("pakchunk0", "/Game/A.uasset"), Some(&1));
+        assert_eq!(second.get("pakchunk1", "/Game/A.uasset"), None);
+        let patched = second.resume_or("pakchunk1", "/Game/A.uasset", || Some(2));
+        assert_eq!(patched, Some(2));
+    }
+
+    #[test]
+    fn test_checkpoint_retries_failed_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scan.log");
+
+        let first: Checkpoint<u32> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(first.resume_or("pakchunk0", "A", || None), None);
+        drop(first);
+
+        let second: Checkpoint<u32> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(second.replayed(), 0);
+        assert_eq!(second.resume_or("pakchunk0", "A", || Some(3)), Some(3));
+    }
+
+    #[test]
+    fn test_checkpoint_drops_truncated_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scan.log");
+        std::fs::write(
+            &path,
+            "{\"scan\":\"scan\"}\n\
+             {\"container\":\"c\",\"path\":\"A\",\"result\":1}\n\
+             {\"container\":\"c\",\"path\":\"B\",\"res",
+        )
+        .unwrap();
+
+        let resumed: Checkpoint<u32> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(resumed.replayed(), 1);
+        resumed.resume_or("c", "B", || Some(2));
+        drop(resumed);
+
+        let again: Checkpoint<u32> = Checkpoint::open(Some(&path), "scan").unwrap();
+        assert_eq!(again.get("c", "A"), Some(&1));
+        assert_eq!(again.get("c", "B"), Some(&2));
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_other_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scan.log");
+        drop(Checkpoint::<u32>::open(Some(&path), "list-classes /game/Paks").unwrap());
+
+        let err = Checkpoint::<u32>::open(Some(&path), "list-classes /other/Paks")
+            .err()
+            .unwrap();
+        assert!(format!("{:#}", err).contains("different scan"));
+    }
+
+    #[test]
+    fn test_checkpoint_disabled() {
+        let none: Checkpoint<u32> = Checkpoint::open(None, "scan").unwrap();
+        assert_eq!(none.resume_or("c", "A", || Some(7)), Some(7));
+        assert_eq!(none.replayed(), 0);
+    }
+}
@@ -0,0 +1,49 @@
+//! Dump a single asset's exports and properties to JSON
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::scanner::IoStoreScanner;
+
+pub struct DumpAssetOptions<'a> {
+    pub input: &'a Path,
+    pub asset_path: &'a str,
+    pub output: Option<&'a Path>,
+    pub usmap: Option<&'a Path>,
+    pub scriptobjects: Option<&'a Path>,
+    pub aes_key: Option<&'a str>,
+}
+
+/// Load one package, parse its exports and tagged properties, and write JSON
+pub fn dump_asset(opts: &DumpAssetOptions<'_>) -> Result<()> {
+    let mut scanner = IoStoreScanner::open(opts.input, opts.aes_key)?;
+
+    if let Some(usmap) = opts.usmap {
+        scanner.load_usmap(usmap)?;
+    }
+    if let Some(scriptobjects) = opts.scriptobjects {
+        scanner.load_scriptobjects(scriptobjects)?;
+    }
+
+    let (path, data) = scanner.read_asset(opts.asset_path)?;
+    eprintln!("Loaded {} ({} bytes)", path, data.len());
+
+    let info = scanner
+        .parse_asset_data(&data, &path)
+        .with_context(|| format!("Failed to parse {}", path))?;
+    eprintln!(
+        "{} exports, {} imports, {} names",
+        info.export_count, info.import_count, info.name_count
+    );
+
+    let json = serde_json::to_string_pretty(&info)?;
+    match opts.output {
+        Some(out_path) => {
+            std::fs::write(out_path, json)?;
+            eprintln!("Wrote {:?}", out_path);
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-//! Find assets by class command
+//! Find assets by class and/or export name command
 
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
@@ -7,25 +7,263 @@ use retoc::{
     container_header::EIoContainerHeaderVersion, iostore, zen::FZenPackageHeader, AesKey, Config,
     EIoStoreTocVersion, FGuid,
 };
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::io::Cursor;
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-/// Find assets by class type
-#[allow(clippy::too_many_lines)]
-pub fn find_assets_by_class(
-    input: &Path,
-    class_name: &str,
-    scriptobjects_path: &Path,
-    aes_key: Option<&str>,
-    output: Option<&Path>,
-) -> Result<()> {
-    use retoc::script_objects::FPackageObjectIndexType;
+use super::checkpoint::Checkpoint;
+use super::list_files::read_toc_infos;
+
+/// What a scan found in one chunk, as logged to a checkpoint
+#[derive(Clone, Serialize, Deserialize)]
+struct ChunkHit {
+    export_names: Vec<String>,
+    content_hash: u64,
+}
+
+/// An asset containing at least one matching export
+struct AssetMatch {
+    path: String,
+    export_names: Vec<String>,
+    /// Hash of the chunk bytes, used to collapse identical assets
+    content_hash: u64,
+    /// Index of the chunk among the scanned assets, to read it again when extracting
+    entry: usize,
+}
+
+fn content_hash(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Collapse byte-identical assets, keeping the first path as representative
+///
+/// Export hashes aren't used as the key: they're derived from the export's
+/// path within its package, so same-named exports in different packages
+/// collide even when their contents differ.
+fn dedup_matches(matches: Vec<AssetMatch>) -> Vec<(AssetMatch, usize)> {
+    let mut unique: Vec<(AssetMatch, usize)> = Vec::new();
+    let mut by_hash: HashMap<u64, usize> = HashMap::new();
+
+    for m in matches {
+        match by_hash.get(&m.content_hash) {
+            Some(&i) => unique[i].1 += 1,
+            None => {
+                by_hash.insert(m.content_hash, unique.len());
+                unique.push((m, 1));
+            }
+        }
+    }
+    unique
+}
+
+/// Relative output path for an asset, keeping only normal path components
+///
+/// Drops leading `/`, `../` and `./` (and any `..` further in) so every
+/// asset lands inside the extraction directory.
+pub(super) fn relative_asset_path(path: &str) -> PathBuf {
+    Path::new(path)
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Pick an output path not already used in this run, adding `_N` before the extension
+fn unclaimed_path(dir: &Path, relative: &Path, claimed: &mut HashSet<PathBuf>) -> PathBuf {
+    let base = dir.join(relative);
+    let mut candidate = base.clone();
+    let mut n = 1;
+    while claimed.contains(&candidate) {
+        let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match base.extension() {
+            Some(ext) => format!("{}_{}.{}", stem, n, ext.to_string_lossy()),
+            None => format!("{}_{}", stem, n),
+        };
+        candidate = base.with_file_name(name);
+        n += 1;
+    }
+    claimed.insert(candidate.clone());
+    candidate
+}
+
+/// Write matched assets' bytes under `dir`, preserving their directory structure
+///
+/// Each asset's bytes come from `read` and are written before the next one
+/// is read, so only one asset is held in memory at a time; assets `read`
+/// can't return are skipped. With `unique`, only the first of each set of
+/// byte-identical assets is written. Returns the number of files and bytes
+/// written.
+fn extract_matches(
+    matches: &[AssetMatch],
+    dir: &Path,
+    unique: bool,
+    read: impl Fn(&AssetMatch) -> Option<Vec<u8>>,
+) -> Result<(usize, u64)> {
+    let mut claimed = HashSet::new();
+    let mut seen_hashes = HashSet::new();
+    let (mut files, mut bytes) = (0, 0u64);
+
+    for m in matches {
+        if unique && !seen_hashes.insert(m.content_hash) {
+            continue;
+        }
+        let Some(data) = read(m) else {
+            continue;
+        };
+
+        let out_path = unclaimed_path(dir, &relative_asset_path(&m.path), &mut claimed);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&out_path, &data)
+            .with_context(|| format!("Failed to write {:?}", out_path))?;
+        files += 1;
+        bytes += data.len() as u64;
+    }
+
+    Ok((files, bytes))
+}
 
-    eprintln!("Searching for assets of class: {}", class_name);
+/// How asset paths are printed; `--extract-to` layout is unaffected
+#[derive(Default)]
+struct PathStyle<'a> {
+    /// Removed from the start of paths that have it
+    strip_prefix: Option<&'a str>,
+    /// Drop the file extension (`.uasset`)
+    no_extension: bool,
+}
+
+impl PathStyle<'_> {
+    fn apply<'p>(&self, path: &'p str) -> &'p str {
+        let path = self
+            .strip_prefix
+            .and_then(|prefix| path.strip_prefix(prefix))
+            .unwrap_or(path);
+        if !self.no_extension {
+            return path;
+        }
+        let name_start = path.rfind('/').map_or(0, |i| i + 1);
+        match path[name_start..].rfind('.') {
+            Some(dot) if dot > 0 => &path[..name_start + dot],
+            _ => path,
+        }
+    }
+}
+
+/// Format output lines: `path<TAB>name`, plus `<TAB>count` when deduplicating
+fn format_lines(matches: Vec<AssetMatch>, unique: bool, style: &PathStyle<'_>) -> Vec<String> {
+    if !unique {
+        return matches
+            .iter()
+            .flat_map(|m| {
+                m.export_names
+                    .iter()
+                    .map(move |name| format!("{}\t{}", style.apply(&m.path), name))
+            })
+            .collect();
+    }
+
+    let deduped = dedup_matches(matches);
+    eprintln!(
+        "{} unique assets after collapsing duplicates",
+        deduped.len()
+    );
+    deduped
+        .iter()
+        .flat_map(|(m, count)| {
+            m.export_names
+                .iter()
+                .map(move |name| format!("{}\t{}\t{}", style.apply(&m.path), name, count))
+        })
+        .collect()
+}
+
+/// Options for [`find_assets`]; `class_name` and `name_globs` combine with AND
+pub struct FindAssetsOptions<'a> {
+    pub input: &'a Path,
+    /// Class whose exports to match (e.g. "InventoryPartDef")
+    pub class_name: Option<&'a str>,
+    /// Glob patterns for export object names; any one may match
+    pub name_globs: &'a [String],
+    /// scriptobjects.json, only read when searching by class
+    pub scriptobjects: &'a Path,
+    pub aes_key: Option<&'a str>,
+    pub output: Option<&'a Path>,
+    /// Collapse byte-identical assets
+    pub unique: bool,
+    /// Also write each matching asset's raw bytes under this directory
+    pub extract_to: Option<&'a Path>,
+    /// Log of finished chunks, replayed to resume an interrupted scan
+    pub checkpoint: Option<&'a Path>,
+    /// Remove this prefix from printed paths (e.g. "/Game/")
+    pub strip_prefix: Option<&'a str>,
+    /// Print paths without their extension
+    pub no_extension: bool,
+}
+
+/// Criteria an export must meet to count as a match
+struct ExportFilter<'a> {
+    /// Script import hash of the class, as uppercase hex
+    class_hash: Option<String>,
+    name_globs: &'a [String],
+}
+
+impl ExportFilter<'_> {
+    /// `export_class` is the export's script import hash, if its class is one
+    fn matches(&self, export_class: Option<&str>, name: &str) -> bool {
+        let class_ok = self
+            .class_hash
+            .as_deref()
+            .is_none_or(|hash| export_class == Some(hash));
+        let name_ok = self.name_globs.is_empty()
+            || self
+                .name_globs
+                .iter()
+                .any(|glob| glob_match::glob_match(glob, name));
+        class_ok && name_ok
+    }
+
+    fn describe(&self, class_name: Option<&str>) -> String {
+        let mut parts = Vec::new();
+        if let Some(class) = class_name {
+            parts.push(format!("class {}", class));
+        }
+        if !self.name_globs.is_empty() {
+            parts.push(format!("name matching {}", self.name_globs.join(" or ")));
+        }
+        parts.join(" and ")
+    }
+}
+
+/// Say which containers had chunks that failed to read, and how they're stored
+///
+/// Encrypted containers usually mean a missing or wrong `--aes-key`.
+fn report_unreadable(input: &Path, unreadable: &BTreeMap<String, usize>) {
+    let tocs = read_toc_infos(input, unreadable.keys().map(String::as_str));
+    for (container, count) in unreadable {
+        match tocs.get(container) {
+            Some(toc) => eprintln!(
+                "Warning: {} assets in {} could not be read; container is {}",
+                count, container, toc
+            ),
+            None => eprintln!(
+                "Warning: {} assets in {} could not be read",
+                count, container
+            ),
+        }
+    }
+}
 
-    // Load scriptobjects
+/// Look up a class's script import hash in scriptobjects.json
+fn resolve_class_hash(scriptobjects_path: &Path, class_name: &str) -> Result<String> {
     let so_data = std::fs::read_to_string(scriptobjects_path)
         .with_context(|| format!("Failed to read {:?}", scriptobjects_path))?;
     let so_json: serde_json::Value = serde_json::from_str(&so_data)?;
@@ -63,10 +301,51 @@ pub fn find_assets_by_class(
         target_hash.context(format!("Class '{}' not found in scriptobjects", class_name))?;
     let target_path = hash_to_path.get(&target_hash).cloned().unwrap_or_default();
     eprintln!("Target class: {} -> {}", target_hash, target_path);
+    Ok(target_hash)
+}
+
+/// Find assets by class type and/or export object name
+///
+/// Prints one tab-separated `path<TAB>object_name` line per matching export,
+/// since a single package may contain several matching exports. With
+/// `unique`, byte-identical assets are collapsed into their first path and
+/// each line gains a `<TAB>count` column.
+///
+/// Name globs are matched against the export names from each package's name
+/// map, so they work without scriptobjects.json when no class is given.
+///
+/// `strip_prefix` and `no_extension` rewrite the printed paths (and the
+/// `output` file) for scripting; paths without the prefix are left as is.
+///
+/// With `extract_to`, each matching asset's raw bytes are also written to
+/// `<extract_to>/<asset-path>`. Matches are read again one at a time after
+/// the scan, so the scan itself keeps no asset bytes in memory.
+///
+/// With `checkpoint`, each scanned asset is appended to that log as it
+/// finishes, and assets already in the log are skipped, so an interrupted
+/// scan can be rerun with the same arguments to pick up where it stopped.
+#[allow(clippy::too_many_lines)]
+pub fn find_assets(opts: &FindAssetsOptions<'_>) -> Result<()> {
+    use retoc::script_objects::FPackageObjectIndexType;
+
+    anyhow::ensure!(
+        opts.class_name.is_some() || !opts.name_globs.is_empty(),
+        "Give a class name, a name glob, or both"
+    );
+
+    let filter = ExportFilter {
+        class_hash: opts
+            .class_name
+            .map(|class| resolve_class_hash(opts.scriptobjects, class))
+            .transpose()?,
+        name_globs: opts.name_globs,
+    };
+    let criteria = filter.describe(opts.class_name);
+    eprintln!("Searching for assets with {}", criteria);
 
     // Build retoc config
     let mut aes_keys = HashMap::new();
-    if let Some(key) = aes_key {
+    if let Some(key) = opts.aes_key {
         let parsed_key: AesKey = key.parse()?;
         aes_keys.insert(FGuid::default(), parsed_key);
     }
@@ -77,7 +356,7 @@ pub fn find_assets_by_class(
     });
 
     // Open IoStore
-    let store = iostore::open(input, config)?;
+    let store = iostore::open(opts.input, config)?;
 
     // Get container versions
     let toc_version = store
@@ -103,6 +382,17 @@ pub fn find_assets_by_class(
 
     eprintln!("Scanning {} .uasset files...", uasset_entries.len());
 
+    let scan = format!(
+        "find-assets {} {}{}",
+        opts.input.display(),
+        criteria,
+        if opts.unique { " (unique)" } else { "" }
+    );
+    let checkpoint: Checkpoint<Option<ChunkHit>> = Checkpoint::open(opts.checkpoint, &scan)?;
+    if checkpoint.replayed() > 0 {
+        eprintln!("Resuming: {} assets already scanned", checkpoint.replayed());
+    }
+
     let pb = ProgressBar::new(uasset_entries.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
@@ -111,58 +401,249 @@ pub fn find_assets_by_class(
             .progress_chars("#>-"),
     );
 
-    // Check each asset's class_index
-    let matching_paths: Vec<String> = uasset_entries
+    // Chunks that failed to read, counted per container
+    let unreadable: Mutex<BTreeMap<String, usize>> = Mutex::new(BTreeMap::new());
+    let read = |chunk: &iostore::ChunkInfo| {
+        chunk.read().ok().or_else(|| {
+            let container = chunk.container().container_name().to_string();
+            *unreadable.lock().unwrap().entry(container).or_default() += 1;
+            None
+        })
+    };
+
+    // Check each asset's exports, keeping the name of every matching one
+    let matches: Vec<AssetMatch> = uasset_entries
         .par_iter()
-        .filter_map(|(chunk, path)| {
+        .enumerate()
+        .filter_map(|(entry, (chunk, path))| {
             pb.inc(1);
 
-            // Read the asset data
-            let data = chunk.read().ok()?;
-
-            // Quick parse to get export class_index
-            let mut cursor = Cursor::new(&data);
-            let header = FZenPackageHeader::deserialize(
-                &mut cursor,
-                None,
-                toc_version,
-                container_header_version,
-                None,
-            )
-            .ok()?;
-
-            // Check each export's class_index
-            for export in &header.export_map {
-                if export.class_index.kind() == FPackageObjectIndexType::ScriptImport {
-                    let class_hash = format!("{:X}", export.class_index.raw_index());
-                    if class_hash == target_hash {
-                        return Some(path.clone());
-                    }
+            let container = chunk.container().container_name();
+            let hit = checkpoint.resume_or(container, path, || {
+                // Read the asset data
+                let bytes = read(chunk)?;
+
+                // Quick parse to get export class_index and the name map
+                let mut cursor = Cursor::new(&bytes);
+                let header = FZenPackageHeader::deserialize(
+                    &mut cursor,
+                    None,
+                    toc_version,
+                    container_header_version,
+                    None,
+                )
+                .ok()?;
+
+                let export_names: Vec<String> = header
+                    .export_map
+                    .iter()
+                    .filter_map(|export| {
+                        let name = header.name_map.get(export.object_name).to_string();
+                        let class = (export.class_index.kind()
+                            == FPackageObjectIndexType::ScriptImport)
+                            .then(|| format!("{:X}", export.class_index.raw_index()));
+                        filter.matches(class.as_deref(), &name).then_some(name)
+                    })
+                    .collect();
+
+                if export_names.is_empty() {
+                    return Some(None);
                 }
-            }
-            None
+                Some(Some(ChunkHit {
+                    export_names,
+                    content_hash: if opts.unique { content_hash(&bytes) } else { 0 },
+                }))
+            })??;
+
+            Some(AssetMatch {
+                path: path.clone(),
+                export_names: hit.export_names,
+                content_hash: hit.content_hash,
+                entry,
+            })
         })
         .collect();
 
     pb.finish_and_clear();
 
+    let export_count: usize = matches.iter().map(|m| m.export_names.len()).sum();
     eprintln!(
-        "Found {} assets of class {}",
-        matching_paths.len(),
-        class_name
+        "Found {} exports in {} assets with {}",
+        export_count,
+        matches.len(),
+        criteria
     );
 
-    // Output results
-    for path in &matching_paths {
-        println!("{}", path);
+    if let Some(dir) = opts.extract_to {
+        let (files, bytes) = extract_matches(&matches, dir, opts.unique, |m| {
+            read(&uasset_entries[m.entry].0)
+        })?;
+        eprintln!("Extracted {} assets ({} bytes) to {:?}", files, bytes, dir);
+    }
+    report_unreadable(opts.input, &unreadable.into_inner().unwrap());
+
+    let style = PathStyle {
+        strip_prefix: opts.strip_prefix,
+        no_extension: opts.no_extension,
+    };
+    let lines = format_lines(matches, opts.unique, &style);
+
+    for line in &lines {
+        println!("{}", line);
     }
 
     // Write to file if requested
-    if let Some(out_path) = output {
-        let content = matching_paths.join("\n");
+    if let Some(out_path) = opts.output {
+        let content = lines.join("\n");
         std::fs::write(out_path, content)?;
-        eprintln!("Wrote paths to {:?}", out_path);
+        eprintln!("Wrote matches to {:?}", out_path);
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(path: &str, bytes: &[u8]) -> AssetMatch {
+        AssetMatch {
+            path: path.to_string(),
+            export_names: vec!["Def".to_string()],
+            content_hash: content_hash(bytes),
+            entry: 0,
+        }
+    }
+
+    #[test]
+    fn test_dedup_matches() {
+        let matches = vec![
+            asset("/Game/A.uasset", b"same"),
+            asset("/Game/B.uasset", b"other"),
+            asset("/Game/C.uasset", b"same"),
+        ];
+
+        let deduped = dedup_matches(matches);
+        let summary: Vec<(&str, usize)> = deduped
+            .iter()
+            .map(|(m, count)| (m.path.as_str(), *count))
+            .collect();
+        assert_eq!(summary, vec![("/Game/A.uasset", 2), ("/Game/B.uasset", 1)]);
+    }
+
+    #[test]
+    fn test_format_lines_unique_adds_count() {
+        let matches = vec![asset("/Game/A.uasset", b"x"), asset("/Game/B.uasset", b"x")];
+        assert_eq!(
+            format_lines(matches, true, &PathStyle::default()),
+            vec!["/Game/A.uasset\tDef\t2".to_string()]
+        );
+
+        let matches = vec![asset("/Game/A.uasset", b"x")];
+        assert_eq!(
+            format_lines(matches, false, &PathStyle::default()),
+            vec!["/Game/A.uasset\tDef".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_path_style() {
+        let style = PathStyle {
+            strip_prefix: Some("/Game/"),
+            no_extension: true,
+        };
+        assert_eq!(style.apply("/Game/Parts/A.uasset"), "Parts/A");
+        assert_eq!(style.apply("../OakGame/B.uasset"), "../OakGame/B");
+        assert_eq!(style.apply("/Game/Dir.v2/.hidden"), "Dir.v2/.hidden");
+
+        let prefix_only = PathStyle {
+            strip_prefix: Some("/Game/"),
+            no_extension: false,
+        };
+        assert_eq!(prefix_only.apply("/Game/Parts/A.uasset"), "Parts/A.uasset");
+
+        let matches = vec![asset("/Game/A.uasset", b"x")];
+        assert_eq!(
+            format_lines(matches, false, &style),
+            vec!["A\tDef".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_relative_asset_path() {
+        assert_eq!(
+            relative_asset_path("../../../OakGame/Content/A.uasset"),
+            PathBuf::from("OakGame/Content/A.uasset")
+        );
+        assert_eq!(
+            relative_asset_path("/Game/../B.uasset"),
+            PathBuf::from("Game/B.uasset")
+        );
+    }
+
+    #[test]
+    fn test_extract_matches_writes_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let contents: [(&str, &[u8]); 3] = [
+            ("/Game/Parts/A.uasset", b"one"),
+            ("../Game/Parts/A.uasset", b"two"),
+            ("/Game/Other/B.uasset", b"one"),
+        ];
+        let matches: Vec<AssetMatch> = contents
+            .iter()
+            .enumerate()
+            .map(|(entry, (path, bytes))| AssetMatch {
+                entry,
+                ..asset(path, bytes)
+            })
+            .collect();
+        let read = |m: &AssetMatch| Some(contents[m.entry].1.to_vec());
+
+        let (files, bytes) = extract_matches(&matches, dir.path(), false, read).unwrap();
+        assert_eq!((files, bytes), (3, 9));
+        let read = |p: &str| std::fs::read(dir.path().join(p)).unwrap();
+        assert_eq!(read("Game/Parts/A.uasset"), b"one");
+        assert_eq!(read("Game/Parts/A_1.uasset"), b"two");
+        assert_eq!(read("Game/Other/B.uasset"), b"one");
+
+        let unique_dir = tempfile::tempdir().unwrap();
+        let (files, _) = extract_matches(&matches, unique_dir.path(), true, read).unwrap();
+        assert_eq!(files, 2);
+        assert!(!unique_dir.path().join("Game/Other/B.uasset").exists());
+
+        // Assets that can no longer be read are skipped
+        let skip_dir = tempfile::tempdir().unwrap();
+        let (files, _) = extract_matches(&matches, skip_dir.path(), false, |_| None).unwrap();
+        assert_eq!(files, 0);
+    }
+
+    #[test]
+    fn test_export_filter_combines_class_and_name() {
+        let globs = vec!["*Pistol_Legendary*".to_string()];
+        let both = ExportFilter {
+            class_hash: Some("1A2B".to_string()),
+            name_globs: &globs,
+        };
+        assert!(both.matches(Some("1A2B"), "DAD_Pistol_Legendary_01"));
+        assert!(!both.matches(Some("FFFF"), "DAD_Pistol_Legendary_01"));
+        assert!(!both.matches(Some("1A2B"), "DAD_Pistol_Common_01"));
+        assert!(!both.matches(None, "DAD_Pistol_Legendary_01"));
+        assert_eq!(
+            both.describe(Some("InventoryPartDef")),
+            "class InventoryPartDef and name matching *Pistol_Legendary*"
+        );
+
+        let name_only = ExportFilter {
+            class_hash: None,
+            name_globs: &globs,
+        };
+        assert!(name_only.matches(None, "JAK_Pistol_Legendary"));
+        assert!(!name_only.matches(Some("1A2B"), "JAK_Shotgun"));
+
+        let class_only = ExportFilter {
+            class_hash: Some("1A2B".to_string()),
+            name_globs: &[],
+        };
+        assert!(class_only.matches(Some("1A2B"), "Anything"));
+    }
+}
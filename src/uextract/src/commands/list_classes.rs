@@ -10,18 +10,45 @@ use retoc::{
 use std::collections::{BTreeMap, HashMap};
 use std::io::Cursor;
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+
+use super::checkpoint::Checkpoint;
+
+/// Options for [`list_classes`]
+pub struct ListClassesOptions<'a> {
+    pub input: &'a Path,
+    /// scriptobjects.json for resolving class hashes to names
+    pub scriptobjects: &'a Path,
+    pub aes_key: Option<&'a str>,
+    /// Max sample asset paths kept per class
+    pub samples: usize,
+    /// Log of scanned assets, replayed to resume an interrupted scan
+    pub checkpoint: Option<&'a Path>,
+    /// Also write the census as CSV to this file
+    pub csv: Option<&'a Path>,
+}
+
+/// Class name, export count and sample asset paths for one class hash
+type ClassInfo = (String, usize, Vec<String>);
 
 /// List all unique class hashes found in pak files
+///
+/// With `checkpoint`, each scanned asset's classes are appended to that log
+/// as it finishes, and assets already in the log are skipped on a rerun.
+/// The table always goes to stdout; `csv` writes the same census to a file.
 #[allow(clippy::too_many_lines)]
-pub fn list_classes(
-    input: &Path,
-    scriptobjects_path: &Path,
-    aes_key: Option<&str>,
-    samples: usize,
-) -> Result<()> {
+pub fn list_classes(opts: &ListClassesOptions) -> Result<()> {
     use retoc::script_objects::FPackageObjectIndexType;
 
+    let ListClassesOptions {
+        input,
+        scriptobjects: scriptobjects_path,
+        aes_key,
+        samples,
+        checkpoint,
+        csv,
+    } = *opts;
+
     // Load scriptobjects for name resolution
     let so_data = std::fs::read_to_string(scriptobjects_path)
         .with_context(|| format!("Failed to read scriptobjects file {:?}", scriptobjects_path))?;
@@ -76,9 +103,11 @@ pub fn list_classes(
 
     eprintln!("Scanning {} .uasset files...", uasset_entries.len());
 
-    // Collect classes: hash -> (class_name, count, sample_paths)
-    type ClassInfo = (String, usize, Vec<String>);
-    let class_map: Arc<Mutex<BTreeMap<String, ClassInfo>>> = Arc::new(Mutex::new(BTreeMap::new()));
+    let scan = format!("list-classes {}", input.display());
+    let checkpoint: Checkpoint<Vec<String>> = Checkpoint::open(checkpoint, &scan)?;
+    if checkpoint.replayed() > 0 {
+        eprintln!("Resuming: {} assets already scanned", checkpoint.replayed());
+    }
 
     let pb = ProgressBar::new(uasset_entries.len() as u64);
     pb.set_style(
@@ -88,46 +117,68 @@ pub fn list_classes(
             .progress_chars("#>-"),
     );
 
-    uasset_entries.par_iter().for_each(|(chunk, path)| {
-        pb.inc(1);
-
-        if let Ok(data) = chunk.read() {
-            let mut cursor = Cursor::new(&data);
-            if let Ok(header) = FZenPackageHeader::deserialize(
-                &mut cursor,
-                None,
-                toc_version,
-                container_header_version,
-                None,
-            ) {
-                for export in &header.export_map {
-                    if export.class_index.kind() == FPackageObjectIndexType::ScriptImport {
-                        let class_hash = format!("{:X}", export.class_index.raw_index());
-                        let mut map = class_map.lock().unwrap();
-                        let entry = map.entry(class_hash.clone()).or_insert_with(|| {
-                            let name = hash_to_path
-                                .get(&class_hash)
-                                .cloned()
-                                .unwrap_or_else(|| "UNKNOWN".to_string());
-                            (name, 0, Vec::new())
-                        });
-                        entry.1 += 1;
-                        if entry.2.len() < samples {
-                            entry.2.push(path.clone());
-                        }
-                    }
-                }
-            }
-        }
-    });
+    // Class hash of every script-class export, per asset
+    let asset_classes: Vec<(&String, Vec<String>)> = uasset_entries
+        .par_iter()
+        .map(|(chunk, path)| {
+            pb.inc(1);
+            let container = chunk.container().container_name();
+            let classes = checkpoint.resume_or(container, path, || {
+                let data = chunk.read().ok()?;
+                let mut cursor = Cursor::new(&data);
+                let header = FZenPackageHeader::deserialize(
+                    &mut cursor,
+                    None,
+                    toc_version,
+                    container_header_version,
+                    None,
+                )
+                .ok()?;
+                let classes: Vec<String> = header
+                    .export_map
+                    .iter()
+                    .filter(|export| {
+                        export.class_index.kind() == FPackageObjectIndexType::ScriptImport
+                    })
+                    .map(|export| format!("{:X}", export.class_index.raw_index()))
+                    .collect();
+                Some(classes)
+            });
+            // Unreadable assets count as having no classes; they're retried on a rerun
+            (path, classes.unwrap_or_default())
+        })
+        .collect();
 
     pb.finish_and_clear();
 
+    // Collect classes: hash -> (class_name, count, sample_paths)
+    let mut map: BTreeMap<String, ClassInfo> = BTreeMap::new();
+    for (path, classes) in asset_classes {
+        for class_hash in classes {
+            let entry = map.entry(class_hash).or_insert_with_key(|hash| {
+                let name = hash_to_path
+                    .get(hash)
+                    .cloned()
+                    .unwrap_or_else(|| "UNKNOWN".to_string());
+                (name, 0, Vec::new())
+            });
+            entry.1 += 1;
+            if entry.2.len() < samples {
+                entry.2.push(path.clone());
+            }
+        }
+    }
+
     // Print results sorted by count
-    let map = class_map.lock().unwrap();
     let mut entries: Vec<_> = map.iter().collect();
     entries.sort_by(|a, b| b.1 .1.cmp(&a.1 .1));
 
+    if let Some(csv_path) = csv {
+        std::fs::write(csv_path, format_csv(&entries))
+            .with_context(|| format!("Failed to write {:?}", csv_path))?;
+        eprintln!("Wrote class census to {:?}", csv_path);
+    }
+
     eprintln!("\n{} unique class types found:", entries.len());
     println!("{:<20} {:<60} Count", "Hash", "Class Name");
     println!("{:-<100}", "");
@@ -141,3 +192,60 @@ pub fn list_classes(
 
     Ok(())
 }
+
+/// Census as `hash,class_name,count,sample_paths` rows, sample paths joined with `;`
+fn format_csv(entries: &[(&String, &ClassInfo)]) -> String {
+    let mut out = String::from("hash,class_name,count,sample_paths\n");
+    for (hash, (name, count, sample_paths)) in entries {
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            escape_csv(hash),
+            escape_csv(name),
+            count,
+            escape_csv(&sample_paths.join(";"))
+        ));
+    }
+    out
+}
+
+/// Quote a CSV field (RFC 4180) if it contains a comma, quote or line break
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_csv_escapes_fields() {
+        let plain = "1A2B".to_string();
+        let quoted = "3C4D".to_string();
+        let plain_info: ClassInfo = (
+            "/Script/Engine.Texture2D".to_string(),
+            12,
+            vec!["Game/A.uasset".to_string(), "Game/B.uasset".to_string()],
+        );
+        let quoted_info: ClassInfo = (
+            "Odd,\"Name\"".to_string(),
+            1,
+            vec!["Game/C,D.uasset".to_string()],
+        );
+
+        assert_eq!(
+            format_csv(&[(&plain, &plain_info), (&quoted, &quoted_info)]),
+            "hash,class_name,count,sample_paths\n\
+             1A2B,/Script/Engine.Texture2D,12,Game/A.uasset;Game/B.uasset\n\
+             3C4D,\"Odd,\"\"Name\"\"\",1,\"Game/C,D.uasset\"\n"
+        );
+    }
+
+    #[test]
+    fn test_format_csv_empty() {
+        assert_eq!(format_csv(&[]), "hash,class_name,count,sample_paths\n");
+    }
+}
@@ -0,0 +1,191 @@
+//! List chunk paths and sizes in an IoStore
+
+use anyhow::{Context, Result};
+use retoc::{
+    iostore::{self, IoStoreTrait},
+    AesKey, Config, FGuid,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::utoc::{toc_path, TocInfo};
+
+/// A single chunk in the listing
+///
+/// Compression and encryption are container-wide settings in IoStore, so
+/// they describe the container the chunk was read from.
+#[derive(Debug, Serialize)]
+pub struct FileEntry {
+    pub path: String,
+    /// Uncompressed length from the .utoc, or None if it couldn't be read
+    pub size: Option<usize>,
+    pub container: String,
+    pub compressed: bool,
+    pub encrypted: bool,
+    /// Encryption key GUID, present for encrypted containers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_guid: Option<String>,
+}
+
+/// Open an IoStore container with an optional AES key
+pub(super) fn open_store(input: &Path, aes_key: Option<&str>) -> Result<Box<dyn IoStoreTrait>> {
+    let mut aes_keys = HashMap::new();
+    if let Some(key) = aes_key {
+        let parsed_key: AesKey = key
+            .parse()
+            .context("Invalid AES key format (use hex or base64)")?;
+        aes_keys.insert(FGuid::default(), parsed_key);
+    }
+    let config = Arc::new(Config {
+        aes_keys,
+        container_header_version_override: None,
+        toc_version_override: None,
+    });
+
+    iostore::open(input, config).with_context(|| format!("Failed to open {:?}", input))
+}
+
+/// Header metadata for each named container, read from its .utoc
+///
+/// Containers whose .utoc can't be read are reported and left out, so their
+/// chunks show as neither compressed nor encrypted.
+pub(super) fn read_toc_infos<'a>(
+    input: &Path,
+    containers: impl IntoIterator<Item = &'a str>,
+) -> HashMap<String, TocInfo> {
+    let mut infos = HashMap::new();
+    for name in containers {
+        if infos.contains_key(name) {
+            continue;
+        }
+        match TocInfo::read(&toc_path(input, name)) {
+            Ok(info) => {
+                infos.insert(name.to_string(), info);
+            }
+            Err(e) => eprintln!("Warning: {:#}", e),
+        }
+    }
+    infos
+}
+
+/// List every chunk path (and its size) accepted by `matches`
+///
+/// Sizes are the uncompressed chunk lengths recorded in each container's
+/// .utoc, so no chunk is read or decompressed. Chunks whose .utoc can't be
+/// read are still listed, without a size.
+pub fn list_files<F>(input: &Path, aes_key: Option<&str>, json: bool, matches: F) -> Result<()>
+where
+    F: Fn(&str) -> bool,
+{
+    let store = open_store(input, aes_key)?;
+
+    let chunks: Vec<_> = store
+        .chunks()
+        .filter_map(|chunk| chunk.path().map(|path| (chunk, path)))
+        .filter(|(_, path)| matches(path.as_str()))
+        .map(|(chunk, path)| {
+            let container = chunk.container().container_name().to_string();
+            (chunk, path, container)
+        })
+        .collect();
+    let tocs = read_toc_infos(input, chunks.iter().map(|(_, _, c)| c.as_str()));
+
+    let mut entries: Vec<FileEntry> = chunks
+        .iter()
+        .map(|(chunk, path, container)| {
+            let toc = tocs.get(container);
+            let encrypted = toc.is_some_and(|t| t.encrypted);
+            let size = toc
+                .and_then(|t| t.chunk_sizes.get(&chunk.id().get_raw()))
+                .map(|&size| size as usize);
+            FileEntry {
+                path: path.clone(),
+                size,
+                container: container.clone(),
+                compressed: toc.is_some_and(|t| t.compressed),
+                encrypted,
+                key_guid: toc.filter(|_| encrypted).map(|t| t.key_guid.clone()),
+            }
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    print_entries(&entries, json)
+}
+
+/// Print the listing as JSON or as a size/flags/path table
+///
+/// Flags are `C` (compressed container) and `E` (encrypted container);
+/// chunks without a known size show `?`.
+fn print_entries(entries: &[FileEntry], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(entries)?);
+    } else {
+        for entry in entries {
+            println!("{}", format_entry(entry));
+        }
+        let total: usize = entries.iter().filter_map(|e| e.size).sum();
+        let no_size = entries.iter().filter(|e| e.size.is_none()).count();
+        eprintln!("{} files, {} bytes total", entries.len(), total);
+        if no_size > 0 {
+            eprintln!("{} files have no size in their .utoc", no_size);
+        }
+    }
+
+    Ok(())
+}
+
+/// One line of the text listing
+fn format_entry(entry: &FileEntry) -> String {
+    let size = entry
+        .size
+        .map_or_else(|| "?".to_string(), |size| size.to_string());
+    let flags = format!(
+        "{}{}",
+        if entry.compressed { 'C' } else { '-' },
+        if entry.encrypted { 'E' } else { '-' }
+    );
+    format!("{:>12}  {}  {}", size, flags, entry.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(size: Option<usize>, encrypted: bool) -> FileEntry {
+        FileEntry {
+            path: "/Game/A.uasset".to_string(),
+            size,
+            container: "pakchunk0-Windows".to_string(),
+            compressed: true,
+            encrypted,
+            key_guid: encrypted.then(|| "0".repeat(32)),
+        }
+    }
+
+    #[test]
+    fn test_format_entry() {
+        assert_eq!(
+            format_entry(&entry(Some(42), false)),
+            "          42  C-  /Game/A.uasset"
+        );
+        assert_eq!(
+            format_entry(&entry(None, true)),
+            "           ?  CE  /Game/A.uasset"
+        );
+    }
+
+    #[test]
+    fn test_key_guid_only_serialized_when_encrypted() {
+        let plain = serde_json::to_value(entry(Some(1), false)).unwrap();
+        assert!(plain.get("key_guid").is_none());
+        assert_eq!(plain["size"], 1);
+
+        let encrypted = serde_json::to_value(entry(None, true)).unwrap();
+        assert_eq!(encrypted["key_guid"], "0".repeat(32));
+        assert!(encrypted["size"].is_null());
+    }
+}
@@ -1,13 +1,19 @@
 //! CLI subcommand implementations
 
+mod checkpoint;
+mod dump_asset;
 mod find_assets;
 mod list_classes;
+mod list_files;
 mod pak;
 mod script_objects;
 mod texture;
+mod textures;
 
-pub use find_assets::find_assets_by_class;
-pub use list_classes::list_classes;
+pub use dump_asset::{dump_asset, DumpAssetOptions};
+pub use find_assets::{find_assets, FindAssetsOptions};
+pub use list_classes::{list_classes, ListClassesOptions};
+pub use list_files::{list_files, FileEntry};
 pub use pak::{extract_pak, ExtractPakOptions};
 pub use script_objects::extract_script_objects;
 
@@ -15,3 +21,4 @@ pub use script_objects::extract_script_objects;
 #[allow(unused_imports)]
 pub use script_objects::{ScriptObjectEntry, ScriptObjectsDump};
 pub use texture::{extract_texture_cmd, ExtractTextureOptions};
+pub use textures::{extract_textures, ExtractTexturesOptions};
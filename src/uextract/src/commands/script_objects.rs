@@ -3,7 +3,7 @@
 use anyhow::{Context, Result};
 use retoc::{iostore, AesKey, Config, FGuid};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -144,6 +144,9 @@ pub fn extract_script_objects(input: &Path, output: &Path, aes_key: Option<&str>
     Ok(())
 }
 
+/// Longest outer chain followed before giving up on a script object path
+const MAX_OUTER_DEPTH: usize = 64;
+
 /// Resolve the full path of a script object by walking the outer chain
 fn resolve_script_object_path(
     obj: &retoc::script_objects::FScriptObjectEntry,
@@ -151,22 +154,116 @@ fn resolve_script_object_path(
 ) -> String {
     use retoc::script_objects::FPackageObjectIndexType;
 
-    let name = script_objects
-        .global_name_map
-        .get(obj.object_name)
-        .to_string();
+    outer_chain_path(
+        obj,
+        |o| o.global_index.raw_index(),
+        |o| {
+            script_objects
+                .global_name_map
+                .get(o.object_name)
+                .to_string()
+        },
+        |o| {
+            // No script import outer (or a missing one) ends the chain
+            if o.outer_index.kind() != FPackageObjectIndexType::ScriptImport {
+                return None;
+            }
+            script_objects.script_object_lookup.get(&o.outer_index)
+        },
+    )
+}
+
+/// Join names from the outermost object down to `start` with `.`
+///
+/// Stops early, keeping the partial path, if the chain loops back on itself
+/// or runs past [`MAX_OUTER_DEPTH`].
+fn outer_chain_path<'a, T>(
+    start: &'a T,
+    id: impl Fn(&T) -> u64,
+    name: impl Fn(&T) -> String,
+    outer: impl Fn(&T) -> Option<&'a T>,
+) -> String {
+    let mut names = Vec::new();
+    let mut visited = HashSet::new();
+    let mut current = Some(start);
+
+    while let Some(obj) = current {
+        if !visited.insert(id(obj)) {
+            eprintln!("Warning: outer chain of {} loops; path truncated", names[0]);
+            break;
+        }
+        if names.len() == MAX_OUTER_DEPTH {
+            eprintln!(
+                "Warning: outer chain of {} deeper than {}; path truncated",
+                names[0], MAX_OUTER_DEPTH
+            );
+            break;
+        }
+        names.push(name(obj));
+        current = outer(obj);
+    }
+
+    names.reverse();
+    names.join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Obj {
+        id: u64,
+        name: &'static str,
+        outer: Option<usize>,
+    }
+
+    fn obj(id: u64, name: &'static str, outer: Option<usize>) -> Obj {
+        Obj { id, name, outer }
+    }
+
+    fn path_of(objects: &[Obj], start: usize) -> String {
+        outer_chain_path(
+            &objects[start],
+            |o| o.id,
+            |o| o.name.to_string(),
+            |o| o.outer.map(|i| &objects[i]),
+        )
+    }
+
+    #[test]
+    fn test_outer_chain_path() {
+        let objects = [
+            obj(1, "/Script/GbxInventory", None),
+            obj(2, "InventoryPartDef", Some(0)),
+            obj(3, "Default__InventoryPartDef", Some(1)),
+        ];
+        assert_eq!(path_of(&objects, 0), "/Script/GbxInventory");
+        assert_eq!(
+            path_of(&objects, 2),
+            "/Script/GbxInventory.InventoryPartDef.Default__InventoryPartDef"
+        );
+    }
 
-    // If no outer, this is a top-level package
-    if obj.outer_index.kind() != FPackageObjectIndexType::ScriptImport {
-        return name;
+    #[test]
+    fn test_outer_chain_path_cycle_terminates() {
+        let objects = [
+            obj(1, "A", Some(1)),
+            obj(2, "B", Some(0)),
+            obj(3, "Self", Some(2)),
+        ];
+        assert_eq!(path_of(&objects, 0), "B.A");
+        assert_eq!(path_of(&objects, 2), "Self");
     }
 
-    // Look up the outer object
-    if let Some(outer) = script_objects.script_object_lookup.get(&obj.outer_index) {
-        let outer_path = resolve_script_object_path(outer, script_objects);
-        format!("{}.{}", outer_path, name)
-    } else {
-        // Outer not found, just return the name
-        name
+    #[test]
+    fn test_outer_chain_path_depth_limit() {
+        let objects: Vec<Obj> = (0..MAX_OUTER_DEPTH + 10)
+            .map(|i| Obj {
+                id: i as u64,
+                name: "X",
+                outer: Some(i + 1).filter(|&n| n < MAX_OUTER_DEPTH + 10),
+            })
+            .collect();
+        assert_eq!(path_of(&objects, 0).split('.').count(), MAX_OUTER_DEPTH);
     }
 }
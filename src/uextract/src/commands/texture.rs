@@ -11,6 +11,8 @@ pub struct ExtractTextureOptions<'a> {
     pub output_path: &'a Path,
     pub mip_level: usize,
     pub format: &'a str,
+    /// Also write the mip chain as JSON here
+    pub metadata_path: Option<&'a Path>,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -22,6 +24,7 @@ pub fn extract_texture_cmd(opts: &ExtractTextureOptions<'_>) -> Result<()> {
         output_path,
         mip_level,
         format,
+        metadata_path,
     } = opts;
     use std::io::Read;
 
@@ -93,5 +96,19 @@ pub fn extract_texture_cmd(opts: &ExtractTextureOptions<'_>) -> Result<()> {
 
     eprintln!("Saved to {:?}", output_path);
 
+    if let Some(metadata_path) = metadata_path {
+        let mip_sizes =
+            texture::mip_chain_sizes(*width, *height, bytes_per_block, file.metadata()?.len());
+        let metadata = texture::TextureMetadata {
+            width: *width,
+            height: *height,
+            format: format.to_string(),
+            mip_count: mip_sizes.len(),
+            mip_sizes,
+        };
+        metadata.write(metadata_path)?;
+        eprintln!("Wrote metadata to {:?}", metadata_path);
+    }
+
     Ok(())
 }
@@ -0,0 +1,178 @@
+//! Batch texture extraction from an IoStore
+
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use rayon::prelude::*;
+use retoc::{
+    container_header::EIoContainerHeaderVersion, iostore, zen::FZenPackageHeader,
+    EIoStoreTocVersion,
+};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use super::find_assets::relative_asset_path;
+use super::list_files::open_store;
+use crate::texture;
+
+pub struct ExtractTexturesOptions<'a> {
+    pub input: &'a Path,
+    pub output: &'a Path,
+    pub mip_level: usize,
+    pub aes_key: Option<&'a str>,
+    /// Write each texture's mip chain to a `.json` beside its PNG
+    pub metadata: bool,
+}
+
+/// Decode every `.uasset` accepted by `matches` to `<output>/<asset-path>.png`
+///
+/// Each texture's mips are read from the `.ubulk` chunk with the same path.
+/// Assets that aren't textures, have no bulk data, or use a pixel format the
+/// decoder doesn't handle are skipped and listed once the run finishes.
+pub fn extract_textures<F>(opts: &ExtractTexturesOptions<'_>, matches: F) -> Result<()>
+where
+    F: Fn(&str) -> bool,
+{
+    let store = open_store(opts.input, opts.aes_key)?;
+    let versions = StoreVersions {
+        toc: store
+            .container_file_version()
+            .unwrap_or(EIoStoreTocVersion::ReplaceIoChunkHashWithIoHash),
+        container_header: store
+            .container_header_version()
+            .unwrap_or(EIoContainerHeaderVersion::NoExportInfo),
+    };
+
+    let mut assets = Vec::new();
+    let mut bulk = HashMap::new();
+    for chunk in store.chunks() {
+        let Some(path) = chunk.path() else {
+            continue;
+        };
+        if path.ends_with(".ubulk") {
+            bulk.insert(path, chunk);
+        } else if path.ends_with(".uasset") && matches(&path) {
+            assets.push((chunk, path));
+        }
+    }
+
+    eprintln!("Extracting {} textures...", assets.len());
+    std::fs::create_dir_all(opts.output)?;
+
+    let pb = ProgressBar::new(assets.len() as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len}")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+
+    let mut skipped: Vec<(&str, String)> = assets
+        .par_iter()
+        .filter_map(|(chunk, path)| {
+            let ubulk = bulk.get(&bulk_path(path));
+            let result = extract_one(chunk, ubulk, path, versions, opts);
+            pb.inc(1);
+            result.err().map(|e| (path.as_str(), format!("{:#}", e)))
+        })
+        .collect();
+
+    pb.finish_and_clear();
+    report(assets.len(), &mut skipped);
+
+    Ok(())
+}
+
+/// Print skipped assets (sorted by path) and the totals
+fn report(total: usize, skipped: &mut [(&str, String)]) {
+    skipped.sort();
+    for (path, reason) in skipped.iter() {
+        eprintln!("Skipped {}: {}", path, reason);
+    }
+    eprintln!(
+        "Extracted: {}, Skipped: {}",
+        total - skipped.len(),
+        skipped.len()
+    );
+}
+
+/// Container versions needed to parse an asset's Zen package header
+#[derive(Clone, Copy)]
+struct StoreVersions {
+    toc: EIoStoreTocVersion,
+    container_header: EIoContainerHeaderVersion,
+}
+
+/// Decode one texture asset to PNG
+fn extract_one(
+    chunk: &iostore::ChunkInfo,
+    ubulk: Option<&iostore::ChunkInfo>,
+    path: &str,
+    versions: StoreVersions,
+    opts: &ExtractTexturesOptions<'_>,
+) -> Result<()> {
+    let ubulk = ubulk.context("no .ubulk bulk data")?;
+    let data = chunk.read()?;
+    let header_end = zen_header_end(&data, versions)?;
+
+    let out_path = texture_output_path(opts.output, path);
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let info =
+        texture::extract_texture(&data, &ubulk.read()?, header_end, &out_path, opts.mip_level)?;
+    if opts.metadata {
+        info.metadata().write(&out_path.with_extension("json"))?;
+    }
+    Ok(())
+}
+
+/// Byte offset where export data begins, just past the Zen package header
+fn zen_header_end(data: &[u8], versions: StoreVersions) -> Result<usize> {
+    let mut cursor = Cursor::new(data);
+    FZenPackageHeader::deserialize(
+        &mut cursor,
+        None,
+        versions.toc,
+        versions.container_header,
+        None,
+    )?;
+    Ok(cursor.position() as usize)
+}
+
+/// IoStore path of the `.ubulk` chunk holding an asset's mips
+fn bulk_path(asset_path: &str) -> String {
+    let stem = asset_path.strip_suffix(".uasset").unwrap_or(asset_path);
+    format!("{}.ubulk", stem)
+}
+
+/// Where a texture asset's PNG lands under the output directory
+fn texture_output_path(output: &Path, asset_path: &str) -> PathBuf {
+    output
+        .join(relative_asset_path(asset_path))
+        .with_extension("png")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_path() {
+        assert_eq!(
+            bulk_path("../../../OakGame/Content/T_Gun_D.uasset"),
+            "../../../OakGame/Content/T_Gun_D.ubulk"
+        );
+    }
+
+    #[test]
+    fn test_texture_output_path() {
+        assert_eq!(
+            texture_output_path(
+                Path::new("out"),
+                "../../../OakGame/Content/UI/T_Icon_D.uasset"
+            ),
+            Path::new("out/OakGame/Content/UI/T_Icon_D.png")
+        );
+    }
+}
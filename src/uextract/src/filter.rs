@@ -2,41 +2,70 @@
 
 use crate::cli::Args;
 
-/// Check if a path matches the configured filters
-pub fn matches_filters(path: &str, args: &Args) -> bool {
-    // Check excludes first
-    for pattern in &args.exclude {
-        if glob_match::glob_match(pattern, path) {
-            return false;
+/// Glob/substring filter set shared by extraction and listing commands
+pub struct PathFilters<'a> {
+    /// Glob patterns to include
+    pub select: &'a [String],
+    /// Substrings to include
+    pub filter: &'a [String],
+    /// Case-insensitive substrings to include
+    pub ifilter: &'a [String],
+    /// Glob patterns to exclude (checked first)
+    pub exclude: &'a [String],
+}
+
+impl<'a> PathFilters<'a> {
+    /// Use the top-level extraction filters
+    pub fn from_args(args: &'a Args) -> Self {
+        Self {
+            select: &args.select,
+            filter: &args.filter,
+            ifilter: &args.ifilter,
+            exclude: &args.exclude,
         }
     }
 
-    // If no positive filters, match all
-    if args.select.is_empty() && args.filter.is_empty() && args.ifilter.is_empty() {
-        return true;
-    }
+    /// Check if a path matches the filters
+    pub fn matches(&self, path: &str) -> bool {
+        // Check excludes first
+        for pattern in self.exclude {
+            if glob_match::glob_match(pattern, path) {
+                return false;
+            }
+        }
 
-    // Check select patterns (glob)
-    for pattern in &args.select {
-        if glob_match::glob_match(pattern, path) {
+        // If no positive filters, match all
+        if self.select.is_empty() && self.filter.is_empty() && self.ifilter.is_empty() {
             return true;
         }
-    }
 
-    // Check filter (substring)
-    for f in &args.filter {
-        if path.contains(f) {
-            return true;
+        // Check select patterns (glob)
+        for pattern in self.select {
+            if glob_match::glob_match(pattern, path) {
+                return true;
+            }
         }
-    }
 
-    // Check ifilter (case-insensitive substring)
-    let path_lower = path.to_lowercase();
-    for f in &args.ifilter {
-        if path_lower.contains(&f.to_lowercase()) {
-            return true;
+        // Check filter (substring)
+        for f in self.filter {
+            if path.contains(f.as_str()) {
+                return true;
+            }
+        }
+
+        // Check ifilter (case-insensitive substring)
+        let path_lower = path.to_lowercase();
+        for f in self.ifilter {
+            if path_lower.contains(&f.to_lowercase()) {
+                return true;
+            }
         }
+
+        false
     }
+}
 
-    false
+/// Check if a path matches the configured filters
+pub fn matches_filters(path: &str, args: &Args) -> bool {
+    PathFilters::from_args(args).matches(path)
 }
@@ -13,4 +13,5 @@ pub mod property;
 pub mod scanner;
 pub mod texture;
 pub mod types;
+pub mod utoc;
 pub mod zen;
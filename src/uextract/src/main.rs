@@ -17,7 +17,7 @@ mod cli;
 mod filter;
 
 use cli::{Args, Commands, OutputFormat};
-use filter::matches_filters;
+use filter::{matches_filters, PathFilters};
 use uextract::commands;
 use uextract::zen::parse_zen_to_json;
 
@@ -49,6 +49,7 @@ fn main() -> Result<()> {
                 output,
                 mip,
                 format,
+                metadata,
             } => commands::extract_texture_cmd(&commands::ExtractTextureOptions {
                 ubulk_path: &ubulk,
                 width,
@@ -56,7 +57,36 @@ fn main() -> Result<()> {
                 output_path: &output,
                 mip_level: mip,
                 format: &format,
+                metadata_path: metadata.as_deref(),
             }),
+            Commands::ExtractTextures {
+                input,
+                out,
+                mip,
+                select,
+                filter,
+                ifilter,
+                exclude,
+                aes_key,
+                metadata,
+            } => {
+                let filters = PathFilters {
+                    select: &select,
+                    filter: &filter,
+                    ifilter: &ifilter,
+                    exclude: &exclude,
+                };
+                commands::extract_textures(
+                    &commands::ExtractTexturesOptions {
+                        input: &input,
+                        output: &out,
+                        mip_level: mip,
+                        aes_key: aes_key.as_deref(),
+                        metadata,
+                    },
+                    |path| filters.matches(path),
+                )
+            }
             Commands::ScriptObjects {
                 input,
                 output,
@@ -65,22 +95,77 @@ fn main() -> Result<()> {
             Commands::FindByClass {
                 input,
                 class_name,
+                name_glob,
                 scriptobjects,
                 aes_key,
                 output,
-            } => commands::find_assets_by_class(
-                &input,
-                &class_name,
-                &scriptobjects,
-                aes_key.as_deref(),
-                output.as_deref(),
-            ),
+                unique,
+                extract_to,
+                checkpoint,
+                strip_prefix,
+                no_extension,
+            } => commands::find_assets(&commands::FindAssetsOptions {
+                input: &input,
+                class_name: class_name.as_deref(),
+                name_globs: &name_glob,
+                scriptobjects: &scriptobjects,
+                aes_key: aes_key.as_deref(),
+                output: output.as_deref(),
+                unique,
+                extract_to: extract_to.as_deref(),
+                checkpoint: checkpoint.as_deref(),
+                strip_prefix: strip_prefix.as_deref(),
+                no_extension,
+            }),
+            Commands::DumpAsset {
+                input,
+                asset_path,
+                out,
+                usmap,
+                scriptobjects,
+                aes_key,
+            } => commands::dump_asset(&commands::DumpAssetOptions {
+                input: &input,
+                asset_path: &asset_path,
+                output: out.as_deref(),
+                usmap: usmap.as_deref(),
+                scriptobjects: scriptobjects.as_deref(),
+                aes_key: aes_key.as_deref(),
+            }),
+            Commands::ListFiles {
+                input,
+                json,
+                select,
+                filter,
+                ifilter,
+                exclude,
+                aes_key,
+            } => {
+                let filters = PathFilters {
+                    select: &select,
+                    filter: &filter,
+                    ifilter: &ifilter,
+                    exclude: &exclude,
+                };
+                commands::list_files(&input, aes_key.as_deref(), json, |path| {
+                    filters.matches(path)
+                })
+            }
             Commands::ListClasses {
                 input,
                 scriptobjects,
                 aes_key,
                 samples,
-            } => commands::list_classes(&input, &scriptobjects, aes_key.as_deref(), samples),
+                checkpoint,
+                csv,
+            } => commands::list_classes(&commands::ListClassesOptions {
+                input: &input,
+                scriptobjects: &scriptobjects,
+                aes_key: aes_key.as_deref(),
+                samples,
+                checkpoint: checkpoint.as_deref(),
+                csv: csv.as_deref(),
+            }),
         };
     }
 
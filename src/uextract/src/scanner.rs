@@ -37,6 +37,14 @@ pub struct RawExport {
     pub data: Vec<u8>,
 }
 
+/// Whether `path` is `wanted` or ends with `/` followed by `wanted`.
+fn ends_with_path(path: &str, wanted: &str) -> bool {
+    match path.strip_suffix(wanted) {
+        Some(rest) => rest.is_empty() || rest.ends_with('/'),
+        None => false,
+    }
+}
+
 /// Extract matching raw exports from a parsed Zen package header.
 fn extract_raw_exports(
     header: &FZenPackageHeader,
@@ -241,6 +249,35 @@ impl IoStoreScanner {
         Ok(paths)
     }
 
+    /// Read a single asset's raw bytes by path.
+    ///
+    /// Matches the exact IoStore path first, then falls back to a path that ends
+    /// with `asset_path` at a `/` (so `OakGame/Content/...` matches
+    /// `../../../OakGame/Content/...`, but `A.uasset` doesn't match `BA.uasset`).
+    /// Returns the full IoStore path alongside the data.
+    pub fn read_asset(&self, asset_path: &str) -> Result<(String, Vec<u8>)> {
+        let wanted = asset_path.trim_start_matches('/');
+        let mut suffix_match = None;
+
+        for chunk in self.store.chunks() {
+            let Some(path) = chunk.path() else {
+                continue;
+            };
+            if path == asset_path {
+                let data = chunk.read()?;
+                return Ok((path, data));
+            }
+            if suffix_match.is_none() && ends_with_path(&path, wanted) {
+                suffix_match = Some((chunk, path));
+            }
+        }
+
+        let (chunk, path) =
+            suffix_match.with_context(|| format!("Asset not found: {}", asset_path))?;
+        let data = chunk.read()?;
+        Ok((path, data))
+    }
+
     /// Parse a single asset's raw data into structured form.
     pub fn parse_asset_data(&self, data: &[u8], path: &str) -> Result<ZenAssetInfo> {
         parse_zen_asset(
@@ -394,3 +431,18 @@ impl IoStoreScanner {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ends_with_path() {
+        let path = "../../../OakGame/Content/Gear/A.uasset";
+        assert!(ends_with_path(path, "OakGame/Content/Gear/A.uasset"));
+        assert!(ends_with_path(path, "A.uasset"));
+        assert!(ends_with_path("A.uasset", "A.uasset"));
+        assert!(!ends_with_path("../Gear/BA.uasset", "A.uasset"));
+        assert!(!ends_with_path(path, "Content/Gear/B.uasset"));
+    }
+}
@@ -5,6 +5,7 @@
 
 use anyhow::{bail, Context, Result};
 use byteorder::{LittleEndian, ReadBytesExt};
+use serde::Serialize;
 use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
 
@@ -79,6 +80,64 @@ pub struct TextureInfo {
     pub ubulk_offset: u64, // Total offset to first mip in ubulk
 }
 
+impl TextureInfo {
+    /// Dimensions, format and mip sizes from the parsed platform data
+    pub fn metadata(&self) -> TextureMetadata {
+        TextureMetadata {
+            width: self.width,
+            height: self.height,
+            format: self.format_name.clone(),
+            mip_count: self.mips.len(),
+            mip_sizes: self.mips.iter().map(|m| m.data_size).collect(),
+        }
+    }
+}
+
+/// Mip chain summary, written as a JSON sidecar next to an extracted PNG
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextureMetadata {
+    pub width: u32,
+    pub height: u32,
+    /// Pixel format name (`PF_BC7`, or the format given for a raw `.ubulk`)
+    pub format: String,
+    pub mip_count: usize,
+    /// Byte size of each mip, largest first
+    pub mip_sizes: Vec<u64>,
+}
+
+impl TextureMetadata {
+    /// Write as pretty-printed JSON
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Byte sizes of a block-compressed mip chain, largest first
+///
+/// Halves each dimension down to 1x1, stopping early at the first mip that
+/// doesn't fit in `available` bytes, so for a `.ubulk` it lists the mips
+/// the file actually holds (the smallest ones usually live in the .uasset).
+pub fn mip_chain_sizes(width: u32, height: u32, bytes_per_block: u64, available: u64) -> Vec<u64> {
+    let (mut w, mut h) = (width.max(1), height.max(1));
+    let mut sizes = Vec::new();
+    let mut total = 0u64;
+    loop {
+        let size = u64::from(w).div_ceil(4) * u64::from(h).div_ceil(4) * bytes_per_block;
+        if total + size > available {
+            break;
+        }
+        total += size;
+        sizes.push(size);
+        if w == 1 && h == 1 {
+            break;
+        }
+        w = (w / 2).max(1);
+        h = (h / 2).max(1);
+    }
+    sizes
+}
+
 /// Parse texture metadata from the cooked serial data within a .uasset
 ///
 /// This parses FTexturePlatformData structure which contains:
@@ -326,13 +385,16 @@ pub fn save_png(rgba_data: &[u8], width: u32, height: u32, path: &Path) -> Resul
 }
 
 /// Extract texture from .uasset and .ubulk files
+///
+/// Returns the parsed platform data, for callers that also want its
+/// [`TextureInfo::metadata`].
 pub fn extract_texture(
     uasset_data: &[u8],
     ubulk_data: &[u8],
     header_size: usize,
     output_path: &Path,
     mip_level: usize, // 0 = highest resolution
-) -> Result<()> {
+) -> Result<TextureInfo> {
     let info = parse_texture_info(uasset_data, header_size)?;
 
     if info.mips.is_empty() {
@@ -369,7 +431,7 @@ pub fn extract_texture(
     // Save as PNG
     save_png(&rgba, mip.width, mip.height, output_path)?;
 
-    Ok(())
+    Ok(info)
 }
 
 #[cfg(test)]
@@ -383,4 +445,58 @@ mod tests {
         assert_eq!(PixelFormat::from_name("PF_DXT5"), PixelFormat::BC3);
         assert_eq!(PixelFormat::from_name("unknown"), PixelFormat::Unknown);
     }
+
+    #[test]
+    fn test_mip_chain_sizes() {
+        // 8x4 BC7: 2x1 blocks, then 4x2 and 2x1 (one block each), then 1x1
+        assert_eq!(mip_chain_sizes(8, 4, 16, u64::MAX), vec![32, 16, 16, 16]);
+        // Only what fits is listed
+        assert_eq!(mip_chain_sizes(8, 4, 16, 50), vec![32, 16]);
+        assert!(mip_chain_sizes(8, 4, 16, 0).is_empty());
+        // BC1 blocks are 8 bytes
+        assert_eq!(mip_chain_sizes(16, 16, 8, u64::MAX), vec![128, 32, 8, 8, 8]);
+    }
+
+    #[test]
+    fn test_texture_info_metadata() {
+        let info = TextureInfo {
+            width: 8,
+            height: 4,
+            format: PixelFormat::BC7,
+            format_name: "PF_BC7".to_string(),
+            num_slices: 1,
+            mips: vec![
+                TextureMip {
+                    width: 8,
+                    height: 4,
+                    depth: 1,
+                    data_size: 32,
+                    data_offset: 0,
+                },
+                TextureMip {
+                    width: 4,
+                    height: 2,
+                    depth: 1,
+                    data_size: 16,
+                    data_offset: 32,
+                },
+            ],
+            ubulk_offset: 0,
+        };
+
+        let metadata = info.metadata();
+        assert_eq!(metadata.format, "PF_BC7");
+        assert_eq!(metadata.mip_count, 2);
+        assert_eq!(metadata.mip_sizes, vec![32, 16]);
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::json!({
+                "width": 8,
+                "height": 4,
+                "format": "PF_BC7",
+                "mip_count": 2,
+                "mip_sizes": [32, 16],
+            })
+        );
+    }
 }
@@ -0,0 +1,260 @@
+//! IoStore container (.utoc) header metadata
+//!
+//! retoc decrypts and decompresses chunks transparently. This reads the
+//! container flags, encryption key GUID and compression method names from
+//! the TOC header so listings can show why a chunk fails to read, and each
+//! chunk's length so they can report sizes without reading the chunks.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const TOC_MAGIC: &[u8; 16] = b"-==--==--==--==-";
+
+/// Bytes of `FIoStoreTocHeader` this reader looks at
+const MIN_HEADER_SIZE: usize = 100;
+
+const FLAG_COMPRESSED: u8 = 1 << 0;
+const FLAG_ENCRYPTED: u8 = 1 << 1;
+
+/// First TOC version with perfect hash seeds after the offset/length table
+const VERSION_PERFECT_HASH: u8 = 4;
+/// First TOC version with an overflow index list after the seeds
+const VERSION_PERFECT_HASH_WITH_OVERFLOW: u8 = 5;
+
+/// Per-entry sizes of the tables between the header and the method names
+const CHUNK_ID_SIZE: usize = 12;
+const OFFSET_LENGTH_SIZE: usize = 10;
+
+/// Container-wide settings and chunk lengths from a .utoc
+///
+/// Encryption applies to a whole container, so every chunk in it shares
+/// these values. `compressed` means the container stores compressed blocks;
+/// individual chunks may still be stored uncompressed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TocInfo {
+    pub compressed: bool,
+    pub encrypted: bool,
+    /// Encryption key GUID as 32 hex digits (all zeros when unencrypted)
+    pub key_guid: String,
+    /// Compression method names, e.g. "Oodle"
+    pub compression_methods: Vec<String>,
+    /// Uncompressed length of each chunk, keyed by its raw 12-byte chunk ID
+    pub chunk_sizes: HashMap<[u8; 12], u64>,
+}
+
+impl TocInfo {
+    /// Read the header of a .utoc file
+    pub fn read(path: &Path) -> Result<Self> {
+        let data = std::fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+        Self::parse(&data).with_context(|| format!("Invalid .utoc header in {:?}", path))
+    }
+
+    /// Parse .utoc file contents
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        anyhow::ensure!(
+            data.len() >= MIN_HEADER_SIZE && data.starts_with(TOC_MAGIC),
+            "missing TOC magic"
+        );
+
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+        };
+        let version = data[16];
+        let header_size = u32_at(20);
+        let entry_count = u32_at(24);
+        let block_count = u32_at(28);
+        let block_entry_size = u32_at(32);
+        let method_count = u32_at(36);
+        let method_name_len = u32_at(40);
+        let flags = data[80];
+
+        let chunk_sizes = read_chunk_sizes(data, header_size, entry_count)?;
+
+        let mut pos = header_size + entry_count * (CHUNK_ID_SIZE + OFFSET_LENGTH_SIZE);
+        if version >= VERSION_PERFECT_HASH {
+            pos += u32_at(84) * 4;
+        }
+        if version >= VERSION_PERFECT_HASH_WITH_OVERFLOW {
+            pos += u32_at(96) * 4;
+        }
+        pos += block_count * block_entry_size;
+
+        let compression_methods = (0..method_count)
+            .map(|i| {
+                let start = pos + i * method_name_len;
+                let name = data
+                    .get(start..start + method_name_len)
+                    .context("compression method names past end of file")?;
+                let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+                Ok(String::from_utf8_lossy(&name[..end]).into_owned())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            compressed: flags & FLAG_COMPRESSED != 0,
+            encrypted: flags & FLAG_ENCRYPTED != 0,
+            key_guid: format_guid(&data[64..80]),
+            compression_methods,
+            chunk_sizes,
+        })
+    }
+}
+
+/// Chunk lengths from the chunk ID and offset/length tables after the header
+///
+/// Each `FIoOffsetAndLength` is a 40-bit big-endian offset followed by a
+/// 40-bit big-endian length, both into the uncompressed container.
+fn read_chunk_sizes(
+    data: &[u8],
+    header_size: usize,
+    entry_count: usize,
+) -> Result<HashMap<[u8; 12], u64>> {
+    let ids_end = header_size + entry_count * CHUNK_ID_SIZE;
+    let lengths_end = ids_end + entry_count * OFFSET_LENGTH_SIZE;
+    let ids = data
+        .get(header_size..ids_end)
+        .context("chunk IDs past end of file")?;
+    let lengths = data
+        .get(ids_end..lengths_end)
+        .context("chunk offsets and lengths past end of file")?;
+
+    Ok(ids
+        .chunks_exact(CHUNK_ID_SIZE)
+        .zip(lengths.chunks_exact(OFFSET_LENGTH_SIZE))
+        .map(|(id, entry)| {
+            let length = entry[5..].iter().fold(0u64, |n, &b| n << 8 | u64::from(b));
+            (id.try_into().unwrap(), length)
+        })
+        .collect())
+}
+
+impl std::fmt::Display for TocInfo {
+    /// e.g. "encrypted (key 1234...), compressed (Oodle)"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if self.encrypted {
+            parts.push(format!("encrypted (key {})", self.key_guid));
+        }
+        if self.compressed {
+            parts.push(format!(
+                "compressed ({})",
+                self.compression_methods.join(", ")
+            ));
+        }
+        if parts.is_empty() {
+            parts.push("unencrypted, uncompressed".to_string());
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Format an FGuid (four little-endian u32s) the way Unreal prints it
+fn format_guid(bytes: &[u8]) -> String {
+    bytes
+        .chunks_exact(4)
+        .map(|part| format!("{:08X}", u32::from_le_bytes(part.try_into().unwrap())))
+        .collect()
+}
+
+/// Path of a container's .utoc, given the path passed to `iostore::open`
+///
+/// `input` is either the Paks directory or one of its .utoc files.
+pub fn toc_path(input: &Path, container_name: &str) -> PathBuf {
+    let dir = if input.is_dir() {
+        input
+    } else {
+        input.parent().unwrap_or(input)
+    };
+    dir.join(format!("{}.utoc", container_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A version 8 header with no chunks and the given methods
+    fn toc(flags: u8, methods: &[&str]) -> Vec<u8> {
+        let mut data = vec![0u8; 144];
+        data[..16].copy_from_slice(TOC_MAGIC);
+        data[16] = 8;
+        data[20..24].copy_from_slice(&144u32.to_le_bytes());
+        data[28..32].copy_from_slice(&2u32.to_le_bytes());
+        data[32..36].copy_from_slice(&12u32.to_le_bytes());
+        data[36..40].copy_from_slice(&(methods.len() as u32).to_le_bytes());
+        data[40..44].copy_from_slice(&32u32.to_le_bytes());
+        data[64..68].copy_from_slice(&0x1234_5678u32.to_le_bytes());
+        data[80] = flags;
+
+        data.extend_from_slice(&[0xAA; 24]); // two compression blocks
+        for method in methods {
+            let mut name = method.as_bytes().to_vec();
+            name.resize(32, 0);
+            data.extend_from_slice(&name);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_encrypted_compressed() {
+        let info = TocInfo::parse(&toc(FLAG_COMPRESSED | FLAG_ENCRYPTED, &["Oodle"])).unwrap();
+        assert!(info.compressed);
+        assert!(info.encrypted);
+        assert_eq!(info.key_guid, "12345678000000000000000000000000");
+        assert_eq!(info.compression_methods, vec!["Oodle"]);
+    }
+
+    #[test]
+    fn test_display() {
+        let info = TocInfo::parse(&toc(FLAG_COMPRESSED | FLAG_ENCRYPTED, &["Oodle"])).unwrap();
+        assert_eq!(
+            info.to_string(),
+            "encrypted (key 12345678000000000000000000000000), compressed (Oodle)"
+        );
+        assert_eq!(TocInfo::default().to_string(), "unencrypted, uncompressed");
+    }
+
+    #[test]
+    fn test_parse_plain() {
+        let info = TocInfo::parse(&toc(0, &[])).unwrap();
+        assert!(!info.compressed);
+        assert!(!info.encrypted);
+        assert!(info.compression_methods.is_empty());
+    }
+
+    #[test]
+    fn test_parse_chunk_sizes() {
+        let mut data = toc(0, &[]);
+        // Two entries: chunk IDs, then offsets and lengths, before the blocks
+        data[24..28].copy_from_slice(&2u32.to_le_bytes());
+        let mut tables = Vec::new();
+        tables.extend_from_slice(&[1; 12]);
+        tables.extend_from_slice(&[2; 12]);
+        tables.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x00]);
+        tables.extend_from_slice(&[0, 0, 0, 0x01, 0x00, 0x01, 0, 0, 0, 0x2A]);
+        data.splice(144..144, tables);
+
+        let info = TocInfo::parse(&data).unwrap();
+        assert_eq!(info.chunk_sizes.len(), 2);
+        assert_eq!(info.chunk_sizes[&[1; 12]], 256);
+        assert_eq!(info.chunk_sizes[&[2; 12]], (1 << 32) + 42);
+        assert!(TocInfo::parse(&toc(0, &[])).unwrap().chunk_sizes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(TocInfo::parse(b"not a toc").is_err());
+
+        let mut truncated = toc(FLAG_COMPRESSED, &["Oodle"]);
+        truncated.truncate(truncated.len() - 8);
+        assert!(TocInfo::parse(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_toc_path_from_file() {
+        assert_eq!(
+            toc_path(Path::new("/nonexistent/Paks/global.utoc"), "pakchunk0"),
+            Path::new("/nonexistent/Paks/pakchunk0.utoc")
+        );
+    }
+}